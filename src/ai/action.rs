@@ -5,5 +5,6 @@ pub enum Action {
     Attack(Entity),
     MoveTo(usize),
     Chase(usize),
-    OpenDoor(Entity)
+    OpenDoor(Entity),
+    Fire(usize),
 }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum AmmunitionType {
+    Arrow,
+    Bolt,
+    Stone,
+    ThrowingDagger,
+}
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Ammunition {
+    pub ammunition_type: AmmunitionType,
+}
@@ -10,6 +10,8 @@ pub enum DamageType {
     Burn,
     Crush,
     Pierce,
+    Choke,
+    Blast,
 }
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
 pub struct CausesDamage {
@@ -1,9 +1,29 @@
+use rltk::RGB;
 use serde::{Deserialize, Serialize};
 use specs::{Component, DenseVecStorage};
 
+// lights with less fuel than this start to dim, shrinking a little every few turns.
+pub const LIGHT_FADE_TURNS: u32 = 50;
+
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
 pub struct CausesLight {
     pub radius: usize,
+    pub color: RGB,
     pub lit: bool,
     pub turns_remaining: Option<u32>,
 }
+
+impl CausesLight {
+    pub fn get_current_radius(&self) -> usize {
+        if !self.lit {
+            return 0;
+        }
+        match self.turns_remaining {
+            Some(turns) if turns < LIGHT_FADE_TURNS => {
+                let scaled = self.radius as f32 * turns as f32 / LIGHT_FADE_TURNS as f32;
+                usize::max(scaled.ceil() as usize, 1)
+            }
+            _ => self.radius,
+        }
+    }
+}
@@ -27,6 +27,8 @@ impl DamageHistory {
                 DamageType::Stab => "stabbed",
                 DamageType::Pierce => "pierced",
                 DamageType::Crush => "crushed",
+                DamageType::Choke => "choked",
+                DamageType::Blast => "blasted",
             })
             .collect();
         if terms.len() > 1 {
@@ -7,7 +7,9 @@ use specs::{
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum DoorState {
     Opened,
-    Closed
+    Closed,
+    // locked doors open for whoever carries the key with the matching id.
+    Locked(u32),
 }
 #[derive(Component, ConvertSaveload, Clone, Debug)]
 pub struct Door {
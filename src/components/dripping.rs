@@ -0,0 +1,9 @@
+use crate::dungeon::surface_type::SurfaceType;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Dripping {
+    pub surface_type: SurfaceType,
+    pub steps: u32,
+}
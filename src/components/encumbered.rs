@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Encumbered {
+    // turns still owed for moving while over-burdened, spent before the entity can act again
+    pub turns_owed: u32,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Explosive {
+    pub radius: u32,
+    pub damage: i32,
+}
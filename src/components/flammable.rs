@@ -1,3 +1,4 @@
+use crate::types::Material;
 use serde::{Deserialize, Serialize};
 use specs::{
     error::NoError,
@@ -8,4 +9,5 @@ use specs::{
 #[derive(Component, ConvertSaveload, Clone, Debug)]
 pub struct Flammable {
     pub turns_remaining: u8,
+    pub material: Material,
 }
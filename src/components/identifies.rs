@@ -0,0 +1,5 @@
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Identifies {}
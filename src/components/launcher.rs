@@ -0,0 +1,11 @@
+use crate::components::ammunition::AmmunitionType;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Launcher {
+    pub ammunition_type: AmmunitionType,
+    pub range: u32,
+    pub accuracy: i32,
+    pub bonus: i32,
+}
@@ -0,0 +1,8 @@
+use crate::types::MechanismType;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Mechanism {
+    pub mechanism_type: MechanismType,
+}
@@ -1,7 +1,8 @@
+pub mod activated;
+pub mod ammunition;
 pub mod area_of_effect;
 pub mod armable;
 pub mod blocks_tile;
-pub mod blood;
 pub mod causes_damage;
 pub mod causes_fire;
 pub mod causes_light;
@@ -14,21 +15,29 @@ pub mod damage_history;
 pub mod disarmable;
 pub mod door;
 pub mod dousable;
+pub mod dripping;
+pub mod encumbered;
 pub mod entity_moved;
 pub mod entry_trigger;
 pub mod equipable;
 pub mod equipment;
+pub mod explosive;
 pub mod flammable;
+pub mod fuel;
 pub mod furniture;
 pub mod grabbable;
 pub mod grabbing;
 pub mod hidden;
 pub mod hiding;
 pub mod hiding_spot;
+pub mod identifies;
 pub mod info;
 pub mod inventory;
 pub mod item;
+pub mod key;
+pub mod launcher;
 pub mod lightable;
+pub mod mechanism;
 pub mod memory;
 pub mod monster;
 pub mod name;
@@ -38,36 +47,50 @@ pub mod particle_lifetime;
 pub mod player;
 pub mod position;
 pub mod potion;
+pub mod provides_block;
 pub mod provides_healing;
 pub mod ranged;
+pub mod refillable;
 pub mod renderable;
 pub mod saveable;
 pub mod serialization_helper;
 pub mod single_activation;
+pub mod stackable;
 pub mod suffer_damage;
 pub mod trap;
 pub mod triggered;
+pub mod two_handed;
 pub mod viewshed;
 pub mod wants_to_close_door;
 pub mod wants_to_disarm_trap;
 pub mod wants_to_douse;
 pub mod wants_to_drop_item;
 pub mod wants_to_equip;
+pub mod wants_to_fire;
 pub mod wants_to_grab;
 pub mod wants_to_hide;
 pub mod wants_to_light;
 pub mod wants_to_melee;
 pub mod wants_to_move;
 pub mod wants_to_open_door;
+pub mod wants_to_pick_lock;
 pub mod wants_to_pick_up_item;
+pub mod wants_to_refuel;
 pub mod wants_to_release_grabbed;
 pub mod wants_to_search_hidden;
+pub mod wants_to_smash;
+pub mod wants_to_study;
+pub mod wants_to_throw;
 pub mod wants_to_trap;
 pub mod wants_to_use;
+pub mod weight;
+pub mod wired_to;
+pub mod workstation;
+pub use activated::Activated;
+pub use ammunition::Ammunition;
 pub use area_of_effect::AreaOfEffect;
 pub use armable::Armable;
 pub use blocks_tile::BlocksTile;
-pub use blood::Blood;
 pub use causes_damage::CausesDamage;
 pub use causes_fire::CausesFire;
 pub use causes_light::CausesLight;
@@ -80,21 +103,29 @@ pub use damage_history::DamageHistory;
 pub use disarmable::Disarmable;
 pub use door::Door;
 pub use dousable::Dousable;
+pub use dripping::Dripping;
+pub use encumbered::Encumbered;
 pub use entity_moved::EntityMoved;
 pub use entry_trigger::EntryTrigger;
 pub use equipable::Equipable;
 pub use equipment::Equipment;
+pub use explosive::Explosive;
 pub use flammable::Flammable;
+pub use fuel::Fuel;
 pub use furniture::Furniture;
 pub use grabbable::Grabbable;
 pub use grabbing::Grabbing;
 pub use hidden::Hidden;
 pub use hiding::Hiding;
 pub use hiding_spot::HidingSpot;
+pub use identifies::Identifies;
 pub use info::Info;
 pub use inventory::Inventory;
 pub use item::Item;
+pub use key::Key;
+pub use launcher::Launcher;
 pub use lightable::Lightable;
+pub use mechanism::Mechanism;
 pub use memory::Memory;
 pub use monster::Monster;
 pub use name::Name;
@@ -104,29 +135,42 @@ pub use particle_lifetime::ParticleLifetime;
 pub use player::Player;
 pub use position::Position;
 pub use potion::Potion;
+pub use provides_block::ProvidesBlock;
 pub use provides_healing::ProvidesHealing;
 pub use ranged::Ranged;
+pub use refillable::Refillable;
 pub use renderable::Renderable;
 pub use saveable::Saveable;
 pub use serialization_helper::SerializationHelper;
 pub use single_activation::SingleActivation;
+pub use stackable::Stackable;
 pub use suffer_damage::SufferDamage;
 pub use trap::Trap;
 pub use triggered::Triggered;
+pub use two_handed::TwoHanded;
 pub use viewshed::Viewshed;
 pub use wants_to_close_door::WantsToCloseDoor;
 pub use wants_to_disarm_trap::WantsToDisarmTrap;
 pub use wants_to_douse::WantsToDouse;
 pub use wants_to_drop_item::WantsToDropItem;
 pub use wants_to_equip::WantsToEquip;
+pub use wants_to_fire::WantsToFire;
 pub use wants_to_grab::WantsToGrab;
 pub use wants_to_hide::WantsToHide;
 pub use wants_to_light::WantsToLight;
 pub use wants_to_melee::WantsToMelee;
 pub use wants_to_move::WantsToMove;
 pub use wants_to_open_door::WantsToOpenDoor;
+pub use wants_to_pick_lock::WantsToPickLock;
 pub use wants_to_pick_up_item::WantsToPickUpItem;
+pub use wants_to_refuel::WantsToRefuel;
 pub use wants_to_release_grabbed::WantsToReleaseGrabbed;
 pub use wants_to_search_hidden::WantsToSearchHidden;
+pub use wants_to_smash::WantsToSmash;
+pub use wants_to_study::WantsToStudy;
+pub use wants_to_throw::WantsToThrow;
 pub use wants_to_trap::WantsToTrap;
 pub use wants_to_use::WantsToUse;
+pub use weight::Weight;
+pub use wired_to::WiredTo;
+pub use workstation::Workstation;
@@ -4,6 +4,7 @@ use specs::{Component, DenseVecStorage};
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum MonsterSpecies {
     Goblin,
+    Duergar,
 }
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
 pub struct Monster {
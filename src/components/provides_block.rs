@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct ProvidesBlock {
+    pub chance: i32,
+}
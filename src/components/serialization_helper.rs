@@ -1,4 +1,5 @@
 use crate::dungeon::dungeon::Dungeon;
+use crate::identification::Identification;
 use serde::{Deserialize, Serialize};
 use specs::{
   error::NoError,
@@ -9,4 +10,5 @@ use specs::{
 #[derive(Component, ConvertSaveload, Debug, Clone)]
 pub struct SerializationHelper {
   pub dungeon: Dungeon,
+  pub identification: Identification,
 }
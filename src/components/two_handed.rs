@@ -2,4 +2,4 @@ use serde::{Deserialize, Serialize};
 use specs::{Component, DenseVecStorage};
 
 #[derive(Component, Serialize, Deserialize, Clone, Debug)]
-pub struct Blood {}
+pub struct TwoHanded {}
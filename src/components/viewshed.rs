@@ -1,10 +1,13 @@
 use std::collections::HashSet;
+use std::ops::Deref;
 
+use super::Position;
 use serde::{Deserialize, Serialize};
 use specs::{
   error::NoError,
   saveload::{ConvertSaveload, Marker},
-  Component, DenseVecStorage, Entity,
+  storage::MaskedStorage,
+  Component, DenseVecStorage, Entity, Join, Storage, WriteStorage,
 };
 
 #[derive(Component, ConvertSaveload, Clone, Debug)]
@@ -14,3 +17,18 @@ pub struct Viewshed {
   pub range: u32,
   pub dirty: bool,
 }
+
+// everyone on a level where something changed what can be seen has to look again.
+pub fn mark_dirty_on_levels<D: Deref<Target = MaskedStorage<Position>>>(
+  levels: &HashSet<u8>,
+  positions: &Storage<Position, D>,
+  viewsheds: &mut WriteStorage<Viewshed>,
+) {
+  if levels.is_empty() {
+    return;
+  }
+  (positions, viewsheds)
+    .join()
+    .filter(|(p, _)| levels.contains(&p.level))
+    .for_each(|(_, v)| v.dirty = true);
+}
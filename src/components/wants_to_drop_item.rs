@@ -3,4 +3,6 @@ use specs::{Component, DenseVecStorage, Entity};
 #[derive(Component, Debug, Clone)]
 pub struct WantsToDropItem {
     pub item: Entity,
+    // otherwise just one is split off the stack and dropped.
+    pub whole_stack: bool,
 }
@@ -0,0 +1,6 @@
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Clone, Debug)]
+pub struct WantsToFire {
+    pub target: usize,
+}
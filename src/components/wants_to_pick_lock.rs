@@ -0,0 +1,6 @@
+use specs::{Component, DenseVecStorage, Entity};
+
+#[derive(Component, Clone, Debug)]
+pub struct WantsToPickLock {
+    pub door: Entity,
+}
@@ -5,4 +5,6 @@ use specs::{Component, DenseVecStorage, Entity};
 pub struct WantsToPickUpItem {
     pub container: Option<Entity>,
     pub items: HashSet<Entity>,
+    // otherwise just one is split off each stack and picked up.
+    pub whole_stack: bool,
 }
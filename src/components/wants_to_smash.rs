@@ -0,0 +1,6 @@
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Clone, Debug)]
+pub struct WantsToSmash {
+    pub idx: usize,
+}
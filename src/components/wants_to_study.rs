@@ -0,0 +1,4 @@
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Clone, Debug)]
+pub struct WantsToStudy {}
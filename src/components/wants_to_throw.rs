@@ -0,0 +1,7 @@
+use specs::{Component, DenseVecStorage, Entity};
+
+#[derive(Component, Clone, Debug)]
+pub struct WantsToThrow {
+    pub item: Entity,
+    pub target: usize,
+}
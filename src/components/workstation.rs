@@ -0,0 +1,8 @@
+use crate::types::WorkstationType;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DenseVecStorage};
+
+#[derive(Component, Serialize, Deserialize, Clone, Debug)]
+pub struct Workstation {
+    pub workstation_type: WorkstationType,
+}
@@ -19,6 +19,8 @@ pub const CTA_INTERACT_OPEN_CONTAINER: &str =
     "Press LEFT and RIGHT to select a container to open, ESC to cancel";
 pub const CTA_INTERACT_OPEN_DOOR: &str =
     "Press LEFT and RIGHT to select a door to open, ESC to cancel";
+pub const CTA_INTERACT_CRAFT: &str =
+    "Press LEFT and RIGHT to select a workstation to craft at, ESC to cancel";
 pub const MENU_OPTION_DISARM: &str = "Disarm Trap";
 pub const MENU_OPTION_ARM: &str = "Arm Trap";
 pub const MENU_OPTION_DOUSE: &str = "Douse";
@@ -30,3 +32,5 @@ pub const MENU_OPTION_PICKUP: &str = "Pickup";
 pub const MENU_OPTION_OPEN: &str = "Open";
 pub const MENU_OPTION_OPEN_DOOR: &str = "Open Door";
 pub const MENU_OPTION_CLOSE_DOOR: &str = "Close Door";
+pub const MENU_OPTION_PICK_LOCK: &str = "Pick Lock";
+pub const MENU_OPTION_CRAFT: &str = "Craft";
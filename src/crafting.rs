@@ -0,0 +1,194 @@
+use crate::components::{Inventory, Name, Stackable};
+use crate::identification::Identification;
+use crate::inventory::{consume_one, get_matching_stack, get_stack_quantity};
+use crate::services::game_log::GameLog;
+use crate::spawner;
+use crate::types::{item_type, ItemType, WorkstationType};
+use ron::from_str;
+use serde::{Deserialize, Serialize};
+use specs::{Entity, World, WorldExt};
+
+const RECIPES_STRING: &str = include_str!("./data/recipes.ron");
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum CraftedItem {
+    HealthPotion,
+    MagicMissileScroll,
+    ConfusionScroll,
+    IdentifyScroll,
+    Trap(ItemType),
+    Arrows,
+    ThrowingDaggers,
+    Bomb,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Ingredient {
+    pub name: String,
+    pub quantity: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Recipe {
+    pub workstation: WorkstationType,
+    pub ingredients: Vec<Ingredient>,
+    pub output: CraftedItem,
+    pub quantity: u32,
+}
+
+pub fn get_name_for_crafted_item(crafted_item: &CraftedItem) -> String {
+    match crafted_item {
+        CraftedItem::HealthPotion => "Health Potion".to_string(),
+        CraftedItem::MagicMissileScroll => "Scroll of Magic Missile".to_string(),
+        CraftedItem::ConfusionScroll => "Scroll of Confusion".to_string(),
+        CraftedItem::IdentifyScroll => "Scroll of Identify".to_string(),
+        CraftedItem::Trap(trap) => item_type::get_name_for_item(trap),
+        CraftedItem::Arrows => "Arrow".to_string(),
+        CraftedItem::ThrowingDaggers => "Throwing Dagger".to_string(),
+        CraftedItem::Bomb => "Bomb".to_string(),
+    }
+}
+
+// every recipe in the game, parsed once when the game starts.
+pub struct Recipes {
+    pub recipes: Vec<Recipe>,
+}
+
+impl Recipes {
+    pub fn new() -> Self {
+        Recipes {
+            recipes: from_str::<Vec<Recipe>>(RECIPES_STRING).expect("could not parse recipes.ron"),
+        }
+    }
+}
+
+pub fn get_recipes_for_workstation(
+    world: &World,
+    workstation_type: WorkstationType,
+) -> Vec<Recipe> {
+    world
+        .fetch::<Recipes>()
+        .recipes
+        .iter()
+        .filter(|recipe| recipe.workstation == workstation_type)
+        .cloned()
+        .collect()
+}
+
+pub fn get_recipe_text(world: &World, recipe: &Recipe) -> String {
+    let identification = world.fetch::<Identification>();
+    let ingredients: Vec<String> = recipe
+        .ingredients
+        .iter()
+        .map(|ingredient| match ingredient.quantity {
+            1 => identification.get_display_name(&ingredient.name),
+            quantity => format!(
+                "{} x{}",
+                identification.get_display_name(&ingredient.name),
+                quantity
+            ),
+        })
+        .collect();
+    let output = get_name_for_crafted_item(&recipe.output);
+    match recipe.quantity {
+        1 => format!("{} ({})", output, ingredients.join(", ")),
+        quantity => format!("{} x{} ({})", output, quantity, ingredients.join(", ")),
+    }
+}
+
+fn get_inventory_items_named(world: &World, name: &str) -> Vec<Entity> {
+    let player_entity = world.fetch::<Entity>();
+    let inventories = world.read_storage::<Inventory>();
+    let names = world.read_storage::<Name>();
+    match inventories.get(*player_entity) {
+        Some(inventory) => inventory
+            .items
+            .iter()
+            .filter(|e| match names.get(**e) {
+                Some(n) => n.name == name,
+                None => false,
+            })
+            .cloned()
+            .collect(),
+        None => vec![],
+    }
+}
+
+pub fn has_ingredients(world: &World, recipe: &Recipe) -> bool {
+    let stackables = world.read_storage::<Stackable>();
+    recipe.ingredients.iter().all(|ingredient| {
+        let available: u32 = get_inventory_items_named(world, &ingredient.name)
+            .into_iter()
+            .map(|e| get_stack_quantity(e, &stackables))
+            .sum();
+        available >= ingredient.quantity
+    })
+}
+
+fn consume_ingredient(world: &mut World, ingredient: &Ingredient) {
+    let player_entity = *world.fetch::<Entity>();
+    let mut remaining = ingredient.quantity;
+    for item in get_inventory_items_named(world, &ingredient.name) {
+        let mut inventories = world.write_storage::<Inventory>();
+        let mut stackables = world.write_storage::<Stackable>();
+        let entities = world.entities();
+        let inventory = inventories.get_mut(player_entity).unwrap();
+        while remaining > 0 && inventory.items.contains(&item) {
+            consume_one(item, inventory, &mut stackables, &entities);
+            remaining -= 1;
+        }
+        if remaining == 0 {
+            break;
+        }
+    }
+}
+
+// crafted items go straight into the player's inventory, joining any matching stack.
+fn add_item_to_player_inventory(world: &mut World, item: Entity) {
+    let player_entity = *world.fetch::<Entity>();
+    let mut inventories = world.write_storage::<Inventory>();
+    let mut stackables = world.write_storage::<Stackable>();
+    let names = world.read_storage::<Name>();
+    let inventory = inventories.get_mut(player_entity).unwrap();
+    match get_matching_stack(item, inventory.items.iter(), &stackables, &names) {
+        Some(stack) => {
+            let quantity = get_stack_quantity(item, &stackables);
+            if let Some(stack) = stackables.get_mut(stack) {
+                stack.quantity += quantity;
+            }
+            world
+                .entities()
+                .delete(item)
+                .expect("failed deleting merged stack");
+        }
+        None => {
+            inventory.items.insert(item);
+        }
+    }
+}
+
+// returns true when the recipe was crafted, and the player's turn was spent.
+pub fn craft(world: &mut World, recipe: &Recipe) -> bool {
+    let output_name = get_name_for_crafted_item(&recipe.output);
+    if !has_ingredients(world, recipe) {
+        let mut log = world.fetch_mut::<GameLog>();
+        log.add(format!(
+            "You don't have the ingredients to make {}.",
+            output_name
+        ));
+        return false;
+    }
+    for ingredient in recipe.ingredients.iter() {
+        consume_ingredient(world, ingredient);
+    }
+    let item = spawner::spawn_crafted_item(world, &recipe.output, recipe.quantity);
+    add_item_to_player_inventory(world, item);
+    // the crafter knows what they've just made.
+    world.fetch_mut::<Identification>().identify(&output_name);
+    let mut log = world.fetch_mut::<GameLog>();
+    match recipe.quantity {
+        1 => log.add(format!("You make {}.", output_name)),
+        quantity => log.add(format!("You make {} x{}.", output_name, quantity)),
+    }
+    true
+}
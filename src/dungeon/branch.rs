@@ -0,0 +1,33 @@
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+
+// one in this many floors between the top and the bottom has a branch hanging off it.
+const BRANCH_CHANCE: i32 = 4;
+
+// a level off to the side of the main stack, reached by its own stairs and going nowhere else.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Branch {
+    GoblinWarren,
+    FloodedCistern,
+}
+
+pub fn get_branch_for_floor(
+    is_top_floor: bool,
+    is_bottom_floor: bool,
+    rng: &mut RandomNumberGenerator,
+) -> Option<Branch> {
+    if is_top_floor || is_bottom_floor || rng.range(0, BRANCH_CHANCE) != 0 {
+        return None;
+    }
+    match rng.range(0, 2) {
+        0 => Some(Branch::GoblinWarren),
+        _ => Some(Branch::FloodedCistern),
+    }
+}
+
+pub fn get_name_for_branch(branch: Branch) -> &'static str {
+    match branch {
+        Branch::GoblinWarren => "goblin warren",
+        Branch::FloodedCistern => "flooded cistern",
+    }
+}
@@ -1,3 +1,7 @@
-// note that these are seperate as they will at some point be unrelated to UI
-pub use crate::screens::constants::{MAP_HEIGHT, MAP_WIDTH};
-pub const MAP_COUNT: usize = MAP_HEIGHT as usize * MAP_WIDTH as usize;
+// levels that don't ask for anything else are built to fill the screen, but any level can be
+// bigger or smaller than that.
+pub const DEFAULT_LEVEL_WIDTH: u8 = 100;
+pub const DEFAULT_LEVEL_HEIGHT: u8 = 50;
+pub const WALL_HP: i32 = 40;
+pub const COLUMN_HP: i32 = 20;
+pub const WEAK_WALL_HP: i32 = 10;
@@ -2,9 +2,29 @@ use super::level::Level;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConnectionKind {
+  Stairs,
+  // pits and trapdoors only go one way, there's no climbing back up them.
+  Pit,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Connection {
+  pub kind: ConnectionKind,
+  pub from_level: u8,
+  pub from_idx: usize,
+  pub to_level: u8,
+  pub to_idx: usize,
+}
+
+// levels are looked up by id, the main stack of floors is numbered by depth and any branches
+// hanging off it come after. how they're joined up is kept separately, so a level can have any
+// number of ways in and out.
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct Dungeon {
   pub levels: HashMap<u8, Level>,
+  pub connections: Vec<Connection>,
 }
 
 impl Dungeon {
@@ -15,4 +35,35 @@ impl Dungeon {
   pub fn get_level_mut(&mut self, floor: u8) -> Option<&mut Level> {
     self.levels.get_mut(&floor)
   }
+
+  // where taking the way out at this spot leads, stairs can be taken from either end.
+  pub fn get_destination(&self, floor: u8, idx: usize) -> Option<(u8, usize)> {
+    self.connections.iter().find_map(|connection| {
+      if connection.from_level == floor && connection.from_idx == idx {
+        Some((connection.to_level, connection.to_idx))
+      } else if connection.kind == ConnectionKind::Stairs
+        && connection.to_level == floor
+        && connection.to_idx == idx
+      {
+        Some((connection.from_level, connection.from_idx))
+      } else {
+        None
+      }
+    })
+  }
+
+  // the floor directly under this one on the main stack, found by following the ways down out of
+  // it. branches have nothing under them, and the way into a branch doesn't count.
+  pub fn get_level_below(&self, floor: u8) -> Option<u8> {
+    self
+      .connections
+      .iter()
+      .filter(|connection| connection.from_level == floor)
+      .map(|connection| connection.to_level)
+      .find(|to_level| {
+        self
+          .get_level(*to_level)
+          .map_or(false, |level| level.branch.is_none())
+      })
+  }
 }
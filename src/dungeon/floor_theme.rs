@@ -0,0 +1,48 @@
+use super::room_decorators::RoomType;
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+
+// what a floor was built for, it decides which rooms are common on it and which are rare.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FloorTheme {
+    Barracks,
+    Residential,
+    Palace,
+}
+
+pub fn get_floor_theme(rng: &mut RandomNumberGenerator) -> FloorTheme {
+    match rng.range(0, 3) {
+        0 => FloorTheme::Barracks,
+        1 => FloorTheme::Residential,
+        _ => FloorTheme::Palace,
+    }
+}
+
+// how likely a room is to be picked on a floor with this theme, rooms that don't belong to
+// the theme either way are left at the base weight.
+pub fn get_weight_for_room_type(theme: FloorTheme, room_type: Option<RoomType>) -> i32 {
+    match (theme, room_type) {
+        (FloorTheme::Barracks, Some(RoomType::Barracks))
+        | (FloorTheme::Barracks, Some(RoomType::MessHall))
+        | (FloorTheme::Barracks, Some(RoomType::Kitchen))
+        | (FloorTheme::Barracks, Some(RoomType::StoreRoom)) => 6,
+        (FloorTheme::Barracks, Some(RoomType::SittingRoom))
+        | (FloorTheme::Barracks, Some(RoomType::DiningRoom))
+        | (FloorTheme::Barracks, Some(RoomType::Baths)) => 1,
+        (FloorTheme::Residential, Some(RoomType::BedRoom))
+        | (FloorTheme::Residential, Some(RoomType::SittingRoom))
+        | (FloorTheme::Residential, Some(RoomType::DiningRoom))
+        | (FloorTheme::Residential, Some(RoomType::Kitchen)) => 6,
+        (FloorTheme::Residential, Some(RoomType::Barracks))
+        | (FloorTheme::Residential, Some(RoomType::MessHall)) => 1,
+        (FloorTheme::Palace, Some(RoomType::SittingRoom))
+        | (FloorTheme::Palace, Some(RoomType::DiningRoom))
+        | (FloorTheme::Palace, Some(RoomType::MeetingRoom))
+        | (FloorTheme::Palace, Some(RoomType::Baths))
+        | (FloorTheme::Palace, Some(RoomType::Courtyard)) => 6,
+        (FloorTheme::Palace, Some(RoomType::Barracks))
+        | (FloorTheme::Palace, Some(RoomType::MessHall))
+        | (FloorTheme::Palace, Some(RoomType::StoreRoom)) => 1,
+        _ => 2,
+    }
+}
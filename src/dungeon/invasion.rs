@@ -0,0 +1,433 @@
+use super::{
+    level::Level, level_utils, rect::Rect, room_decorators::RoomPart, surface_type::SurfaceType,
+    tile_type::TileType,
+};
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+use stamp_rs::StampPart::Use;
+
+// one in this many floors below the top has been overrun.
+const INVASION_CHANCE: i32 = 3;
+// one in this many pieces of furniture is smashed by goblins looking for loot.
+const LOOTED_FURNITURE_CHANCE: i32 = 2;
+const BARRICADED_ROOM_CHANCE: i32 = 2;
+const DOORWAY_TRAP_CHANCE: i32 = 3;
+const GUARDED_ROOM_CHANCE: i32 = 2;
+const MIN_CAMPS: i32 = 1;
+const MAX_CAMPS: i32 = 3;
+const MIN_TUNNELS: i32 = 2;
+const MAX_TUNNELS: i32 = 4;
+const MAX_FORGES: i32 = 2;
+const MIN_CORPSES: i32 = 6;
+const MAX_CORPSES: i32 = 12;
+const BATTLEFIELD_RUBBLE_CHANCE: i32 = 12;
+const BATTLEFIELD_BLOOD_CHANCE: i32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Invasion {
+    Goblin,
+    Duergar,
+    Both,
+}
+
+// things an invasion leaves behind that aren't part of any room.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum InvasionSpawn {
+    Barricade,
+    Campfire,
+    Trap,
+    Forge,
+    DuergarGuard,
+    WeaponRack,
+    Rubble,
+    GoblinCorpse,
+    DuergarCorpse,
+}
+
+pub fn get_invasion_for_floor(
+    is_top_floor: bool,
+    rng: &mut RandomNumberGenerator,
+) -> Option<Invasion> {
+    if is_top_floor || rng.range(0, INVASION_CHANCE) != 0 {
+        return None;
+    }
+    match rng.range(0, 3) {
+        0 => Some(Invasion::Goblin),
+        1 => Some(Invasion::Duergar),
+        _ => Some(Invasion::Both),
+    }
+}
+
+fn is_furniture(part: RoomPart) -> bool {
+    match part {
+        RoomPart::Bed
+        | RoomPart::Armoire
+        | RoomPart::Dresser
+        | RoomPart::BedsideTable
+        | RoomPart::Chest
+        | RoomPart::Chair
+        | RoomPart::Desk
+        | RoomPart::Shelf
+        | RoomPart::Table
+        | RoomPart::Stove
+        | RoomPart::Cupboard
+        | RoomPart::Counter
+        | RoomPart::Barrel
+        | RoomPart::WeaponRack
+        | RoomPart::TowelRack
+        | RoomPart::Throne
+        | RoomPart::Podium => true,
+        _ => false,
+    }
+}
+
+fn get_interior_xys(rect: &Rect) -> Vec<(i32, i32)> {
+    (rect.y1 + 1..rect.y2 - 1)
+        .map(|y| (rect.x1 + 1..rect.x2 - 1).map(move |x| (x, y)))
+        .flatten()
+        .collect()
+}
+
+fn spawn_is_at(level: &Level, idx: usize) -> bool {
+    level.invasion_spawns.iter().any(|(i, _)| *i == idx)
+}
+
+// floor inside the room that nothing has been stamped or placed on yet.
+fn get_open_floor_idxs(level: &Level, room_index: usize) -> Vec<usize> {
+    let room = &level.rooms[room_index];
+    let width = level.width as u32;
+    get_interior_xys(&room.rect)
+        .into_iter()
+        .filter(|(x, y)| {
+            let stamp_at = ((x - room.rect.x1) as usize, (y - room.rect.y1) as usize);
+            match room.stamp.get_at(stamp_at) {
+                Some(Use(RoomPart::Floor)) => true,
+                _ => false,
+            }
+        })
+        .map(|(x, y)| level_utils::xy_idx(width, x, y))
+        .filter(|idx| level.tiles[*idx] == TileType::Floor && !spawn_is_at(level, *idx))
+        .collect()
+}
+
+// the tile just inside each door of a room, along with the two tiles either side of it.
+fn get_doorways(level: &Level, room_index: usize) -> Vec<(usize, usize, usize)> {
+    let rect = level.rooms[room_index].rect;
+    let width = level.width as u32;
+    let mut doorways = vec![];
+    for y in rect.y1..rect.y2 {
+        for x in rect.x1..rect.x2 {
+            if level.tiles[level_utils::xy_idx(width, x, y)] != TileType::Door {
+                continue;
+            }
+            let delta_x = if x == rect.x1 {
+                1
+            } else if x == rect.x2 - 1 {
+                -1
+            } else {
+                0
+            };
+            let delta_y = if y == rect.y1 {
+                1
+            } else if y == rect.y2 - 1 {
+                -1
+            } else {
+                0
+            };
+            if (delta_x == 0) == (delta_y == 0) {
+                continue;
+            }
+            let (inside_x, inside_y) = (x + delta_x, y + delta_y);
+            let flanks = match delta_x {
+                0 => ((inside_x - 1, inside_y), (inside_x + 1, inside_y)),
+                _ => ((inside_x, inside_y - 1), (inside_x, inside_y + 1)),
+            };
+            doorways.push((
+                level_utils::xy_idx(width, inside_x, inside_y),
+                level_utils::xy_idx(width, (flanks.0).0, (flanks.0).1),
+                level_utils::xy_idx(width, (flanks.1).0, (flanks.1).1),
+            ));
+        }
+    }
+    doorways
+}
+
+fn get_rooms_without_stairs(level: &Level) -> Vec<usize> {
+    (0..level.rooms.len())
+        .filter(|i| !level_utils::rect_has_stairs(level, &level.rooms[*i].rect))
+        .collect()
+}
+
+fn loot_furniture(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    for room in level.rooms.iter_mut() {
+        for row in room.stamp.pattern.iter_mut() {
+            for part in row.iter_mut() {
+                let looted = match part {
+                    Use(room_part) => is_furniture(*room_part),
+                    _ => false,
+                };
+                if looted && rng.range(0, LOOTED_FURNITURE_CHANCE) == 0 {
+                    *part = Use(RoomPart::Debris);
+                }
+            }
+        }
+    }
+}
+
+// barricades are piled up either side of a doorway, leaving a gap to get through.
+fn add_barricades(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    for room_index in 0..level.rooms.len() {
+        if rng.range(0, BARRICADED_ROOM_CHANCE) != 0 {
+            continue;
+        }
+        let open = get_open_floor_idxs(level, room_index);
+        for (_, left, right) in get_doorways(level, room_index) {
+            for idx in vec![left, right] {
+                if open.contains(&idx) && !spawn_is_at(level, idx) {
+                    level.invasion_spawns.push((idx, InvasionSpawn::Barricade));
+                }
+            }
+        }
+    }
+}
+
+fn add_doorway_traps(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    for room_index in 0..level.rooms.len() {
+        let open = get_open_floor_idxs(level, room_index);
+        for (inside, _, _) in get_doorways(level, room_index) {
+            if open.contains(&inside)
+                && !spawn_is_at(level, inside)
+                && rng.range(0, DOORWAY_TRAP_CHANCE) == 0
+            {
+                level.invasion_spawns.push((inside, InvasionSpawn::Trap));
+            }
+        }
+    }
+}
+
+// camps are built around a fire as close to the middle of a room as there's space for.
+fn add_camps(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let mut rooms = get_rooms_without_stairs(level);
+    let camp_count = rng.range(MIN_CAMPS, MAX_CAMPS + 1);
+    for _ in 0..camp_count {
+        if rooms.len() == 0 {
+            break;
+        }
+        let room_index = rooms.remove(rng.range(0, rooms.len() as i32) as usize);
+        let (center_x, center_y) = level.rooms[room_index].rect.center();
+        let center_idx = level_utils::xy_idx(level.width as u32, center_x, center_y);
+        let fire_idx = get_open_floor_idxs(level, room_index)
+            .into_iter()
+            .min_by_key(|idx| {
+                (level_utils::get_distance_between_idxs(level, center_idx, *idx) * 10.0) as i32
+            });
+        if let Some(fire_idx) = fire_idx {
+            level
+                .invasion_spawns
+                .push((fire_idx, InvasionSpawn::Campfire));
+        }
+    }
+}
+
+// duergar dig straight through whatever stands between them and where they want to be.
+fn dig_tunnels(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    if level.rooms.len() < 2 {
+        return;
+    }
+    let width = level.width as i32;
+    let height = level.height as i32;
+    let tunnel_count = rng.range(MIN_TUNNELS, MAX_TUNNELS + 1);
+    for _ in 0..tunnel_count {
+        let from = level.rooms[rng.range(0, level.rooms.len() as i32) as usize]
+            .rect
+            .center();
+        let to = level.rooms[rng.range(0, level.rooms.len() as i32) as usize]
+            .rect
+            .center();
+        let horizontal = (i32::min(from.0, to.0)..=i32::max(from.0, to.0)).map(|x| (x, from.1));
+        let vertical = (i32::min(from.1, to.1)..=i32::max(from.1, to.1)).map(|y| (to.0, y));
+        for (x, y) in horizontal.chain(vertical) {
+            if x < 1 || y < 1 || x >= width - 1 || y >= height - 1 {
+                continue;
+            }
+            let idx = level_utils::xy_idx(width as u32, x, y);
+            match level.tiles[idx] {
+                TileType::Wall | TileType::Column => {
+                    level.tiles[idx] = TileType::Floor;
+                    level.tile_hp[idx] = 0;
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+// forges are only set up in proper rooms, backed onto a wall.
+fn add_forges(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let width = level.width as u32;
+    let mut rooms: Vec<usize> = get_rooms_without_stairs(level)
+        .into_iter()
+        .filter(|i| level.rooms[*i].room_type.is_some())
+        .collect();
+    let forge_count = rng.range(1, MAX_FORGES + 1);
+    for _ in 0..forge_count {
+        if rooms.len() == 0 {
+            break;
+        }
+        let room_index = rooms.remove(rng.range(0, rooms.len() as i32) as usize);
+        let against_wall: Vec<usize> = get_open_floor_idxs(level, room_index)
+            .into_iter()
+            .filter(|idx| {
+                let (x, y) = level_utils::idx_xy(width, *idx);
+                vec![(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .any(|(dx, dy)| level_utils::tile_at_xy_is_wall(level, x + dx, y + dy))
+            })
+            .collect();
+        if let Some(idx) = rng.random_slice_entry(&against_wall) {
+            level.invasion_spawns.push((*idx, InvasionSpawn::Forge));
+        }
+    }
+}
+
+// a guard stands beside each door of a guarded room, with their weapons racked on the other side.
+fn add_guard_posts(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    for room_index in 0..level.rooms.len() {
+        if rng.range(0, GUARDED_ROOM_CHANCE) != 0 {
+            continue;
+        }
+        let open = get_open_floor_idxs(level, room_index);
+        for (_, left, right) in get_doorways(level, room_index) {
+            if open.contains(&left)
+                && open.contains(&right)
+                && !spawn_is_at(level, left)
+                && !spawn_is_at(level, right)
+            {
+                level
+                    .invasion_spawns
+                    .push((left, InvasionSpawn::DuergarGuard));
+                level
+                    .invasion_spawns
+                    .push((right, InvasionSpawn::WeaponRack));
+            }
+        }
+    }
+}
+
+// the biggest room is cleared out and fought over, goblins dug in on one side and duergar on
+// the other, with gaps left in the lines where the fighting broke through.
+fn add_battlefield(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let room_index = match get_rooms_without_stairs(level)
+        .into_iter()
+        .max_by_key(|i| level.rooms[*i].rect.area())
+    {
+        Some(room_index) => room_index,
+        None => return,
+    };
+    for row in level.rooms[room_index].stamp.pattern.iter_mut() {
+        for part in row.iter_mut() {
+            let cleared = match part {
+                Use(room_part) => is_furniture(*room_part),
+                _ => false,
+            };
+            if cleared {
+                *part = Use(RoomPart::Floor);
+            }
+        }
+    }
+    let rect = level.rooms[room_index].rect;
+    let width = level.width as u32;
+    let goblin_line = rect.x1 + rect.width() / 3;
+    let duergar_line = rect.x1 + rect.width() * 2 / 3;
+    // a room too short to leave gaps in the lines would be cut in half.
+    let has_lines = rect.height() - 2 >= 3;
+    let in_line = |idx: usize| {
+        let (x, _) = level_utils::idx_xy(width, idx);
+        has_lines && (x == goblin_line || x == duergar_line)
+    };
+    let in_gap = |idx: usize| {
+        let (_, y) = level_utils::idx_xy(width, idx);
+        (y - rect.y1) % 3 == 0
+    };
+    let doorways: Vec<usize> = get_doorways(level, room_index)
+        .into_iter()
+        .map(|(inside, _, _)| inside)
+        .collect();
+    for idx in get_open_floor_idxs(level, room_index) {
+        if in_line(idx) && !in_gap(idx) && !doorways.contains(&idx) {
+            level.invasion_spawns.push((idx, InvasionSpawn::Barricade));
+        }
+    }
+    // nothing is left lying in the gaps, they're the only way from one side to the other.
+    let mut open: Vec<usize> = get_open_floor_idxs(level, room_index)
+        .into_iter()
+        .filter(|idx| !(in_line(*idx) && in_gap(*idx)))
+        .collect();
+    let corpse_count = rng.range(MIN_CORPSES, MAX_CORPSES + 1);
+    for _ in 0..corpse_count {
+        if open.len() == 0 {
+            break;
+        }
+        let idx = open.remove(rng.range(0, open.len() as i32) as usize);
+        let (x, _) = level_utils::idx_xy(width, idx);
+        let corpse = match x < rect.x1 + rect.width() / 2 {
+            true => InvasionSpawn::GoblinCorpse,
+            false => InvasionSpawn::DuergarCorpse,
+        };
+        level.invasion_spawns.push((idx, corpse));
+        level.surfaces[idx] = Some(SurfaceType::Blood);
+    }
+    for idx in open {
+        if doorways.contains(&idx) {
+            continue;
+        }
+        if rng.range(0, BATTLEFIELD_RUBBLE_CHANCE) == 0 {
+            level.invasion_spawns.push((idx, InvasionSpawn::Rubble));
+        } else if rng.range(0, BATTLEFIELD_BLOOD_CHANCE) == 0 {
+            level.surfaces[idx] = Some(SurfaceType::Blood);
+        }
+    }
+}
+
+fn spawn_blocks_tile(spawn: InvasionSpawn) -> bool {
+    match spawn {
+        InvasionSpawn::Barricade
+        | InvasionSpawn::Forge
+        | InvasionSpawn::WeaponRack
+        | InvasionSpawn::Rubble => true,
+        _ => false,
+    }
+}
+
+pub fn apply_invasion(level: &mut Level, invasion: Invasion, rng: &mut RandomNumberGenerator) {
+    level.invasion = Some(invasion);
+    match invasion {
+        Invasion::Goblin => {
+            loot_furniture(level, rng);
+            add_camps(level, rng);
+            add_barricades(level, rng);
+            add_doorway_traps(level, rng);
+        }
+        Invasion::Duergar => {
+            dig_tunnels(level, rng);
+            add_forges(level, rng);
+            add_guard_posts(level, rng);
+        }
+        Invasion::Both => {
+            add_battlefield(level, rng);
+            loot_furniture(level, rng);
+            dig_tunnels(level, rng);
+            add_barricades(level, rng);
+            add_guard_posts(level, rng);
+        }
+    }
+    level_utils::populate_blocked(level);
+    level_utils::populate_opaque(level);
+    // what's left behind is in the way from the start, not only once it's been spawned, so the
+    // level can be checked for anything it's cut off.
+    for (idx, spawn) in level.invasion_spawns.iter() {
+        if spawn_blocks_tile(*spawn) {
+            level.blocked[*idx] = true;
+        }
+    }
+}
@@ -1,11 +1,18 @@
-use super::constants::{MAP_COUNT, MAP_HEIGHT, MAP_WIDTH};
+use super::branch::Branch;
+use super::floor_theme::FloorTheme;
+use super::invasion::{Invasion, InvasionSpawn};
 use super::level_utils;
+use super::locks::{self, KeyPlacement};
 use super::room::Room;
+use super::surface_type::SurfaceType;
 use super::tile_type::TileType;
-use rltk::{Algorithm2D, BaseMap, DistanceAlg::Pythagoras, Point, SmallVec};
+use rltk::{Algorithm2D, BaseMap, DistanceAlg::Pythagoras, Point, SmallVec, RGB};
 use serde::{Deserialize, Serialize};
 use specs::Entity;
 
+// wading through standing water is slow going, so paths go around it when they can.
+const WADING_COST: f32 = 1.0;
+
 #[derive(Default, Serialize, Deserialize, Clone, Debug)]
 pub struct Level {
     pub height: u8,
@@ -13,33 +20,87 @@ pub struct Level {
     pub tiles: Box<[TileType]>,
     pub rooms: Vec<Room>,
     pub revealed_tiles: Box<[bool]>,
-    pub lit_tiles: Box<[bool]>, // can we skip serializing this?d
+    // lighting is recalculated every turn, so there's no need to save it.
+    #[serde(skip_serializing, skip_deserializing)]
+    pub lit_tiles: Box<[f32]>,
+    #[serde(skip_serializing, skip_deserializing)]
+    pub light_colors: Box<[RGB]>,
     pub blocked: Box<[bool]>,
     pub opaque: Box<[bool]>,
+    pub surfaces: Box<[Option<SurfaceType>]>,
+    // tracks hold the order they were left in, so the newest one shows where to go next.
+    pub tracks: Box<[u32]>,
+    pub track_count: u32,
+    // turns left for each burning tile, and how thick the smoke hanging over each tile is.
+    pub fire: Box<[u8]>,
+    pub smoke: Box<[u8]>,
+    // walls and columns can be broken down, anything without hit points can't be damaged.
+    pub tile_hp: Box<[i32]>,
     pub depth: u8,
-    pub stairs_down: Option<usize>,
-    pub stairs_up: Option<usize>,
+    // the key the dungeon keeps this level under. a branch has the same depth as the floor below
+    // the one it hangs off, but an id of its own.
+    pub id: u8,
+    // a level can have any number of ways up and down, the dungeon knows where each one leads.
+    pub stairs_down: Vec<usize>,
+    pub stairs_up: Vec<usize>,
     pub exit: Option<usize>,
+    // pits can be seen from across the room, trapdoors are hidden until found or fallen through.
+    pub pits: Vec<usize>,
+    pub trapdoors: Vec<usize>,
+    pub branch: Option<Branch>,
+    // anything that falls through the floor needs somewhere to land.
+    pub has_floor_below: bool,
+    // what the floor was built for, levels without rooms of their own don't have one.
+    pub theme: Option<FloorTheme>,
+    // who has overrun this floor, if anyone, and what they left behind.
+    pub invasion: Option<Invasion>,
+    pub invasion_spawns: Vec<(usize, InvasionSpawn)>,
+    // doors that are still locked and the ids of their keys, and where each key was left.
+    pub locked_doors: Vec<(usize, u32)>,
+    pub keys: Vec<(usize, u32, KeyPlacement)>,
+    // doors nobody has found yet, they look like wall, and the rooms only they lead into.
+    pub secret_doors: Vec<usize>,
+    pub secret_rooms: Vec<usize>,
     #[serde(skip_serializing, skip_deserializing)]
     pub tile_content: Vec<Vec<Entity>>,
 }
 
 impl Level {
-    pub fn new(depth: u8) -> Self {
+    pub fn new(depth: u8, width: u8, height: u8) -> Self {
+        let count = width as usize * height as usize;
         Self {
-            tiles: Box::new([TileType::Wall; MAP_COUNT]),
+            tiles: vec![TileType::Wall; count].into_boxed_slice(),
             rooms: vec![], // TODO: determine if this is useful beyond the level building phase
-            width: MAP_WIDTH,
-            height: MAP_HEIGHT,
-            revealed_tiles: Box::new([false; MAP_COUNT]),
-            lit_tiles: Box::new([false; MAP_COUNT]),
-            blocked: Box::new([false; MAP_COUNT]),
-            opaque: Box::new([false; MAP_COUNT]),
-            tile_content: vec![vec![]; MAP_COUNT],
-            stairs_down: None,
-            stairs_up: None,
+            width,
+            height,
+            revealed_tiles: vec![false; count].into_boxed_slice(),
+            lit_tiles: vec![0.0; count].into_boxed_slice(),
+            light_colors: vec![RGB::named(rltk::BLACK); count].into_boxed_slice(),
+            blocked: vec![false; count].into_boxed_slice(),
+            opaque: vec![false; count].into_boxed_slice(),
+            surfaces: vec![None; count].into_boxed_slice(),
+            tracks: vec![0; count].into_boxed_slice(),
+            track_count: 0,
+            fire: vec![0; count].into_boxed_slice(),
+            smoke: vec![0; count].into_boxed_slice(),
+            tile_hp: vec![0; count].into_boxed_slice(),
+            tile_content: vec![vec![]; count],
+            stairs_down: vec![],
+            stairs_up: vec![],
             exit: None,
+            pits: vec![],
+            trapdoors: vec![],
+            branch: None,
+            has_floor_below: false,
+            theme: None,
+            invasion: None,
+            invasion_spawns: vec![],
+            locked_doors: vec![],
+            keys: vec![],
+            secret_doors: vec![],
+            secret_rooms: vec![],
             depth,
+            id: depth,
         }
     }
     pub fn get_costs_for_tile(&self, idx: usize, diagonal: bool) -> f32 {
@@ -47,8 +108,17 @@ impl Level {
             true => 1.45,
             false => 1.0,
         };
+        let cost = match self.surfaces[idx] {
+            Some(SurfaceType::Water) => cost + WADING_COST,
+            _ => cost,
+        };
+        // nobody drops down a pit on purpose.
+        if level_utils::idx_has_pit(self, idx) {
+            return cost + 25.0;
+        }
         match self.blocked[idx] {
             true => match self.tiles[idx] {
+                TileType::Door if locks::idx_is_locked(self, idx) => cost + 25.0,
                 TileType::Door => cost + 1.0,
                 _ => cost + 25.0,
             },
@@ -64,20 +134,26 @@ impl BaseMap for Level {
 
     fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
         let mut exits = SmallVec::new();
-        for (idx, diagonal) in [
-            (idx - 1, false),
-            (idx + 1, false),
-            (idx - self.width as usize, false),
-            (idx + self.width as usize, false),
-            (idx - self.width as usize - 1, true),
-            (idx - self.width as usize + 1, true),
-            (idx + self.width as usize - 1, true),
-            (idx + self.width as usize + 1, true),
+        let (x, y) = level_utils::idx_xy(self.width as u32, idx);
+        for (dx, dy, diagonal) in [
+            (-1, 0, false),
+            (1, 0, false),
+            (0, -1, false),
+            (0, 1, false),
+            (-1, -1, true),
+            (1, -1, true),
+            (-1, 1, true),
+            (1, 1, true),
         ]
         .iter()
         {
-            if level_utils::is_exit_valid(self, *idx) {
-                exits.push((*idx, self.get_costs_for_tile(*idx, *diagonal)))
+            // neighbours off the edge of the level don't wrap around to the other side.
+            let exit_idx = match level_utils::get_idx_at_xy(self, x + dx, y + dy) {
+                Some(exit_idx) => exit_idx,
+                None => continue,
+            };
+            if level_utils::is_exit_valid(self, exit_idx) {
+                exits.push((exit_idx, self.get_costs_for_tile(exit_idx, *diagonal)))
             }
         }
         exits
@@ -0,0 +1,60 @@
+use super::{get_rooms_from_open_areas, remove_unreachable_areas, LevelBuilder};
+use crate::dungeon::{level::Level, level_utils, tile_type::TileType};
+use rltk::RandomNumberGenerator;
+
+const INITIAL_WALL_CHANCE: i32 = 45;
+const SMOOTHING_PASSES: u32 = 5;
+// caves open out into anything from a few grottos to a sprawling cavern.
+const MIN_WIDTH: i32 = 80;
+const MAX_WIDTH: i32 = 140;
+const MIN_HEIGHT: i32 = 40;
+const MAX_HEIGHT: i32 = 70;
+
+// anything off the edge of the level counts as wall.
+fn count_neighboring_walls(level: &Level, idx: usize) -> usize {
+    let floors = level_utils::get_neighbor_idxs(level, idx)
+        .iter()
+        .filter(|neighbor| level.tiles[**neighbor] == TileType::Floor)
+        .count();
+    8 - floors
+}
+
+// natural caverns grown from noise, smoothed until the walls clump together.
+pub struct CellularAutomataBuilder {}
+
+impl LevelBuilder for CellularAutomataBuilder {
+    fn get_dimensions(&self, rng: &mut RandomNumberGenerator) -> (u8, u8) {
+        (
+            rng.range(MIN_WIDTH, MAX_WIDTH + 1) as u8,
+            rng.range(MIN_HEIGHT, MAX_HEIGHT + 1) as u8,
+        )
+    }
+
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator) {
+        let width = level.width as i32;
+        let height = level.height as i32;
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                if rng.range(0, 100) >= INITIAL_WALL_CHANCE {
+                    let idx = level_utils::xy_idx(level.width as u32, x, y);
+                    level_utils::set_tile_to_floor(level, idx);
+                }
+            }
+        }
+        for _ in 0..SMOOTHING_PASSES {
+            let mut next_tiles = level.tiles.clone();
+            for y in 1..height - 1 {
+                for x in 1..width - 1 {
+                    let idx = level_utils::xy_idx(level.width as u32, x, y);
+                    next_tiles[idx] = match count_neighboring_walls(level, idx) {
+                        0 | 5..=8 => TileType::Wall,
+                        _ => TileType::Floor,
+                    };
+                }
+            }
+            level.tiles = next_tiles;
+        }
+        remove_unreachable_areas(level);
+        level.rooms = get_rooms_from_open_areas(level, rng);
+    }
+}
@@ -0,0 +1,82 @@
+use super::{get_rooms_from_open_areas, LevelBuilder};
+use crate::dungeon::{level::Level, level_utils, tile_type::TileType};
+use rltk::RandomNumberGenerator;
+
+// how much of the map gets dug out before the miners stop.
+const FLOOR_PERCENT: usize = 40;
+const MAX_STEPS_PER_MINER: u32 = 400;
+// one in this many steps a miner stops to hollow out a small chamber.
+const CHAMBER_CHANCE: i32 = 60;
+// mines are cramped, they never fill the screen.
+const MIN_WIDTH: i32 = 50;
+const MAX_WIDTH: i32 = 70;
+const MIN_HEIGHT: i32 = 30;
+const MAX_HEIGHT: i32 = 40;
+
+fn dig(level: &mut Level, x: i32, y: i32) {
+    if x < 1 || y < 1 || x >= level.width as i32 - 1 || y >= level.height as i32 - 1 {
+        return;
+    }
+    let idx = level_utils::xy_idx(level.width as u32, x, y);
+    level_utils::set_tile_to_floor(level, idx);
+}
+
+fn count_floor_tiles(level: &Level) -> usize {
+    level
+        .tiles
+        .iter()
+        .filter(|tile| **tile == TileType::Floor)
+        .count()
+}
+
+// winding mine tunnels dug by miners wandering at random, each new miner starts from somewhere
+// already dug so the mine is always connected.
+pub struct DrunkardsWalkBuilder {}
+
+impl LevelBuilder for DrunkardsWalkBuilder {
+    fn get_dimensions(&self, rng: &mut RandomNumberGenerator) -> (u8, u8) {
+        (
+            rng.range(MIN_WIDTH, MAX_WIDTH + 1) as u8,
+            rng.range(MIN_HEIGHT, MAX_HEIGHT + 1) as u8,
+        )
+    }
+
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator) {
+        let width = level.width as u32;
+        let target_floor_tiles = level.tiles.len() * FLOOR_PERCENT / 100;
+        let (start_x, start_y) = (level.width as i32 / 2, level.height as i32 / 2);
+        dig(level, start_x, start_y);
+        while count_floor_tiles(level) < target_floor_tiles {
+            let floor_tiles: Vec<usize> = level
+                .tiles
+                .iter()
+                .enumerate()
+                .filter(|(_idx, tile)| **tile == TileType::Floor)
+                .map(|(idx, _tile)| idx)
+                .collect();
+            let (mut x, mut y) = match rng.random_slice_entry(&floor_tiles) {
+                Some(idx) => level_utils::idx_xy(width, *idx),
+                None => (start_x, start_y),
+            };
+            for _ in 0..MAX_STEPS_PER_MINER {
+                match rng.range(0, 4) {
+                    0 => x += 1,
+                    1 => x -= 1,
+                    2 => y += 1,
+                    _ => y -= 1,
+                }
+                x = i32::max(1, i32::min(x, level.width as i32 - 2));
+                y = i32::max(1, i32::min(y, level.height as i32 - 2));
+                dig(level, x, y);
+                if rng.range(0, CHAMBER_CHANCE) == 0 {
+                    for chamber_y in y - 1..=y + 1 {
+                        for chamber_x in x - 1..=x + 1 {
+                            dig(level, chamber_x, chamber_y);
+                        }
+                    }
+                }
+            }
+        }
+        level.rooms = get_rooms_from_open_areas(level, rng);
+    }
+}
@@ -0,0 +1,117 @@
+use super::{
+    add_circular_room, add_corridor, add_doors_to_rooms, add_rectangular_room, make_rect_square,
+    LevelBuilder,
+};
+use crate::dungeon::{
+    level::Level,
+    level_utils,
+    prefabs::{self, Prefab, PrefabCell},
+    rect::Rect,
+    room::Room,
+    room_placement,
+};
+use rltk::{Point, RandomNumberGenerator};
+use std::sync::Arc;
+
+const FORTRESS_COLUMNS: i32 = 4;
+const FORTRESS_ROWS: i32 = 3;
+const MAX_CELL_MARGIN: i32 = 3;
+// one in this many rooms is left without a prefab.
+const EMPTY_ROOM_CHANCE: i32 = 3;
+// fortresses sprawl well past the edges of the screen, their halls are big enough to hold court
+// in.
+const MIN_WIDTH: i32 = 130;
+const MAX_WIDTH: i32 = 180;
+const MIN_HEIGHT: i32 = 60;
+const MAX_HEIGHT: i32 = 80;
+
+// prefabs are centered in the room and leave a ring of floor around them so doors aren't
+// walled off.
+fn add_prefab(level: &mut Level, room: &Rect, prefab: &Prefab) {
+    if prefab.width() > room.width() - 3 || prefab.height() > room.height() - 3 {
+        return;
+    }
+    let (center_x, center_y) = room.center();
+    let start_x = center_x - prefab.width() / 2;
+    let start_y = center_y - prefab.height() / 2;
+    for (y, row) in prefab.pattern.iter().enumerate() {
+        for (x, character) in row.chars().enumerate() {
+            if let PrefabCell::Part(part) = prefab.get_cell(character) {
+                let idx =
+                    level_utils::xy_idx(level.width as u32, start_x + x as i32, start_y + y as i32);
+                level.tiles[idx] = prefabs::get_tile_for_room_part(part);
+            }
+        }
+    }
+}
+
+// a walled grid of halls and chambers with round towers at the corners, most of the rooms are
+// built around a prefab.
+pub struct FortressBuilder {
+    pub prefabs: Arc<Vec<Prefab>>,
+}
+
+impl LevelBuilder for FortressBuilder {
+    fn get_dimensions(&self, rng: &mut RandomNumberGenerator) -> (u8, u8) {
+        (
+            rng.range(MIN_WIDTH, MAX_WIDTH + 1) as u8,
+            rng.range(MIN_HEIGHT, MAX_HEIGHT + 1) as u8,
+        )
+    }
+
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator) {
+        let cell_width = (level.width as i32 - 2) / FORTRESS_COLUMNS;
+        let cell_height = (level.height as i32 - 2) / FORTRESS_ROWS;
+        let fortress_prefabs: Vec<&Prefab> = self
+            .prefabs
+            .iter()
+            .filter(|prefab| prefab.fortress)
+            .collect();
+        let mut room_rects = vec![];
+        for row in 0..FORTRESS_ROWS {
+            for column in 0..FORTRESS_COLUMNS {
+                let margin_left = rng.range(0, MAX_CELL_MARGIN + 1);
+                let margin_top = rng.range(0, MAX_CELL_MARGIN + 1);
+                let margin_right = rng.range(0, MAX_CELL_MARGIN + 1);
+                let margin_bottom = rng.range(0, MAX_CELL_MARGIN + 1);
+                let mut rect = Rect::new(
+                    1 + column * cell_width + margin_left,
+                    1 + row * cell_height + margin_top,
+                    cell_width - margin_left - margin_right - 1,
+                    cell_height - margin_top - margin_bottom - 1,
+                );
+                let is_corner = (row == 0 || row == FORTRESS_ROWS - 1)
+                    && (column == 0 || column == FORTRESS_COLUMNS - 1);
+                if is_corner {
+                    make_rect_square(&mut rect);
+                    add_circular_room(level, &rect);
+                } else {
+                    add_rectangular_room(level, &rect);
+                    if rng.range(0, EMPTY_ROOM_CHANCE) != 0 {
+                        if let Some(prefab) = rng.random_slice_entry(&fortress_prefabs) {
+                            add_prefab(level, &rect, prefab);
+                        }
+                    }
+                }
+                room_rects.push(rect);
+            }
+        }
+        for row in 0..FORTRESS_ROWS {
+            for column in 0..FORTRESS_COLUMNS {
+                let i = (row * FORTRESS_COLUMNS + column) as usize;
+                let from = Point::from(room_rects[i].center());
+                if column < FORTRESS_COLUMNS - 1 {
+                    let to = Point::from(room_rects[i + 1].center());
+                    add_corridor(level, rng, from, to);
+                }
+                if row < FORTRESS_ROWS - 1 {
+                    let to = Point::from(room_rects[i + FORTRESS_COLUMNS as usize].center());
+                    add_corridor(level, rng, from, to);
+                }
+            }
+        }
+        level.rooms = room_rects.iter().map(|r| Room::new(*r, rng)).collect();
+        add_doors_to_rooms(level);
+        room_placement::assign_room_types(level, rng);
+    }
+}
@@ -0,0 +1,48 @@
+use crate::dungeon::{
+    constants::{DEFAULT_LEVEL_HEIGHT, DEFAULT_LEVEL_WIDTH},
+    level::Level,
+};
+use rltk::RandomNumberGenerator;
+
+// a level builder lays out the floor plan and the rooms of a level, everything that happens to
+// the level after that is shared between builders as steps in the chain.
+pub trait LevelBuilder {
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator);
+
+    // how big a level this builder lays out, in tiles wide and high.
+    fn get_dimensions(&self, _rng: &mut RandomNumberGenerator) -> (u8, u8) {
+        (DEFAULT_LEVEL_WIDTH, DEFAULT_LEVEL_HEIGHT)
+    }
+}
+
+pub struct BuilderChain {
+    builder: Box<dyn LevelBuilder>,
+    steps: Vec<Box<dyn Fn(&mut Level, &mut RandomNumberGenerator)>>,
+}
+
+impl BuilderChain {
+    pub fn new(builder: Box<dyn LevelBuilder>) -> Self {
+        BuilderChain {
+            builder,
+            steps: vec![],
+        }
+    }
+
+    pub fn with<F>(mut self, step: F) -> Self
+    where
+        F: Fn(&mut Level, &mut RandomNumberGenerator) + 'static,
+    {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn build(&self, depth: u8, rng: &mut RandomNumberGenerator) -> Level {
+        let (width, height) = self.builder.get_dimensions(rng);
+        let mut level = Level::new(depth, width, height);
+        self.builder.build_map(&mut level, rng);
+        for step in self.steps.iter() {
+            step(&mut level, rng);
+        }
+        level
+    }
+}
@@ -0,0 +1,1015 @@
+mod cellular_automata;
+mod drunkards_walk;
+mod fortress;
+pub mod level_builder;
+mod rooms_and_corridors;
+mod single_room;
+
+pub use cellular_automata::CellularAutomataBuilder;
+pub use drunkards_walk::DrunkardsWalkBuilder;
+pub use fortress::FortressBuilder;
+pub use level_builder::{BuilderChain, LevelBuilder};
+pub use rooms_and_corridors::RoomsAndCorridorsBuilder;
+pub use single_room::SingleRoomBuilder;
+
+use super::room_feature::RoomFeature::{
+    ColumnsDoubleAll, ColumnsDoubleBottom, ColumnsDoubleHorizontal, ColumnsDoubleLeft,
+    ColumnsDoubleMiddle, ColumnsDoubleRight, ColumnsDoubleTop, ColumnsDoubleVertical,
+    ColumnsSingleAll, ColumnsSingleBottom, ColumnsSingleHorizontal, ColumnsSingleLeft,
+    ColumnsSingleMiddle, ColumnsSingleRight, ColumnsSingleTop, ColumnsSingleVertical,
+    ColumnsTripleAll, ColumnsTripleBottom, ColumnsTripleHorizontal, ColumnsTripleLeft,
+    ColumnsTripleRight, ColumnsTripleTop, ColumnsTripleVertical, Flood,
+};
+use super::{
+    branch::{self, Branch},
+    column_placers,
+    constants::WEAK_WALL_HP,
+    dungeon::{Connection, ConnectionKind, Dungeon},
+    invasion::{self, Invasion},
+    level::Level,
+    level_utils, locks, prefabs,
+    prefabs::{PrefabCell, Prefabs},
+    rect::Rect,
+    room::Room,
+    room_decorators,
+    room_decorators::{
+        RoomPart, RoomType,
+        RoomPart::{Column, Door, DownStairs, Exit, Floor, Ledge, UpStairs, Wall, WaterDeep},
+    },
+    secrets,
+    surface_type::SurfaceType,
+    tile_type::TileType,
+};
+use rltk::{DistanceAlg::Pythagoras, Point, RandomNumberGenerator};
+use stamp_rs::{
+    StampPart,
+    StampPart::{Transparent, Use},
+};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+// caves and mines have no rooms of their own, so they are split into areas this size that are
+// treated as rooms for decorating and spawning.
+const OPEN_AREA_WIDTH: i32 = 14;
+const OPEN_AREA_HEIGHT: i32 = 10;
+const MIN_OPEN_AREA_FLOOR_TILES: usize = 12;
+// one in this many rooms gets a second corridor on top of the spanning tree.
+const EXTRA_CORRIDOR_CHANCE: i32 = 3;
+const MAX_BUILD_ATTEMPTS: u32 = 20;
+const MIN_FLOOD_PERCENT: i32 = 30;
+const MAX_FLOOD_PERCENT: i32 = 70;
+// one in this many rooms that a prefab fits in is replaced by one.
+const PREFAB_ROOM_CHANCE: i32 = 6;
+const MAX_FLIGHTS_OF_STAIRS: i32 = 2;
+// one in this many floors with another below it has holes in the floor.
+const PIT_FLOOR_CHANCE: i32 = 3;
+const MAX_PITS: i32 = 3;
+// one in this many pits is covered over by a trapdoor.
+const TRAPDOOR_CHANCE: i32 = 2;
+
+fn generate_rects_for_level(
+    level_width: i32,
+    level_height: i32,
+    rng: &mut RandomNumberGenerator,
+) -> Vec<Rect> {
+    const MIN_ROOM_SIZE: i32 = 5;
+    let mut rects = Vec::new();
+    rects.push(Rect::new(1, 1, level_width - 3, level_height - 3));
+    for _ in 0..100 {
+        let random_index = rng.range(0, rects.len() as i32);
+        let rect = rects[random_index as usize];
+        let width = rect.x2 - rect.x1;
+        let height = rect.y2 - rect.y1;
+        if width > MIN_ROOM_SIZE * 2 && width > height {
+            let x = cmp::max(
+                rect.x1 + MIN_ROOM_SIZE,
+                rng.range(rect.x1, rect.x2 - MIN_ROOM_SIZE),
+            );
+            rects.remove(random_index as usize);
+            rects.push(Rect::new(rect.x1, rect.y1, x - rect.x1, rect.y2 - rect.y1));
+            rects.push(Rect::new(x, rect.y1, rect.x2 - x, rect.y2 - rect.y1));
+        } else if height > MIN_ROOM_SIZE * 2 && height > width {
+            let y = cmp::max(
+                rect.y1 + MIN_ROOM_SIZE,
+                rng.range(rect.y1, rect.y2 - MIN_ROOM_SIZE),
+            );
+            rects.remove(random_index as usize);
+            rects.push(Rect::new(rect.x1, rect.y1, rect.x2 - rect.x1, y - rect.y1));
+            rects.push(Rect::new(rect.x1, y, rect.x2 - rect.x1, rect.y2 - y));
+        }
+    }
+    rects
+}
+
+// water pools from a low spot in the room and spreads out across part of the floor.
+fn flood_room(level: &mut Level, rect: &Rect, rng: &mut RandomNumberGenerator) {
+    let mut floor_tiles = level_utils::get_walkable_tiles_in_rect(rect, level);
+    if floor_tiles.len() == 0 {
+        return;
+    }
+    let source_idx = floor_tiles[rng.range(0, floor_tiles.len() as i32) as usize];
+    floor_tiles.sort_by(|a, b| {
+        level_utils::get_distance_between_idxs(level, source_idx, *a)
+            .partial_cmp(&level_utils::get_distance_between_idxs(level, source_idx, *b))
+            .unwrap()
+    });
+    let flooded_percent = rng.range(MIN_FLOOD_PERCENT, MAX_FLOOD_PERCENT + 1) as usize;
+    let flooded_count = floor_tiles.len() * flooded_percent / 100;
+    for idx in floor_tiles.iter().take(flooded_count) {
+        level.surfaces[*idx] = Some(SurfaceType::Water);
+    }
+}
+
+fn update_level_from_room_features(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let mut updates: Vec<((i32, i32), TileType)> = vec![];
+    let mut flooded_rooms: Vec<Rect> = vec![];
+    for room in &level.rooms {
+        let rect = &room.rect;
+        for feature in &room.features {
+            if let Some(Flood) = feature {
+                flooded_rooms.push(*rect);
+            }
+            let mut additional_updates: Vec<((i32, i32), TileType)> = match feature {
+                Some(ColumnsSingleLeft) => column_placers::get_columns_left(rect, rng, 1),
+                Some(ColumnsDoubleLeft) => column_placers::get_columns_left(rect, rng, 2),
+                Some(ColumnsTripleLeft) => column_placers::get_columns_left(rect, rng, 3),
+                Some(ColumnsSingleRight) => column_placers::get_columns_right(rect, rng, 1),
+                Some(ColumnsDoubleRight) => column_placers::get_columns_right(rect, rng, 2),
+                Some(ColumnsTripleRight) => column_placers::get_columns_right(rect, rng, 3),
+                Some(ColumnsSingleBottom) => column_placers::get_columns_bottom(rect, rng, 1),
+                Some(ColumnsDoubleBottom) => column_placers::get_columns_bottom(rect, rng, 2),
+                Some(ColumnsTripleBottom) => column_placers::get_columns_bottom(rect, rng, 3),
+                Some(ColumnsSingleTop) => column_placers::get_columns_top(rect, rng, 1),
+                Some(ColumnsDoubleTop) => column_placers::get_columns_top(rect, rng, 2),
+                Some(ColumnsTripleTop) => column_placers::get_columns_top(rect, rng, 3),
+                Some(ColumnsSingleVertical) => column_placers::get_columns_vertical(rect, rng, 1),
+                Some(ColumnsDoubleVertical) => column_placers::get_columns_vertical(rect, rng, 2),
+                Some(ColumnsTripleVertical) => column_placers::get_columns_vertical(rect, rng, 3),
+                Some(ColumnsSingleHorizontal) => {
+                    column_placers::get_columns_horizontal(rect, rng, 1)
+                }
+                Some(ColumnsDoubleHorizontal) => {
+                    column_placers::get_columns_horizontal(rect, rng, 2)
+                }
+                Some(ColumnsTripleHorizontal) => {
+                    column_placers::get_columns_horizontal(rect, rng, 3)
+                }
+                Some(ColumnsSingleAll) => column_placers::get_columns_all(rect, rng, 1),
+                Some(ColumnsDoubleAll) => column_placers::get_columns_all(rect, rng, 2),
+                Some(ColumnsTripleAll) => column_placers::get_columns_all(rect, rng, 3),
+                Some(ColumnsSingleMiddle) => column_placers::get_columns_single_middle(rect),
+                Some(ColumnsDoubleMiddle) => column_placers::get_columns_double_middle(rect, rng),
+                _ => vec![],
+            };
+            updates.append(&mut additional_updates);
+        }
+    }
+    let level_width = level.width as u32;
+    for (xy, tile_type) in &updates {
+        if !level_utils::tile_at_xy_is_wall(level, xy.0, xy.1)
+            && !level_utils::tile_is_between_walls(level, xy.0, xy.1)
+            && !level_utils::tile_is_door_adjacent(level, xy.0, xy.1)
+        {
+            let idx = level_utils::xy_idx(level_width, xy.0, xy.1);
+            level.tiles[idx as usize] = *tile_type;
+        }
+    }
+    for rect in flooded_rooms.iter() {
+        flood_room(level, rect, rng);
+    }
+}
+
+fn update_level_from_room_stamps(level: &mut Level) {
+    let mut updates: Vec<(usize, usize, Option<TileType>)> = vec![];
+    for room in level.rooms.iter() {
+        for x in room.rect.x1..room.rect.x2 {
+            for y in room.rect.y1..room.rect.y2 {
+                let room_x = x - room.rect.x1;
+                let room_y = y - room.rect.y1;
+                let tile_type = match room.stamp.get_at((room_x as usize, room_y as usize)) {
+                    Some(Use(Ledge)) => Some(TileType::Ledge),
+                    Some(Use(WaterDeep)) => Some(TileType::WaterDeep),
+                    _ => None,
+                };
+                updates.push((x as usize, y as usize, tile_type));
+            }
+        }
+    }
+    let width = level.width as u32;
+    updates.iter().for_each(|(x, y, tile_type)| {
+        let idx = level_utils::xy_idx(width, *x as i32, *y as i32);
+        if let Some(t) = tile_type {
+            level.tiles[idx as usize] = *t;
+        }
+    });
+}
+
+// prefabs are centered in the room and written straight to the tiles as well as the stamp, so
+// the walls and doors they bring with them are there when the level data is populated.
+fn place_prefabs(level: &mut Level, rng: &mut RandomNumberGenerator, available_prefabs: &Prefabs) {
+    let width = level.width as u32;
+    for room_index in 0..level.rooms.len() {
+        let rect = level.rooms[room_index].rect;
+        if level_utils::rect_has_stairs(level, &rect) || rng.range(0, PREFAB_ROOM_CHANCE) != 0 {
+            continue;
+        }
+        let fitting: Vec<&prefabs::Prefab> = available_prefabs
+            .prefabs
+            .iter()
+            .filter(|p| !p.fortress && p.fits(&rect))
+            .collect();
+        let prefab = match rng.random_slice_entry(&fitting) {
+            Some(prefab) => *prefab,
+            None => continue,
+        };
+        let start_x = rect.x1 + 1 + (rect.width() - 2 - prefab.width()) / 2;
+        let start_y = rect.y1 + 1 + (rect.height() - 2 - prefab.height()) / 2;
+        let mut spawns = vec![];
+        for (y, row) in prefab.pattern.iter().enumerate() {
+            for (x, character) in row.chars().enumerate() {
+                let tile_x = start_x + x as i32;
+                let tile_y = start_y + y as i32;
+                let idx = level_utils::xy_idx(width, tile_x, tile_y);
+                let part = match prefab.get_cell(character) {
+                    PrefabCell::Transparent => continue,
+                    PrefabCell::Part(part) => part,
+                    PrefabCell::Spawn(spawn) => {
+                        spawns.push((idx, spawn));
+                        Floor
+                    }
+                };
+                level.tiles[idx] = prefabs::get_tile_for_room_part(part);
+                level.surfaces[idx] = None;
+                let stamp_at = ((tile_x - rect.x1) as usize, (tile_y - rect.y1) as usize);
+                level.rooms[room_index].stamp.set_at(stamp_at, Use(part));
+            }
+        }
+        let room = &mut level.rooms[room_index];
+        room.prefab = Some(prefab.name.clone());
+        room.prefab_spawns = spawns;
+    }
+}
+
+// prefab rooms have already been laid out by hand.
+fn decorate_level(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    level
+        .rooms
+        .iter_mut()
+        .filter(|room| room.prefab.is_none())
+        .for_each(|room| {
+            room_decorators::add_features_to_room(&mut room.stamp, &room.features, rng);
+            room_decorators::decorate_room(&mut room.stamp, &room.room_type, rng);
+        });
+}
+
+fn update_room_stamps_from_level(level: &mut Level) {
+    let mut updates: Vec<(usize, usize, usize, StampPart<RoomPart>)> = vec![];
+    level.rooms.iter_mut().for_each(|room| {
+        // the rect needs to be expanded at this point so that the stamp can have all 4 walls
+        // TODO: This feels messey and should be part of a level generation refactor.
+        room.rect.x2 = room.rect.x2 + 1;
+        room.rect.y2 = room.rect.y2 + 1;
+    });
+    for (room_index, room) in level.rooms.iter().enumerate() {
+        for x in room.rect.x1..room.rect.x2 {
+            for y in room.rect.y1..room.rect.y2 {
+                let room_x = x - room.rect.x1;
+                let room_y = y - room.rect.y1;
+                let room_stamp_part = match level_utils::get_tile_at_xy(level, x, y) {
+                    Some(&TileType::Floor) => Use(Floor),
+                    Some(&TileType::Wall) => Use(Wall),
+                    Some(&TileType::Door) => Use(Door),
+                    Some(&TileType::DownStairs) => Use(DownStairs),
+                    Some(&TileType::UpStairs) => Use(UpStairs),
+                    Some(&TileType::Exit) => Use(Exit),
+                    Some(&TileType::Column) => Use(Column),
+                    Some(&TileType::Ledge) => Use(Ledge),
+                    Some(&TileType::WaterDeep) => Use(WaterDeep),
+                    None => Transparent,
+                };
+                updates.push((
+                    room_index,
+                    room_x as usize,
+                    room_y as usize,
+                    room_stamp_part,
+                ));
+            }
+        }
+    }
+    updates
+        .iter()
+        .for_each(|(room_index, room_x, room_y, stamp_part)| {
+            level.rooms[*room_index]
+                .stamp
+                .set_at((*room_x, *room_y), stamp_part.clone());
+        });
+}
+
+fn add_rectangular_room(level: &mut Level, room: &Rect) {
+    let width = level.width as u32;
+    for y in room.y1 + 1..room.y2 {
+        for x in room.x1 + 1..room.x2 {
+            let idx = level_utils::xy_idx(width, x, y) as usize;
+            level_utils::set_tile_to_floor(level, idx);
+        }
+    }
+}
+
+fn add_circular_room(level: &mut Level, room: &Rect) {
+    let radius = i32::min(room.x2 - room.x1, room.y2 - room.y1) as f32 / 2.0;
+    let center_point = Point::from(room.center());
+    let width = level.width as u32;
+    for y in room.y1 + 1..room.y2 {
+        for x in room.x1 + 1..room.x2 {
+            let distance = Pythagoras.distance2d(center_point, rltk::Point::new(x, y));
+            if distance < radius {
+                let idx = level_utils::xy_idx(width, x, y);
+                level_utils::set_tile_to_floor(level, idx as usize);
+            }
+        }
+    }
+}
+
+fn add_horizontal_tunnel(level: &mut Level, x1: i32, x2: i32, y: i32) {
+    let width = level.width as u32;
+    for x in cmp::min(x1, x2)..=cmp::max(x1, x2) {
+        let idx = level_utils::xy_idx(width, x, y);
+        if level.tiles.get(idx).is_some() {
+            level_utils::set_tile_to_floor(level, idx as usize);
+        }
+    }
+}
+
+fn add_vertical_tunnel(level: &mut Level, x: i32, y1: i32, y2: i32) {
+    let width = level.width as u32;
+    for y in cmp::min(y1, y2)..=cmp::max(y1, y2) {
+        let idx = level_utils::xy_idx(width, x, y);
+        if level.tiles.get(idx).is_some() {
+            level_utils::set_tile_to_floor(level, idx as usize);
+        }
+    }
+}
+
+fn add_corridor(level: &mut Level, rng: &mut RandomNumberGenerator, from: Point, to: Point) {
+    if rng.range(0, 2) == 1 {
+        add_horizontal_tunnel(level, from.x, to.x, from.y);
+        add_vertical_tunnel(level, to.x, to.y, from.y);
+    } else {
+        add_vertical_tunnel(level, from.x, from.y, to.y);
+        add_horizontal_tunnel(level, from.x, to.x, to.y);
+    }
+}
+
+fn get_room_distance(level: &Level, i: usize, j: usize) -> f32 {
+    let from = Point::from(level.rooms[i].rect.center());
+    let to = Point::from(level.rooms[j].rect.center());
+    Pythagoras.distance2d(from, to)
+}
+
+// rooms are joined by a minimum spanning tree so every room is linked with as little digging as
+// possible, then a few extra corridors are added so the level isn't one long dead end.
+fn get_corridor_plan(level: &Level, rng: &mut RandomNumberGenerator) -> Vec<(usize, usize)> {
+    let room_count = level.rooms.len();
+    let mut in_tree: HashSet<usize> = HashSet::new();
+    let mut corridors: Vec<(usize, usize)> = vec![];
+    if room_count == 0 {
+        return corridors;
+    }
+    in_tree.insert(0);
+    while in_tree.len() < room_count {
+        let mut closest: Option<(usize, usize, f32)> = None;
+        for i in in_tree.iter() {
+            for j in (0..room_count).filter(|j| !in_tree.contains(j)) {
+                let distance = get_room_distance(level, *i, j);
+                match closest {
+                    Some((_, _, closest_distance)) if closest_distance <= distance => (),
+                    _ => closest = Some((*i, j, distance)),
+                }
+            }
+        }
+        if let Some((i, j, _)) = closest {
+            in_tree.insert(j);
+            corridors.push((i, j));
+        }
+    }
+    for i in 0..room_count {
+        if rng.range(0, EXTRA_CORRIDOR_CHANCE) != 0 {
+            continue;
+        }
+        let nearest_unlinked = (0..room_count)
+            .filter(|j| {
+                *j != i && !corridors.contains(&(i, *j)) && !corridors.contains(&(*j, i))
+            })
+            .min_by(|a, b| {
+                get_room_distance(level, i, *a)
+                    .partial_cmp(&get_room_distance(level, i, *b))
+                    .unwrap()
+            });
+        if let Some(j) = nearest_unlinked {
+            corridors.push((i, j));
+        }
+    }
+    corridors
+}
+
+fn add_spanning_tree_corridors(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    for (i, j) in get_corridor_plan(level, rng) {
+        let from = Point::from(level.rooms[i].rect.center());
+        let to = Point::from(level.rooms[j].rect.center());
+        add_corridor(level, rng, from, to);
+    }
+}
+
+// any room that can't be walked to from the first room gets a corridor dug from it to the
+// nearest tile that can.
+fn connect_unreachable_rooms(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let start_idx = match level.rooms.first() {
+        Some(room) => match level_utils::get_walkable_tiles_in_rect(&room.rect, level).first() {
+            Some(idx) => *idx,
+            None => return,
+        },
+        None => return,
+    };
+    let level_width = level.width as u32;
+    for i in 1..level.rooms.len() {
+        let reachable = level_utils::get_reachable_idxs(level, start_idx);
+        let room_tiles = level_utils::get_walkable_tiles_in_rect(&level.rooms[i].rect, level);
+        let from_idx = match room_tiles.first() {
+            Some(idx) if !reachable.contains(idx) => *idx,
+            _ => continue,
+        };
+        let nearest_reachable = reachable.iter().min_by(|a, b| {
+            level_utils::get_distance_between_idxs(level, from_idx, **a)
+                .partial_cmp(&level_utils::get_distance_between_idxs(level, from_idx, **b))
+                .unwrap()
+        });
+        if let Some(to_idx) = nearest_reachable {
+            let from = level_utils::idx_point(level_width, from_idx);
+            let to = level_utils::idx_point(level_width, *to_idx);
+            add_corridor(level, rng, from, to);
+        }
+    }
+}
+
+// a level is only kept if the stairs and every room can be reached from the way in.
+fn level_is_connected(level: &Level) -> bool {
+    if level.rooms.is_empty() {
+        return false;
+    }
+    let start_idx = match level_utils::get_entrance_idx(level) {
+        Some(idx) => idx,
+        None => return false,
+    };
+    let reachable = level_utils::get_reachable_idxs(level, start_idx);
+    let stairs_reachable = level_utils::all_stairs_reachable(level, &reachable);
+    stairs_reachable
+        && level.rooms.iter().all(|room| {
+            level_utils::get_walkable_tiles_in_rect(&room.rect, level)
+                .iter()
+                .filter(|idx| !level_utils::idx_has_pit(level, **idx))
+                .all(|idx| reachable.contains(idx))
+        })
+}
+
+// digs a line between the two spots, only breaking through what can't be walked on so any
+// stairs and doors along the way are left where they are.
+fn dig_repair_tunnel(level: &mut Level, from_idx: usize, to_idx: usize) {
+    for idx in level_utils::get_line_between_idxs(level, from_idx, to_idx) {
+        if !level_utils::is_exit_valid(level, idx) {
+            level_utils::set_tile_to_floor(level, idx);
+        }
+    }
+    if !level_utils::is_exit_valid(level, from_idx) {
+        level_utils::set_tile_to_floor(level, from_idx);
+    }
+}
+
+// every stairs and room tile that can't be reached from the way in is tunnelled through to the
+// nearest one that can, until there are none left.
+fn repair_level(level: &mut Level) {
+    let start_idx = match level_utils::get_entrance_idx(level) {
+        Some(idx) => idx,
+        None => return,
+    };
+    loop {
+        let reachable = level_utils::get_reachable_idxs(level, start_idx);
+        let unreachable = level
+            .stairs_up
+            .iter()
+            .chain(level.stairs_down.iter())
+            .copied()
+            .chain(
+                level
+                    .rooms
+                    .iter()
+                    .flat_map(|room| level_utils::get_walkable_tiles_in_rect(&room.rect, level)),
+            )
+            .find(|idx| !reachable.contains(idx));
+        let from_idx = match unreachable {
+            Some(idx) => idx,
+            None => break,
+        };
+        let nearest_reachable = reachable.iter().copied().min_by(|a, b| {
+            level_utils::get_distance_between_idxs(level, from_idx, *a)
+                .partial_cmp(&level_utils::get_distance_between_idxs(level, from_idx, *b))
+                .unwrap()
+        });
+        match nearest_reachable {
+            Some(to_idx) => dig_repair_tunnel(level, from_idx, to_idx),
+            None => break,
+        }
+    }
+    populate_level_data(level);
+}
+
+// This is the part that needs to determine the locations of door ents.
+fn add_doors_to_rooms(level: &mut Level) {
+    let mut door_idxs: Vec<usize> = Vec::new();
+    let level_width = level.width as u32;
+    for room in level.rooms.iter() {
+        for x in room.rect.x1..=room.rect.x2 {
+            for y in room.rect.y1..=room.rect.y2 {
+                // Checks the left and right walls of a room and adds a door if the tile is empty with a wall above & below
+                if x == room.rect.x1 || x == room.rect.x2 {
+                    if !level_utils::tile_at_xy_is_wall(&level, x, y)
+                        && level_utils::tile_is_between_walls_vertical(&level, x, y)
+                    {
+                        door_idxs.push(level_utils::xy_idx(level_width, x, y));
+                    }
+                }
+
+                // Checks the up and bottom walls of a room and adds a door if the tile is empty with a wall left & right
+                if y == room.rect.y1 || y == room.rect.y2 {
+                    if !level_utils::tile_at_xy_is_wall(&level, x, y)
+                        && level_utils::tile_is_between_walls_horizontal(level, x, y)
+                    {
+                        door_idxs.push(level_utils::xy_idx(level_width, x, y));
+                    }
+                }
+            }
+        }
+    }
+    for idx in door_idxs.iter() {
+        level_utils::set_tile_to_door(level, *idx as usize);
+    }
+}
+
+// the walls of a room that has already fallen in are the first to give way again.
+fn weaken_collapsed_room_walls(level: &mut Level) {
+    let level_width = level.width as u32;
+    let mut weak_idxs: Vec<usize> = Vec::new();
+    for room in level.rooms.iter() {
+        if room.room_type != Some(RoomType::Collapsed) {
+            continue;
+        }
+        for x in room.rect.x1..=room.rect.x2 {
+            for y in room.rect.y1..=room.rect.y2 {
+                let on_wall = x == room.rect.x1
+                    || x == room.rect.x2
+                    || y == room.rect.y1
+                    || y == room.rect.y2;
+                if on_wall {
+                    weak_idxs.push(level_utils::xy_idx(level_width, x, y));
+                }
+            }
+        }
+    }
+    for idx in weak_idxs.iter() {
+        if level_utils::tile_is_breakable(level, *idx) {
+            level.tile_hp[*idx] = i32::min(level.tile_hp[*idx], WEAK_WALL_HP);
+        }
+    }
+}
+
+// walls off every pocket of floor that can't be walked to from the largest open area.
+fn remove_unreachable_areas(level: &mut Level) {
+    let width = level.width as i32;
+    let mut visited: HashSet<usize> = HashSet::new();
+    let mut largest_area: HashSet<usize> = HashSet::new();
+    for start_idx in 0..level.tiles.len() {
+        if level.tiles[start_idx] != TileType::Floor || visited.contains(&start_idx) {
+            continue;
+        }
+        let mut area = HashSet::new();
+        let mut to_visit = vec![start_idx];
+        while let Some(idx) = to_visit.pop() {
+            if level.tiles[idx] != TileType::Floor || !area.insert(idx) {
+                continue;
+            }
+            let (x, y) = level_utils::idx_xy(width as u32, idx);
+            let neighbors = [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)];
+            for (neighbor_x, neighbor_y) in neighbors.iter() {
+                let neighbor = level_utils::get_tile_at_xy(level, *neighbor_x, *neighbor_y);
+                if let Some(TileType::Floor) = neighbor {
+                    to_visit.push(level_utils::xy_idx(width as u32, *neighbor_x, *neighbor_y));
+                }
+            }
+        }
+        visited.extend(area.iter());
+        if area.len() > largest_area.len() {
+            largest_area = area;
+        }
+    }
+    for idx in 0..level.tiles.len() {
+        if level.tiles[idx] == TileType::Floor && !largest_area.contains(&idx) {
+            level.tiles[idx] = TileType::Wall;
+        }
+    }
+}
+
+fn get_rooms_from_open_areas(level: &Level, rng: &mut RandomNumberGenerator) -> Vec<Room> {
+    let width = level.width as i32;
+    let height = level.height as i32;
+    let mut rooms = vec![];
+    for y in (1..height - 1).step_by(OPEN_AREA_HEIGHT as usize) {
+        for x in (1..width - 1).step_by(OPEN_AREA_WIDTH as usize) {
+            let area_width = i32::min(OPEN_AREA_WIDTH, width - 1 - x);
+            let area_height = i32::min(OPEN_AREA_HEIGHT, height - 1 - y);
+            let rect = Rect::new(x - 1, y - 1, area_width + 1, area_height + 1);
+            let floor_tiles = level_utils::get_walkable_tiles_in_rect(&rect, level);
+            if floor_tiles.len() >= MIN_OPEN_AREA_FLOOR_TILES {
+                rooms.push(Room::new(rect, rng));
+            }
+        }
+    }
+    rooms
+}
+
+fn make_rect_square(rect: &mut Rect) {
+    let size_height = rect.y2 - rect.y1;
+    let size_width = rect.x2 - rect.x1;
+    let smallest_side = i32::min(size_width, size_height);
+    rect.x1 = rect.x1 + (size_width - smallest_side) / 2;
+    rect.x2 = rect.x1 + smallest_side;
+    rect.y1 = rect.y1 + (size_height - smallest_side) / 2;
+    rect.y2 = rect.y1 + smallest_side;
+}
+
+// a dry spot in the room, or anywhere on the level with dry floor if the room has none left.
+// a level without any is left without the stairs, and thrown away.
+fn get_stairs_idx_in_room(
+    level: &Level,
+    rng: &mut RandomNumberGenerator,
+    room_index: usize,
+) -> Option<usize> {
+    let tiles = level_utils::filter_water_from_tiles(
+        level_utils::get_walkable_tiles_in_rect(&level.rooms[room_index].rect, level),
+        level,
+    );
+    match rng.random_slice_entry(&tiles) {
+        Some(idx) => Some(*idx),
+        None => level_utils::get_random_unblocked_floor_point(level, rng),
+    }
+}
+
+fn add_exit(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    if let Some(exit_idx) = get_stairs_idx_in_room(level, rng, 0) {
+        level.tiles[exit_idx] = TileType::Exit;
+        level.exit = Some(exit_idx);
+    }
+}
+
+// the first stairs each way go where they always have, any others are spread out over the rest
+// of the level.
+fn add_stairs_tile(
+    level: &mut Level,
+    rng: &mut RandomNumberGenerator,
+    first_room_index: usize,
+    stairs_number: usize,
+    tile_type: TileType,
+) -> Option<usize> {
+    let room_index = match stairs_number {
+        0 => first_room_index,
+        _ => rng.range(0, level.rooms.len()),
+    };
+    let stairs_idx = get_stairs_idx_in_room(level, rng, room_index)?;
+    level.tiles[stairs_idx] = tile_type;
+    Some(stairs_idx)
+}
+
+fn add_stairs(
+    level: &mut Level,
+    rng: &mut RandomNumberGenerator,
+    is_top_floor: bool,
+    up_stairs: usize,
+    down_stairs: usize,
+) {
+    // with nowhere to put them the level has no way in, and it's thrown away.
+    if level.rooms.is_empty() {
+        return;
+    }
+    if is_top_floor {
+        add_exit(level, rng);
+    }
+    for stairs_number in 0..up_stairs {
+        if let Some(idx) = add_stairs_tile(level, rng, 0, stairs_number, TileType::UpStairs) {
+            level.stairs_up.push(idx);
+        }
+    }
+    let last_room_index = level.rooms.len() - 1;
+    for stairs_number in 0..down_stairs {
+        if let Some(idx) =
+            add_stairs_tile(level, rng, last_room_index, stairs_number, TileType::DownStairs)
+        {
+            level.stairs_down.push(idx);
+        }
+    }
+}
+
+// every room of a cistern is standing in water.
+fn flood_cistern(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let rects: Vec<Rect> = level.rooms.iter().map(|room| room.rect).collect();
+    for rect in rects.iter() {
+        flood_room(level, rect, rng);
+    }
+}
+
+// pits are only dug out in the open, where there's always a way around them, and never
+// anywhere that can't be reached from the stairs.
+fn add_pits(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    if rng.range(0, PIT_FLOOR_CHANCE) != 0 {
+        return;
+    }
+    let mut candidates: Vec<usize> = get_landing_idxs(level)
+        .into_iter()
+        .filter(|idx| {
+            let neighbors = level_utils::get_neighbor_idxs(level, *idx);
+            neighbors.len() == 8
+                && neighbors
+                    .iter()
+                    .all(|neighbor| level.tiles[*neighbor] == TileType::Floor)
+        })
+        .collect();
+    candidates.sort();
+    for _ in 0..rng.range(1, MAX_PITS + 1) {
+        if candidates.len() == 0 {
+            break;
+        }
+        let idx = candidates.remove(rng.range(0, candidates.len()));
+        match rng.range(0, TRAPDOOR_CHANCE) {
+            0 => level.trapdoors.push(idx),
+            _ => level.pits.push(idx),
+        }
+    }
+}
+
+// anywhere open that can be walked to from the stairs, so whatever falls down a pit isn't left
+// stuck somewhere it can't get out of.
+fn get_landing_idxs(level: &Level) -> Vec<usize> {
+    let start_idx = match level_utils::get_entrance_idx(level) {
+        Some(idx) => idx,
+        None => return vec![],
+    };
+    let reachable = locks::get_reachable_without_keys(level, start_idx);
+    let mut landing_idxs: Vec<usize> = locks::get_reachable_floor_idxs(level, &reachable)
+        .into_iter()
+        .filter(|idx| !level_utils::idx_has_pit(level, *idx))
+        .collect();
+    landing_idxs.sort();
+    landing_idxs
+}
+
+fn populate_level_data(level: &mut Level) {
+    level_utils::populate_blocked(level);
+    level_utils::populate_opaque(level);
+    level_utils::populate_tile_hp(level);
+}
+
+// the top floor is always built the old way so the way in looks the same every game.
+fn get_builder_for_floor(
+    is_top_floor: bool,
+    branch: Option<Branch>,
+    prefabs: &Prefabs,
+    rng: &mut RandomNumberGenerator,
+) -> Box<dyn LevelBuilder> {
+    match branch {
+        Some(Branch::GoblinWarren) => return Box::new(CellularAutomataBuilder {}),
+        Some(Branch::FloodedCistern) => return Box::new(RoomsAndCorridorsBuilder {}),
+        None => (),
+    }
+    if is_top_floor {
+        return Box::new(RoomsAndCorridorsBuilder {});
+    }
+    match rng.range(0, 5) {
+        0 => Box::new(CellularAutomataBuilder {}),
+        1 => Box::new(DrunkardsWalkBuilder {}),
+        2 => Box::new(FortressBuilder {
+            prefabs: prefabs.prefabs.clone(),
+        }),
+        _ => Box::new(RoomsAndCorridorsBuilder {}),
+    }
+}
+
+// everything a level needs to know about the levels around it, decided before any of them are
+// built so the stairs on either side of a flight match up.
+#[derive(Clone, Copy, Debug)]
+pub struct LevelPlan {
+    pub id: u8,
+    pub depth: u8,
+    pub is_top_floor: bool,
+    pub up_stairs: usize,
+    pub down_stairs: usize,
+    pub has_floor_below: bool,
+    pub branch: Option<Branch>,
+}
+
+fn build_level(
+    plan: LevelPlan,
+    builder: Box<dyn LevelBuilder>,
+    prefabs: &Prefabs,
+    rng: &mut RandomNumberGenerator,
+) -> Level {
+    let prefabs = prefabs.clone();
+    BuilderChain::new(builder)
+        .with(connect_unreachable_rooms)
+        .with(update_level_from_room_features)
+        .with(move |level, rng| {
+            if plan.branch == Some(Branch::FloodedCistern) {
+                flood_cistern(level, rng);
+            }
+        })
+        .with(move |level, rng| {
+            add_stairs(level, rng, plan.is_top_floor, plan.up_stairs, plan.down_stairs)
+        })
+        .with(|level, _rng| update_room_stamps_from_level(level))
+        .with(move |level, rng| place_prefabs(level, rng, &prefabs))
+        .with(decorate_level)
+        .with(|level, _rng| update_level_from_room_stamps(level))
+        .with(|level, _rng| populate_level_data(level))
+        .with(|level, _rng| weaken_collapsed_room_walls(level))
+        .build(plan.depth, rng)
+}
+
+// nothing is added that could cut any part of the room off.
+fn build_fallback_level(plan: LevelPlan, rng: &mut RandomNumberGenerator) -> Level {
+    BuilderChain::new(Box::new(SingleRoomBuilder {}))
+        .with(move |level, rng| {
+            add_stairs(level, rng, plan.is_top_floor, plan.up_stairs, plan.down_stairs)
+        })
+        .with(|level, _rng| update_room_stamps_from_level(level))
+        .with(|level, _rng| populate_level_data(level))
+        .build(plan.depth, rng)
+}
+
+// overlays are tried out on a copy of the level, and thrown away if they'd cut anything off.
+fn apply_overlay(
+    level: &mut Level,
+    rng: &mut RandomNumberGenerator,
+    overlay: impl FnOnce(&mut Level, &mut RandomNumberGenerator),
+) {
+    let mut overlaid = level.clone();
+    overlay(&mut overlaid, rng);
+    if level_is_connected(&overlaid) {
+        *level = overlaid;
+    }
+}
+
+// the stairs on either side of a flight have to match up, so a level missing any it was planned
+// with is no use however well connected it is.
+fn level_has_planned_stairs(level: &Level, plan: &LevelPlan) -> bool {
+    level.stairs_up.len() == plan.up_stairs
+        && level.stairs_down.len() == plan.down_stairs
+        && (!plan.is_top_floor || level.exit.is_some())
+}
+
+fn level_is_usable(level: &Level, plan: &LevelPlan) -> bool {
+    level_has_planned_stairs(level, plan) && level_is_connected(level)
+}
+
+pub fn build(plan: LevelPlan, prefabs: &Prefabs, rng: &mut RandomNumberGenerator) -> Level {
+    let builder = get_builder_for_floor(plan.is_top_floor, plan.branch, prefabs, rng);
+    let mut level = build_level(plan, builder, prefabs, rng);
+    for _ in 1..MAX_BUILD_ATTEMPTS {
+        if level_is_usable(&level, &plan) {
+            break;
+        }
+        let builder = get_builder_for_floor(plan.is_top_floor, plan.branch, prefabs, rng);
+        level = build_level(plan, builder, prefabs, rng);
+    }
+    if !level_is_usable(&level, &plan) {
+        repair_level(&mut level);
+    }
+    // only a level without any rooms is beyond repair, and rooms and corridors always has some.
+    for _ in 0..MAX_BUILD_ATTEMPTS {
+        if level_is_usable(&level, &plan) {
+            break;
+        }
+        level = build_level(plan, Box::new(RoomsAndCorridorsBuilder {}), prefabs, rng);
+        repair_level(&mut level);
+    }
+    if !level_is_usable(&level, &plan) {
+        level = build_fallback_level(plan, rng);
+    }
+    level.id = plan.id;
+    level.branch = plan.branch;
+    level.has_floor_below = plan.has_floor_below;
+    // the goblin warren is the goblins' own.
+    let invasion = match plan.branch {
+        Some(Branch::GoblinWarren) => Some(Invasion::Goblin),
+        Some(_) => None,
+        None => invasion::get_invasion_for_floor(plan.is_top_floor, rng),
+    };
+    if let Some(invasion) = invasion {
+        apply_overlay(&mut level, rng, |level, rng| invasion::apply_invasion(level, invasion, rng));
+    }
+    // secrets go in first so no key is ever left somewhere only a search would turn up.
+    apply_overlay(&mut level, rng, secrets::add_secret_room);
+    apply_overlay(&mut level, rng, secrets::add_secret_passages);
+    // key ids are numbered by depth, which a branch shares with the floor below it.
+    if plan.branch.is_none() {
+        apply_overlay(&mut level, rng, locks::add_locked_rooms);
+    }
+    if plan.has_floor_below {
+        apply_overlay(&mut level, rng, add_pits);
+    }
+    level
+}
+
+// floors are numbered from the bottom up, and each is joined to the one below it by one or more
+// flights of stairs. branches hang off a floor by their own stairs and are numbered after the
+// main stack.
+pub fn build_dungeon(floors: u8, prefabs: &Prefabs) -> Dungeon {
+    let mut rng = RandomNumberGenerator::new();
+    let flights: Vec<usize> = (0..floors)
+        .map(|floor| match floor {
+            0 => 0,
+            _ => rng.range(1, MAX_FLIGHTS_OF_STAIRS + 1) as usize,
+        })
+        .collect();
+    let branches: Vec<Option<Branch>> = (0..floors)
+        .map(|floor| branch::get_branch_for_floor(floor == floors - 1, floor == 0, &mut rng))
+        .collect();
+    let mut levels = HashMap::new();
+    let mut connections = vec![];
+    for floor in 0..floors {
+        let is_top_floor = floor == floors - 1;
+        let plan = LevelPlan {
+            id: floor,
+            depth: floor,
+            is_top_floor,
+            up_stairs: match is_top_floor {
+                true => 0,
+                false => flights[floor as usize + 1],
+            },
+            down_stairs: flights[floor as usize] + branches[floor as usize].map_or(0, |_| 1),
+            has_floor_below: floor > 0,
+            branch: None,
+        };
+        levels.insert(floor, build(plan, prefabs, &mut rng));
+    }
+    let mut next_level_id = floors;
+    for (floor, branch) in branches.iter().enumerate() {
+        let floor = floor as u8;
+        if let Some(branch) = branch {
+            let plan = LevelPlan {
+                id: next_level_id,
+                depth: floor - 1,
+                is_top_floor: false,
+                up_stairs: 1,
+                down_stairs: 0,
+                has_floor_below: false,
+                branch: Some(*branch),
+            };
+            let branch_level = build(plan, prefabs, &mut rng);
+            let from_idx = levels[&floor].stairs_down[flights[floor as usize]];
+            connections.push(Connection {
+                kind: ConnectionKind::Stairs,
+                from_level: floor,
+                from_idx,
+                to_level: next_level_id,
+                to_idx: branch_level.stairs_up[0],
+            });
+            levels.insert(next_level_id, branch_level);
+            next_level_id += 1;
+        }
+    }
+    for floor in 1..floors {
+        let upper = &levels[&floor];
+        let lower = &levels[&(floor - 1)];
+        for flight in 0..flights[floor as usize] {
+            connections.push(Connection {
+                kind: ConnectionKind::Stairs,
+                from_level: floor,
+                from_idx: upper.stairs_down[flight],
+                to_level: floor - 1,
+                to_idx: lower.stairs_up[flight],
+            });
+        }
+        let landing_idxs = get_landing_idxs(lower);
+        for idx in upper.pits.iter().chain(upper.trapdoors.iter()) {
+            if let Some(to_idx) = rng.random_slice_entry(&landing_idxs) {
+                connections.push(Connection {
+                    kind: ConnectionKind::Pit,
+                    from_level: floor,
+                    from_idx: *idx,
+                    to_level: floor - 1,
+                    to_idx: *to_idx,
+                });
+            }
+        }
+    }
+    Dungeon {
+        levels,
+        connections,
+    }
+}
@@ -0,0 +1,29 @@
+use super::{
+    add_circular_room, add_doors_to_rooms, add_rectangular_room, add_spanning_tree_corridors,
+    generate_rects_for_level, make_rect_square, LevelBuilder,
+};
+use crate::dungeon::{level::Level, room::Room, room_placement};
+use crate::utils::get_x_random_elements;
+use rltk::RandomNumberGenerator;
+
+// rooms cut from a recursively split map and joined up by a spanning tree of corridors.
+pub struct RoomsAndCorridorsBuilder {}
+
+impl LevelBuilder for RoomsAndCorridorsBuilder {
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator) {
+        let mut rects = generate_rects_for_level(level.width as i32, level.height as i32, rng);
+        let room_count = rng.range(2, rects.len() as i32);
+        let mut room_rects = get_x_random_elements(rng, room_count as u32, &mut rects);
+        room_rects.iter_mut().for_each(|r| match rng.range(0, 6) {
+            1 => {
+                make_rect_square(r);
+                add_circular_room(level, r)
+            }
+            _ => add_rectangular_room(level, r),
+        });
+        level.rooms = room_rects.iter().map(|r| Room::new(*r, rng)).collect();
+        add_spanning_tree_corridors(level, rng);
+        add_doors_to_rooms(level);
+        room_placement::assign_room_types(level, rng);
+    }
+}
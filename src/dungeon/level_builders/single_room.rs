@@ -0,0 +1,15 @@
+use super::{add_rectangular_room, LevelBuilder};
+use crate::dungeon::{level::Level, rect::Rect, room::Room};
+use rltk::RandomNumberGenerator;
+
+// one bare room filling the whole level, which can always be got around. it's only used when
+// every other builder has failed to come up with a level that works.
+pub struct SingleRoomBuilder {}
+
+impl LevelBuilder for SingleRoomBuilder {
+    fn build_map(&self, level: &mut Level, rng: &mut RandomNumberGenerator) {
+        let rect = Rect::new(0, 0, level.width as i32 - 1, level.height as i32 - 1);
+        add_rectangular_room(level, &rect);
+        level.rooms = vec![Room::new(rect, rng)];
+    }
+}
@@ -1,7 +1,12 @@
 use std::collections::HashSet;
 
-use super::{level::Level, rect::Rect, tile_type::TileType};
-use rltk::{DistanceAlg::Pythagoras, Point, RandomNumberGenerator};
+use super::{
+    constants::{COLUMN_HP, WALL_HP},
+    level::Level,
+    rect::Rect,
+    tile_type::TileType,
+};
+use rltk::{DistanceAlg::Pythagoras, LineAlg, Point, RandomNumberGenerator};
 use specs::Entity;
 
 pub fn xy_idx(width: u32, x: i32, y: i32) -> usize {
@@ -12,17 +17,25 @@ pub fn idx_xy(width: u32, idx: usize) -> (i32, i32) {
     (idx as i32 % width as i32, idx as i32 / width as i32)
 }
 
-pub fn add_xy_to_idx(width: i32, x: i32, y: i32, idx: i32) -> i32 {
-    idx + x + width * y
-}
-
 pub fn idx_point(width: u32, idx: usize) -> Point {
     let (x, y) = idx_xy(width, idx);
     Point::new(x, y)
 }
 
+pub fn xy_in_level(level: &Level, x: i32, y: i32) -> bool {
+    x >= 0 && y >= 0 && x < level.width as i32 && y < level.height as i32
+}
+
+// the index of a spot on the level, or none if it's off the edge rather than wrapping around.
+pub fn get_idx_at_xy(level: &Level, x: i32, y: i32) -> Option<usize> {
+    match xy_in_level(level, x, y) {
+        true => Some(xy_idx(level.width as u32, x, y)),
+        false => None,
+    }
+}
+
 pub fn get_tile_at_xy(level: &Level, x: i32, y: i32) -> Option<&TileType> {
-    level.tiles.get(xy_idx(level.width as u32, x, y) as usize)
+    get_idx_at_xy(level, x, y).and_then(|idx| level.tiles.get(idx))
 }
 
 pub fn tile_at_xy_is_wall(level: &Level, x: i32, y: i32) -> bool {
@@ -82,6 +95,29 @@ pub fn populate_opaque(level: &mut Level) {
     }
 }
 
+// the outer edge of the map holds everything up, so it can never be broken.
+pub fn populate_tile_hp(level: &mut Level) {
+    let width = level.width as i32;
+    let height = level.height as i32;
+    for (i, tile) in level.tiles.iter().enumerate() {
+        let (x, y) = idx_xy(level.width as u32, i);
+        let on_edge = x == 0 || y == 0 || x == width - 1 || y == height - 1;
+        level.tile_hp[i] = match *tile {
+            _ if on_edge => 0,
+            TileType::Wall => WALL_HP,
+            TileType::Column => COLUMN_HP,
+            _ => 0,
+        }
+    }
+}
+
+pub fn tile_is_breakable(level: &Level, idx: usize) -> bool {
+    match level.tile_hp.get(idx) {
+        Some(hp) => *hp > 0,
+        None => false,
+    }
+}
+
 pub fn tile_is_blocked(idx: usize, level: &Level) -> bool {
     level.blocked[idx]
 }
@@ -91,13 +127,34 @@ pub fn idx_not_in_map(level: &Level, idx: usize) -> bool {
 }
 
 pub fn is_exit_valid(level: &Level, idx: usize) -> bool {
-    if idx > level.tiles.len() {
+    if idx >= level.tiles.len() {
         return false;
     }
     let tile = level.tiles[idx];
     !(tile == TileType::Wall || tile == TileType::Column || tile == TileType::Ledge)
 }
 
+// closed doors are assumed to open, but anything else blocking a tile is in the way, and nobody
+// walks through a pit.
+pub fn can_walk_through(level: &Level, idx: usize) -> bool {
+    is_exit_valid(level, idx)
+        && (!level.blocked[idx] || level.tiles[idx] == TileType::Door)
+        && !idx_has_pit(level, idx)
+}
+
+// every tile that can be walked to from the start.
+pub fn get_reachable_idxs(level: &Level, start_idx: usize) -> HashSet<usize> {
+    let mut reachable = HashSet::new();
+    let mut to_visit = vec![start_idx];
+    while let Some(idx) = to_visit.pop() {
+        if idx_not_in_map(level, idx) || !can_walk_through(level, idx) || !reachable.insert(idx) {
+            continue;
+        }
+        to_visit.append(&mut get_neighbor_idxs(level, idx));
+    }
+    reachable
+}
+
 pub fn clear_content_index(level: &mut Level) {
     for content in level.tile_content.iter_mut() {
         content.clear();
@@ -127,6 +184,36 @@ pub fn filter_water_from_tiles(tiles: Vec<usize>, level: &Level) -> Vec<usize> {
         .collect()
 }
 
+pub fn rect_has_stairs(level: &Level, rect: &Rect) -> bool {
+    let width = level.width as u32;
+    level
+        .exit
+        .iter()
+        .chain(level.stairs_up.iter())
+        .chain(level.stairs_down.iter())
+        .any(|idx| {
+            let (x, y) = idx_xy(width, *idx);
+            rect.contains(x, y)
+        })
+}
+
+// the way into a level, the exit on the top floor and the first stairs up everywhere else.
+pub fn get_entrance_idx(level: &Level) -> Option<usize> {
+    level.exit.or(level.stairs_up.first().copied())
+}
+
+pub fn all_stairs_reachable(level: &Level, reachable: &HashSet<usize>) -> bool {
+    level
+        .stairs_up
+        .iter()
+        .chain(level.stairs_down.iter())
+        .all(|idx| reachable.contains(idx))
+}
+
+pub fn idx_has_pit(level: &Level, idx: usize) -> bool {
+    level.pits.contains(&idx) || level.trapdoors.contains(&idx)
+}
+
 pub fn get_random_spawn_point(
     rect: &Rect,
     level: &Level,
@@ -214,15 +301,31 @@ pub fn get_distance_between_idxs(level: &Level, idx1: usize, idx2: usize) -> f32
     Pythagoras.distance2d(point1, point2)
 }
 
-pub fn get_neighbors_for_idx(level_width: i32, idx: i32) -> [i32; 8] {
+pub fn get_line_between_idxs(level: &Level, start_idx: usize, end_idx: usize) -> Vec<usize> {
+    let start = idx_point(level.width as u32, start_idx);
+    let end = idx_point(level.width as u32, end_idx);
+    rltk::line2d(LineAlg::Bresenham, start, end)
+        .iter()
+        .map(|p| xy_idx(level.width as u32, p.x, p.y))
+        .filter(|idx| *idx != start_idx)
+        .collect()
+}
+
+// the tiles around a spot, leaving out any that would be off the edge of the level rather than
+// wrapping around to the other side of it.
+pub fn get_neighbor_idxs(level: &Level, idx: usize) -> Vec<usize> {
+    let (x, y) = idx_xy(level.width as u32, idx);
     [
-        idx + 1,
-        idx - 1,
-        idx - level_width,
-        idx + level_width,
-        idx + 1 - level_width,
-        idx + 1 + level_width,
-        idx - 1 - level_width,
-        idx - 1 + level_width,
+        (1, 0),
+        (-1, 0),
+        (0, -1),
+        (0, 1),
+        (1, -1),
+        (1, 1),
+        (-1, -1),
+        (-1, 1),
     ]
+    .iter()
+    .filter_map(|(dx, dy)| get_idx_at_xy(level, x + dx, y + dy))
+    .collect()
 }
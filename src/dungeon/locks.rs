@@ -0,0 +1,179 @@
+use super::{level::Level, level_utils, room_decorators::RoomPart, secrets, tile_type::TileType};
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+use stamp_rs::StampPart::Use;
+use std::collections::HashSet;
+
+// one in this many rooms that could be locked away is.
+const LOCKED_ROOM_CHANCE: i32 = 6;
+const MAX_LOCKED_ROOMS: usize = 3;
+// one in this many keys is carried by a goblin rather than left in a chest.
+const CARRIED_KEY_CHANCE: i32 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum KeyPlacement {
+    // in the chest standing at the key's idx.
+    Container,
+    // on a goblin standing at the key's idx.
+    Carried,
+}
+
+pub fn get_key_id(depth: u8, lock_number: usize) -> u32 {
+    depth as u32 * 100 + lock_number as u32
+}
+
+pub fn idx_is_locked(level: &Level, idx: usize) -> bool {
+    level.locked_doors.iter().any(|(i, _)| *i == idx)
+}
+
+pub fn unlock_idx(level: &mut Level, idx: usize) {
+    level.locked_doors.retain(|(i, _)| *i != idx);
+}
+
+// everything that can be walked to from the start without a key or knowing where the secret
+// doors are.
+pub fn get_reachable_without_keys(level: &Level, start_idx: usize) -> HashSet<usize> {
+    let mut reachable = HashSet::new();
+    let mut to_visit = vec![start_idx];
+    while let Some(idx) = to_visit.pop() {
+        if level_utils::idx_not_in_map(level, idx)
+            || !level_utils::can_walk_through(level, idx)
+            || idx_is_locked(level, idx)
+            || secrets::idx_is_secret(level, idx)
+            || !reachable.insert(idx)
+        {
+            continue;
+        }
+        to_visit.append(&mut level_utils::get_neighbor_idxs(level, idx));
+    }
+    reachable
+}
+
+pub fn get_door_idxs_for_room(level: &Level, room_index: usize) -> Vec<usize> {
+    let rect = level.rooms[room_index].rect;
+    let width = level.width as u32;
+    (rect.y1..rect.y2)
+        .map(|y| (rect.x1..rect.x2).map(move |x| (x, y)))
+        .flatten()
+        .map(|(x, y)| level_utils::xy_idx(width, x, y))
+        .filter(|idx| level.tiles[*idx] == TileType::Door)
+        .collect()
+}
+
+// chests sit on blocked tiles, so they count as reachable if something next to them is.
+fn get_reachable_chest_idxs(level: &Level, reachable: &HashSet<usize>) -> Vec<usize> {
+    let width = level.width as u32;
+    let mut chests = vec![];
+    for room in level.rooms.iter() {
+        for (y, row) in room.stamp.pattern.iter().enumerate() {
+            for (x, part) in row.iter().enumerate() {
+                let is_chest = match part {
+                    Use(RoomPart::Chest) => true,
+                    _ => false,
+                };
+                if !is_chest {
+                    continue;
+                }
+                let idx =
+                    level_utils::xy_idx(width, room.rect.x1 + x as i32, room.rect.y1 + y as i32);
+                let next_to_reachable = level_utils::get_neighbor_idxs(level, idx)
+                    .iter()
+                    .any(|neighbor| reachable.contains(neighbor));
+                if next_to_reachable && !level.keys.iter().any(|(i, _, _)| *i == idx) {
+                    chests.push(idx);
+                }
+            }
+        }
+    }
+    chests
+}
+
+// furniture hasn't been spawned yet, so the stamps are all there is to say what's in the way.
+pub fn get_stamped_idxs(level: &Level) -> HashSet<usize> {
+    let width = level.width as u32;
+    let mut stamped = HashSet::new();
+    for room in level.rooms.iter() {
+        for (y, row) in room.stamp.pattern.iter().enumerate() {
+            for (x, part) in row.iter().enumerate() {
+                match part {
+                    Use(RoomPart::Floor) => (),
+                    Use(_) => {
+                        stamped.insert(level_utils::xy_idx(
+                            width,
+                            room.rect.x1 + x as i32,
+                            room.rect.y1 + y as i32,
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+    stamped
+}
+
+pub fn get_reachable_floor_idxs(level: &Level, reachable: &HashSet<usize>) -> Vec<usize> {
+    let stamped = get_stamped_idxs(level);
+    reachable
+        .iter()
+        .filter(|idx| {
+            level.tiles[**idx] == TileType::Floor
+                && !stamped.contains(*idx)
+                && !level.blocked[**idx]
+                && !level.keys.iter().any(|(i, _, _)| i == *idx)
+                && !level.invasion_spawns.iter().any(|(i, _)| i == *idx)
+        })
+        .cloned()
+        .collect()
+}
+
+// every door of a locked room is locked, and its key is put somewhere that can be reached from
+// the start without passing through any locked door, so there's never a key locked away behind
+// the door it opens.
+pub fn add_locked_rooms(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let start_idx = match level_utils::get_entrance_idx(level) {
+        Some(idx) => idx,
+        None => return,
+    };
+    let mut locked_rooms = 0;
+    for room_index in 1..level.rooms.len() {
+        if locked_rooms >= MAX_LOCKED_ROOMS {
+            break;
+        }
+        let rect = level.rooms[room_index].rect;
+        if level_utils::rect_has_stairs(level, &rect) || rng.range(0, LOCKED_ROOM_CHANCE) != 0 {
+            continue;
+        }
+        let doors = get_door_idxs_for_room(level, room_index);
+        let already_hidden = doors
+            .iter()
+            .any(|idx| idx_is_locked(level, *idx) || secrets::idx_is_secret(level, *idx));
+        if doors.len() == 0 || already_hidden {
+            continue;
+        }
+        let key_id = get_key_id(level.depth, locked_rooms);
+        for idx in doors.iter() {
+            level.locked_doors.push((*idx, key_id));
+        }
+        let reachable = get_reachable_without_keys(level, start_idx);
+        let stairs_reachable = level_utils::all_stairs_reachable(level, &reachable);
+        let chests = get_reachable_chest_idxs(level, &reachable);
+        let floor = get_reachable_floor_idxs(level, &reachable);
+        let placement = if chests.len() > 0 && rng.range(0, CARRIED_KEY_CHANCE) != 0 {
+            rng.random_slice_entry(&chests)
+                .map(|idx| (*idx, KeyPlacement::Container))
+        } else {
+            rng.random_slice_entry(&floor)
+                .map(|idx| (*idx, KeyPlacement::Carried))
+        };
+        match placement {
+            Some((idx, placement)) if stairs_reachable => {
+                level.keys.push((idx, key_id, placement));
+                locked_rooms += 1;
+            }
+            _ => {
+                level.locked_doors.retain(|(_, id)| *id != key_id);
+            }
+        }
+    }
+}
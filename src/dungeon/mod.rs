@@ -1,11 +1,19 @@
+pub mod branch;
 pub mod column_placers;
 pub mod constants;
 pub mod dungeon;
+pub mod floor_theme;
+pub mod invasion;
 pub mod level;
 pub mod level_builders;
 pub mod level_utils;
+pub mod locks;
+pub mod prefabs;
 pub mod rect;
 pub mod room;
 pub mod room_decorators;
 pub mod room_feature;
+pub mod room_placement;
+pub mod secrets;
+pub mod surface_type;
 pub mod tile_type;
@@ -0,0 +1,198 @@
+use super::{rect::Rect, room_decorators::RoomPart, tile_type::TileType};
+use ron::from_str;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::{env, fs};
+
+const PREFABS_STRING: &str = include_str!("../data/prefabs.ron");
+
+// things placed by a prefab that aren't part of the room itself.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PrefabSpawn {
+    Goblin,
+    Item,
+    Trap,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PrefabCell {
+    Part(RoomPart),
+    Spawn(PrefabSpawn),
+    // leaves whatever the room already had in this spot.
+    Transparent,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Prefab {
+    pub name: String,
+    pub pattern: Vec<String>,
+    // characters that mean something different in this prefab than in the default legend.
+    #[serde(default)]
+    pub legend: HashMap<char, PrefabCell>,
+    // the largest room interior this prefab can be placed in, so it isn't lost in a huge hall.
+    #[serde(default)]
+    pub max_width: Option<i32>,
+    #[serde(default)]
+    pub max_height: Option<i32>,
+    // fortress prefabs are laid into its rooms as the walls go up, and aren't placed anywhere else.
+    #[serde(default)]
+    pub fortress: bool,
+}
+
+fn get_default_cell(character: char) -> Option<PrefabCell> {
+    let cell = match character {
+        ' ' => PrefabCell::Transparent,
+        '.' => PrefabCell::Part(RoomPart::Floor),
+        '#' => PrefabCell::Part(RoomPart::Wall),
+        '+' => PrefabCell::Part(RoomPart::Door),
+        'O' => PrefabCell::Part(RoomPart::Column),
+        '=' => PrefabCell::Part(RoomPart::Ledge),
+        '~' => PrefabCell::Part(RoomPart::WaterDeep),
+        'B' => PrefabCell::Part(RoomPart::Bed),
+        'A' => PrefabCell::Part(RoomPart::Armoire),
+        'D' => PrefabCell::Part(RoomPart::Dresser),
+        'b' => PrefabCell::Part(RoomPart::BedsideTable),
+        'C' => PrefabCell::Part(RoomPart::Chest),
+        'h' => PrefabCell::Part(RoomPart::Chair),
+        'd' => PrefabCell::Part(RoomPart::Desk),
+        's' => PrefabCell::Part(RoomPart::Shelf),
+        't' => PrefabCell::Part(RoomPart::Table),
+        'S' => PrefabCell::Part(RoomPart::Stove),
+        'c' => PrefabCell::Part(RoomPart::Cupboard),
+        'k' => PrefabCell::Part(RoomPart::Counter),
+        'r' => PrefabCell::Part(RoomPart::Barrel),
+        'W' => PrefabCell::Part(RoomPart::WeaponRack),
+        ',' => PrefabCell::Part(RoomPart::Debris),
+        'T' => PrefabCell::Part(RoomPart::TowelRack),
+        'Y' => PrefabCell::Part(RoomPart::Throne),
+        'P' => PrefabCell::Part(RoomPart::Podium),
+        '*' => PrefabCell::Part(RoomPart::Sconce),
+        'g' => PrefabCell::Spawn(PrefabSpawn::Goblin),
+        '!' => PrefabCell::Spawn(PrefabSpawn::Item),
+        '^' => PrefabCell::Spawn(PrefabSpawn::Trap),
+        _ => return None,
+    };
+    Some(cell)
+}
+
+// the tile a room part sits on, furniture and spawns stand on plain floor.
+pub fn get_tile_for_room_part(part: RoomPart) -> TileType {
+    match part {
+        RoomPart::Wall => TileType::Wall,
+        RoomPart::Door => TileType::Door,
+        RoomPart::Column => TileType::Column,
+        RoomPart::Ledge => TileType::Ledge,
+        RoomPart::WaterDeep => TileType::WaterDeep,
+        _ => TileType::Floor,
+    }
+}
+
+impl Prefab {
+    pub fn width(&self) -> i32 {
+        match self.pattern.first() {
+            Some(row) => row.chars().count() as i32,
+            None => 0,
+        }
+    }
+
+    pub fn height(&self) -> i32 {
+        self.pattern.len() as i32
+    }
+
+    pub fn get_cell(&self, character: char) -> PrefabCell {
+        match self.legend.get(&character) {
+            Some(cell) => *cell,
+            None => get_default_cell(character).unwrap_or(PrefabCell::Transparent),
+        }
+    }
+
+    // rects here have already been expanded to include all 4 walls, the prefab has to fit
+    // between them.
+    pub fn fits(&self, rect: &Rect) -> bool {
+        let interior_width = rect.width() - 2;
+        let interior_height = rect.height() - 2;
+        interior_width >= self.width()
+            && interior_height >= self.height()
+            && self.max_width.map_or(true, |max| interior_width <= max)
+            && self.max_height.map_or(true, |max| interior_height <= max)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let width = self.width() as usize;
+        if width == 0 {
+            return Err(format!("prefab {} has an empty pattern", self.name));
+        }
+        for row in self.pattern.iter() {
+            if row.chars().count() != width {
+                return Err(format!("prefab {} has rows of different widths", self.name));
+            }
+            for character in row.chars() {
+                if !self.legend.contains_key(&character) && get_default_cell(character).is_none() {
+                    return Err(format!(
+                        "prefab {} uses '{}' which isn't in the legend",
+                        self.name, character
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_prefabs(text: &str) -> Result<Vec<Prefab>, String> {
+    let prefabs = from_str::<Vec<Prefab>>(text).map_err(|e| e.to_string())?;
+    for prefab in prefabs.iter() {
+        prefab.validate()?;
+    }
+    Ok(prefabs)
+}
+
+// designers can try out new rooms without rebuilding the game by pointing this at a file of
+// their own, they're placed alongside the built in ones.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_extra_prefabs() -> Result<Vec<Prefab>, String> {
+    let path = match env::var("APPRENTICE_PREFABS") {
+        Ok(path) => path,
+        Err(_) => return Ok(vec![]),
+    };
+    fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| parse_prefabs(&text))
+        .map_err(|e| format!("{}: {}", path, e))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn load_extra_prefabs() -> Result<Vec<Prefab>, String> {
+    Ok(vec![])
+}
+
+// every prefab in the game, read once when the game starts. Each level's build chain keeps a
+// handle to them, so they're shared rather than copied.
+#[derive(Clone)]
+pub struct Prefabs {
+    pub prefabs: Arc<Vec<Prefab>>,
+    // why the prefabs couldn't be read, passed on to the player when a game starts.
+    pub load_error: Option<String>,
+}
+
+impl Prefabs {
+    pub fn new() -> Result<Self, String> {
+        let mut prefabs =
+            parse_prefabs(PREFABS_STRING).map_err(|e| format!("prefabs.ron: {}", e))?;
+        prefabs.append(&mut load_extra_prefabs()?);
+        Ok(Prefabs {
+            prefabs: Arc::new(prefabs),
+            load_error: None,
+        })
+    }
+
+    // levels can still be built without any prefabs, they just have plainer rooms.
+    pub fn load() -> Self {
+        Prefabs::new().unwrap_or_else(|e| Prefabs {
+            prefabs: Arc::new(vec![]),
+            load_error: Some(e),
+        })
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    prefabs::PrefabSpawn,
     rect::Rect,
     room_decorators::{RoomPart, RoomPart::Floor, RoomType},
     room_feature::RoomFeature,
@@ -14,56 +15,21 @@ pub struct Room {
     pub room_type: Option<RoomType>,
     pub stamp: Stamp<StampPart<RoomPart>>,
     pub features: Vec<Option<RoomFeature>>,
+    pub prefab: Option<String>,
+    pub prefab_spawns: Vec<(usize, PrefabSpawn)>,
 }
 
 impl Room {
-    pub fn new(rect: Rect) -> Self {
-        let mut rng = RandomNumberGenerator::new();
-        let room_type = match rect.area() {
-            0..=8 => None,
-            9..=75 => {
-                let choices = vec![
-                    Some(RoomType::SittingRoom),
-                    Some(RoomType::TreasureRoom),
-                    Some(RoomType::Collapsed),
-                    Some(RoomType::StoreRoom),
-                    Some(RoomType::BedRoom),
-                    Some(RoomType::Kitchen),
-                    None,
-                ];
-                get_random_element(&mut rng, &choices).to_owned()
-            }
-            76..=100 => {
-                let choices = vec![
-                    Some(RoomType::TreasureRoom),
-                    Some(RoomType::Collapsed),
-                    Some(RoomType::StoreRoom),
-                    Some(RoomType::DiningRoom),
-                    None,
-                ];
-                get_random_element(&mut rng, &choices).to_owned()
-            }
-            101..=200 => {
-                let mut choices = vec![Some(RoomType::MessHall), Some(RoomType::Barracks), None];
-                if rect.height() >= 8 && rect.width() >= 8 {
-                    choices.push(Some(RoomType::ClassRoom));
-                    choices.push(Some(RoomType::MeetingRoom));
-                }
-                get_random_element(&mut rng, &choices).to_owned()
-            }
-            _ => {
-                let choices = vec![
-                    Some(RoomType::Courtyard),
-                    Some(RoomType::Courtyard),
-                    Some(RoomType::Courtyard),
-                    Some(RoomType::Courtyard),
-                    Some(RoomType::Baths),
-                    Some(RoomType::Baths),
-                    Some(RoomType::ThroneRoom),
-                ];
-                get_random_element(&mut rng, &choices).to_owned()
-            }
-        };
+    // what the room is for is decided later, once the rest of the level is known.
+    pub fn new(rect: Rect, rng: &mut RandomNumberGenerator) -> Self {
+        Room::with_room_type(rect, None, rng)
+    }
+
+    pub fn with_room_type(
+        rect: Rect,
+        room_type: Option<RoomType>,
+        rng: &mut RandomNumberGenerator,
+    ) -> Self {
         let mut features = vec![];
         match &room_type {
             Some(RoomType::MessHall) => {
@@ -86,7 +52,9 @@ impl Room {
                     Some(RoomFeature::ColumnsDoubleBottom),
                     None,
                 ];
-                features.push(get_random_element(&mut rng, &column_choices).to_owned());
+                features.push(get_random_element(rng, &column_choices).to_owned());
+                let water_choices = vec![Some(RoomFeature::Trough), None, None];
+                features.push(get_random_element(rng, &water_choices).to_owned());
             }
             Some(RoomType::Barracks) => {
                 let column_choices = vec![
@@ -101,7 +69,9 @@ impl Room {
                     Some(RoomFeature::ColumnsSingleBottom),
                     None,
                 ];
-                features.push(get_random_element(&mut rng, &column_choices).to_owned());
+                features.push(get_random_element(rng, &column_choices).to_owned());
+                let water_choices = vec![Some(RoomFeature::Trough), None, None];
+                features.push(get_random_element(rng, &water_choices).to_owned());
             }
             Some(RoomType::BedRoom) | Some(RoomType::SittingRoom) | Some(RoomType::DiningRoom) => {
                 let column_choices = vec![
@@ -111,7 +81,16 @@ impl Room {
                     Some(RoomFeature::ColumnsSingleBottom),
                     None,
                 ];
-                features.push(get_random_element(&mut rng, &column_choices).to_owned());
+                features.push(get_random_element(rng, &column_choices).to_owned());
+                let water_choices = vec![
+                    Some(RoomFeature::BathSquare),
+                    Some(RoomFeature::BathCircular),
+                    Some(RoomFeature::FountainCircular),
+                    None,
+                    None,
+                    None,
+                ];
+                features.push(get_random_element(rng, &water_choices).to_owned());
             }
             Some(RoomType::ThroneRoom) => {
                 let column_choices = vec![
@@ -122,7 +101,13 @@ impl Room {
                     Some(RoomFeature::ColumnsTripleHorizontal),
                     Some(RoomFeature::ColumnsTripleAll),
                 ];
-                features.push(get_random_element(&mut rng, &column_choices).to_owned());
+                features.push(get_random_element(rng, &column_choices).to_owned());
+                let water_choices = vec![
+                    Some(RoomFeature::FountainCircular),
+                    Some(RoomFeature::FountainSquare),
+                    None,
+                ];
+                features.push(get_random_element(rng, &water_choices).to_owned());
             }
             Some(RoomType::Courtyard) | Some(RoomType::Baths) => {
                 let column_choices = vec![
@@ -133,13 +118,22 @@ impl Room {
                     Some(RoomFeature::ColumnsTripleHorizontal),
                     Some(RoomFeature::ColumnsTripleAll),
                 ];
-                features.push(get_random_element(&mut rng, &column_choices).to_owned());
+                features.push(get_random_element(rng, &column_choices).to_owned());
                 let middle_column_choices = vec![
                     Some(RoomFeature::ColumnsDoubleMiddle),
                     Some(RoomFeature::ColumnsSingleMiddle),
                     None,
                 ];
-                features.push(get_random_element(&mut rng, &middle_column_choices).to_owned());
+                features.push(get_random_element(rng, &middle_column_choices).to_owned());
+            }
+            Some(RoomType::Kitchen) => {
+                let water_choices = vec![Some(RoomFeature::Trough), None];
+                features.push(get_random_element(rng, &water_choices).to_owned());
+            }
+            // rooms left to rot, or never built at all, are the first to flood.
+            Some(RoomType::StoreRoom) | Some(RoomType::Collapsed) | None => {
+                let flood_choices = vec![Some(RoomFeature::Flood), None, None, None];
+                features.push(get_random_element(rng, &flood_choices).to_owned());
             }
             _ => {}
         };
@@ -156,6 +150,8 @@ impl Room {
             room_type,
             stamp,
             features,
+            prefab: None,
+            prefab_spawns: vec![],
         }
     }
 }
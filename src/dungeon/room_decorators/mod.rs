@@ -18,7 +18,9 @@ mod place_sconces;
 pub mod room_part;
 pub mod room_type;
 mod utils;
+mod water_features;
 
 pub use decorate_room::decorate_room;
 pub use room_part::RoomPart;
 pub use room_type::RoomType;
+pub use water_features::add_features_to_room;
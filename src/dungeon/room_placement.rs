@@ -0,0 +1,246 @@
+use super::{
+    floor_theme::{self, FloorTheme},
+    level::Level,
+    level_utils,
+    rect::Rect,
+    room::Room,
+    room_decorators::RoomType,
+    tile_type::TileType,
+};
+use rltk::{DistanceAlg::Pythagoras, Point, RandomNumberGenerator};
+use std::collections::VecDeque;
+
+// rooms closer than this many tiles are treated as next door to each other.
+const NEIGHBOR_GAP: i32 = 8;
+// one in this many floors that aren't a palace still have a throne room, or a treasure room.
+const THRONE_ROOM_CHANCE: i32 = 4;
+const TREASURE_ROOM_CHANCE: i32 = 2;
+const KITCHEN_NEIGHBOR_WEIGHT: i32 = 3;
+const BEDROOM_NEIGHBOR_WEIGHT: i32 = 4;
+
+fn get_choices_for_rect(rect: &Rect) -> Vec<Option<RoomType>> {
+    match rect.area() {
+        0..=8 => vec![None],
+        9..=75 => vec![
+            Some(RoomType::SittingRoom),
+            Some(RoomType::Collapsed),
+            Some(RoomType::StoreRoom),
+            Some(RoomType::BedRoom),
+            Some(RoomType::Kitchen),
+            None,
+        ],
+        76..=100 => vec![
+            Some(RoomType::Collapsed),
+            Some(RoomType::StoreRoom),
+            Some(RoomType::DiningRoom),
+            None,
+        ],
+        101..=200 => {
+            let mut choices = vec![Some(RoomType::MessHall), Some(RoomType::Barracks), None];
+            if rect.height() >= 8 && rect.width() >= 8 {
+                choices.push(Some(RoomType::ClassRoom));
+                choices.push(Some(RoomType::MeetingRoom));
+            }
+            choices
+        }
+        _ => vec![
+            Some(RoomType::Courtyard),
+            Some(RoomType::Courtyard),
+            Some(RoomType::Baths),
+        ],
+    }
+}
+
+fn rects_are_neighbors(a: &Rect, b: &Rect) -> bool {
+    let gap_x = i32::max(a.x1 - b.x2, b.x1 - a.x2);
+    let gap_y = i32::max(a.y1 - b.y2, b.y1 - a.y2);
+    gap_x <= NEIGHBOR_GAP && gap_y <= NEIGHBOR_GAP
+}
+
+fn get_neighbors(level: &Level, room_index: usize) -> Vec<usize> {
+    let rect = level.rooms[room_index].rect;
+    (0..level.rooms.len())
+        .filter(|i| *i != room_index && rects_are_neighbors(&rect, &level.rooms[*i].rect))
+        .collect()
+}
+
+// the fewest doors that have to be passed through to get to each room from the first one, or
+// none if the room can't be walked to yet.
+fn get_doors_to_rooms(level: &Level) -> Vec<Option<u32>> {
+    let mut doors_to_idx: Vec<Option<u32>> = vec![None; level.tiles.len()];
+    if let Some(room) = level.rooms.first() {
+        if let Some(start_idx) = level_utils::get_walkable_tiles_in_rect(&room.rect, level).first()
+        {
+            doors_to_idx[*start_idx] = Some(0);
+            let mut to_visit = VecDeque::new();
+            to_visit.push_back(*start_idx);
+            while let Some(idx) = to_visit.pop_front() {
+                let doors = doors_to_idx[idx].unwrap();
+                for neighbor in level_utils::get_neighbor_idxs(level, idx) {
+                    if !level_utils::is_exit_valid(level, neighbor) {
+                        continue;
+                    }
+                    let neighbor_doors = match level.tiles[neighbor] {
+                        TileType::Door => doors + 1,
+                        _ => doors,
+                    };
+                    match doors_to_idx[neighbor] {
+                        Some(known) if known <= neighbor_doors => continue,
+                        _ => doors_to_idx[neighbor] = Some(neighbor_doors),
+                    }
+                    // passing through a door costs one, so those tiles wait behind the rest.
+                    match neighbor_doors == doors {
+                        true => to_visit.push_front(neighbor),
+                        false => to_visit.push_back(neighbor),
+                    }
+                }
+            }
+        }
+    }
+    level
+        .rooms
+        .iter()
+        .map(|room| {
+            level_utils::get_walkable_tiles_in_rect(&room.rect, level)
+                .iter()
+                .filter_map(|idx| doors_to_idx[*idx])
+                .min()
+        })
+        .collect()
+}
+
+fn get_distance_from_entrance(level: &Level, room_index: usize) -> f32 {
+    let from = Point::from(level.rooms[0].rect.center());
+    let to = Point::from(level.rooms[room_index].rect.center());
+    Pythagoras.distance2d(from, to)
+}
+
+// the throne room is the grandest hall furthest from the way in.
+fn place_throne_room(
+    level: &Level,
+    theme: FloorTheme,
+    rng: &mut RandomNumberGenerator,
+    room_types: &mut [Option<Option<RoomType>>],
+) {
+    if theme != FloorTheme::Palace && rng.range(0, THRONE_ROOM_CHANCE) != 0 {
+        return;
+    }
+    let throne_room = (1..level.rooms.len())
+        .filter(|i| level.rooms[*i].rect.area() > 200)
+        .max_by(|a, b| {
+            get_distance_from_entrance(level, *a)
+                .partial_cmp(&get_distance_from_entrance(level, *b))
+                .unwrap()
+        });
+    if let Some(room_index) = throne_room {
+        room_types[room_index] = Some(Some(RoomType::ThroneRoom));
+    }
+}
+
+// the treasure is kept behind as many doors as the level has to put in the way.
+fn place_treasure_room(
+    level: &Level,
+    theme: FloorTheme,
+    rng: &mut RandomNumberGenerator,
+    room_types: &mut [Option<Option<RoomType>>],
+) {
+    if theme != FloorTheme::Palace && rng.range(0, TREASURE_ROOM_CHANCE) != 0 {
+        return;
+    }
+    let doors_to_rooms = get_doors_to_rooms(level);
+    let candidates: Vec<(usize, u32)> = (1..level.rooms.len())
+        .filter(|i| room_types[*i].is_none())
+        .filter(|i| (9..=100).contains(&level.rooms[*i].rect.area()))
+        .filter_map(|i| doors_to_rooms[i].map(|doors| (i, doors)))
+        .collect();
+    let most_doors = match candidates.iter().map(|(_i, doors)| *doors).max() {
+        Some(doors) => doors,
+        None => return,
+    };
+    let most_guarded: Vec<usize> = candidates
+        .iter()
+        .filter(|(_i, doors)| *doors == most_doors)
+        .map(|(i, _doors)| *i)
+        .collect();
+    if let Some(room_index) = rng.random_slice_entry(&most_guarded) {
+        room_types[*room_index] = Some(Some(RoomType::TreasureRoom));
+    }
+}
+
+fn get_weighted_choice(
+    rng: &mut RandomNumberGenerator,
+    choices: &[(Option<RoomType>, i32)],
+) -> Option<RoomType> {
+    let total: i32 = choices.iter().map(|(_choice, weight)| weight).sum();
+    let mut roll = rng.range(0, total);
+    for (choice, weight) in choices.iter() {
+        if roll < *weight {
+            return *choice;
+        }
+        roll -= weight;
+    }
+    None
+}
+
+// the rest of the rooms are picked biggest first, so the halls a kitchen needs to be next to
+// are there before any kitchens are, and bedrooms are drawn towards other bedrooms.
+fn place_remaining_rooms(
+    level: &Level,
+    theme: FloorTheme,
+    rng: &mut RandomNumberGenerator,
+    room_types: &mut [Option<Option<RoomType>>],
+) {
+    let mut room_indexes: Vec<usize> = (0..level.rooms.len()).collect();
+    room_indexes.sort_by_key(|i| -level.rooms[*i].rect.area());
+    for room_index in room_indexes {
+        if room_types[room_index].is_some() {
+            continue;
+        }
+        let neighbor_types: Vec<RoomType> = get_neighbors(level, room_index)
+            .iter()
+            .filter_map(|i| room_types[*i].flatten())
+            .collect();
+        let next_to_hall = neighbor_types
+            .iter()
+            .any(|t| *t == RoomType::DiningRoom || *t == RoomType::MessHall);
+        let neighboring_bedrooms = neighbor_types
+            .iter()
+            .filter(|t| **t == RoomType::BedRoom)
+            .count() as i32;
+        let choices: Vec<(Option<RoomType>, i32)> =
+            get_choices_for_rect(&level.rooms[room_index].rect)
+                .into_iter()
+                .filter(|choice| *choice != Some(RoomType::Kitchen) || next_to_hall)
+                .map(|choice| {
+                    let weight = floor_theme::get_weight_for_room_type(theme, choice);
+                    let weight = match choice {
+                        Some(RoomType::Kitchen) => weight * KITCHEN_NEIGHBOR_WEIGHT,
+                        Some(RoomType::BedRoom) => {
+                            weight + neighboring_bedrooms * BEDROOM_NEIGHBOR_WEIGHT
+                        }
+                        _ => weight,
+                    };
+                    (choice, weight)
+                })
+                .collect();
+        room_types[room_index] = Some(get_weighted_choice(rng, &choices));
+    }
+}
+
+// picks a theme for the level and decides what every room is for as a whole, rather than
+// each room on its own. the doors have to be in so the treasure can be hidden behind them.
+pub fn assign_room_types(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let theme = floor_theme::get_floor_theme(rng);
+    // a room is left as none until it's been decided, even if what's decided is nothing at all.
+    let mut room_types: Vec<Option<Option<RoomType>>> = vec![None; level.rooms.len()];
+    place_throne_room(level, theme, rng, &mut room_types);
+    place_treasure_room(level, theme, rng, &mut room_types);
+    place_remaining_rooms(level, theme, rng, &mut room_types);
+    level.rooms = level
+        .rooms
+        .iter()
+        .zip(room_types.iter())
+        .map(|(room, room_type)| Room::with_room_type(room.rect, room_type.flatten(), rng))
+        .collect();
+    level.theme = Some(theme);
+}
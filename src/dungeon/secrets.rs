@@ -0,0 +1,163 @@
+use super::{level::Level, level_utils, locks, room_decorators::RoomPart, tile_type::TileType};
+use rltk::{BaseMap, RandomNumberGenerator, SmallVec};
+use stamp_rs::StampPart::Use;
+use std::collections::HashSet;
+
+// one in this many levels hides a room full of treasure away behind secret doors.
+const SECRET_ROOM_CHANCE: i32 = 3;
+const MAX_SECRET_PASSAGES: i32 = 3;
+
+pub fn idx_is_secret(level: &Level, idx: usize) -> bool {
+    level.secret_doors.contains(&idx)
+}
+
+pub fn reveal_idx(level: &mut Level, idx: usize) {
+    level.secret_doors.retain(|i| *i != idx);
+}
+
+// the level as it looks to someone who doesn't know where the secret doors are, paths searched
+// across it go the long way round instead of through the walls.
+pub struct WithoutSecretDoors<'a> {
+    pub level: &'a Level,
+}
+
+impl<'a> BaseMap for WithoutSecretDoors<'a> {
+    fn is_opaque(&self, idx: usize) -> bool {
+        self.level.is_opaque(idx)
+    }
+
+    fn get_available_exits(&self, idx: usize) -> SmallVec<[(usize, f32); 10]> {
+        self.level
+            .get_available_exits(idx)
+            .into_iter()
+            .filter(|(exit_idx, _cost)| !idx_is_secret(self.level, *exit_idx))
+            .collect()
+    }
+
+    fn get_pathing_distance(&self, idx1: usize, idx2: usize) -> f32 {
+        self.level.get_pathing_distance(idx1, idx2)
+    }
+}
+
+fn get_room_index_for_interior_idx(level: &Level, idx: usize) -> Option<usize> {
+    let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+    level.rooms.iter().position(|room| {
+        x > room.rect.x1 && x < room.rect.x2 - 1 && y > room.rect.y1 && y < room.rect.y2 - 1
+    })
+}
+
+// rooms that share a wall both have it in their stamp, so every one of them has to agree on
+// what's there or the door won't be spawned.
+fn set_stamps_at_idx(level: &mut Level, idx: usize, part: RoomPart) {
+    let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+    for room in level.rooms.iter_mut() {
+        let rect = room.rect;
+        if x < rect.x1 || x >= rect.x2 || y < rect.y1 || y >= rect.y2 {
+            continue;
+        }
+        room.stamp.pattern[(y - rect.y1) as usize][(x - rect.x1) as usize] = Use(part);
+    }
+}
+
+fn tiles_are_walkable(level: &Level, stamped: &HashSet<usize>, idxs: &[usize]) -> bool {
+    idxs.iter().all(|idx| {
+        level.tiles[*idx] == TileType::Floor && !level.blocked[*idx] && !stamped.contains(idx)
+    })
+}
+
+// a single wall between two different rooms, or a room and a corridor, that a door could be
+// cut through without anything in the way on either side.
+fn get_passage_idxs(level: &Level) -> Vec<usize> {
+    let width = level.width as i32;
+    let height = level.height as i32;
+    let stamped = locks::get_stamped_idxs(level);
+    let mut passages = vec![];
+    for y in 1..height - 1 {
+        for x in 1..width - 1 {
+            let idx = level_utils::xy_idx(width as u32, x, y);
+            if level.tiles[idx] != TileType::Wall || level_utils::tile_is_door_adjacent(level, x, y)
+            {
+                continue;
+            }
+            let sides = if level_utils::tile_is_between_walls_vertical(level, x, y) {
+                [
+                    level_utils::xy_idx(width as u32, x - 1, y),
+                    level_utils::xy_idx(width as u32, x + 1, y),
+                ]
+            } else if level_utils::tile_is_between_walls_horizontal(level, x, y) {
+                [
+                    level_utils::xy_idx(width as u32, x, y - 1),
+                    level_utils::xy_idx(width as u32, x, y + 1),
+                ]
+            } else {
+                continue;
+            };
+            if !tiles_are_walkable(level, &stamped, &sides) {
+                continue;
+            }
+            let first_room = get_room_index_for_interior_idx(level, sides[0]);
+            let second_room = get_room_index_for_interior_idx(level, sides[1]);
+            if first_room != second_room {
+                passages.push(idx);
+            }
+        }
+    }
+    passages
+}
+
+// short cuts through the walls between rooms, they look like any other wall until someone
+// searches next to them.
+pub fn add_secret_passages(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let mut candidates = get_passage_idxs(level);
+    let passage_count = rng.range(0, MAX_SECRET_PASSAGES + 1);
+    for _ in 0..passage_count {
+        if candidates.len() == 0 {
+            break;
+        }
+        let idx = candidates.remove(rng.range(0, candidates.len()));
+        let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+        // an earlier passage might have been cut right next to this one.
+        if level_utils::tile_is_door_adjacent(level, x, y) {
+            continue;
+        }
+        level_utils::set_tile_to_door(level, idx);
+        level.blocked[idx] = true;
+        level.opaque[idx] = true;
+        level.tile_hp[idx] = 0;
+        set_stamps_at_idx(level, idx, RoomPart::Door);
+        level.secret_doors.push(idx);
+    }
+}
+
+// every door of the room is hidden, so the only way to find what's inside is to search for it.
+// the room is only kept secret if it really is cut off, and none of the stairs are cut off with
+// it.
+pub fn add_secret_room(level: &mut Level, rng: &mut RandomNumberGenerator) {
+    let start_idx = match level_utils::get_entrance_idx(level) {
+        Some(idx) => idx,
+        None => return,
+    };
+    if level.rooms.len() < 2 || rng.range(0, SECRET_ROOM_CHANCE) != 0 {
+        return;
+    }
+    let room_index = rng.range(1, level.rooms.len());
+    let rect = level.rooms[room_index].rect;
+    if level_utils::rect_has_stairs(level, &rect) {
+        return;
+    }
+    let doors = locks::get_door_idxs_for_room(level, room_index);
+    if doors.len() == 0 {
+        return;
+    }
+    level.secret_doors.extend(doors.iter());
+    let reachable = locks::get_reachable_without_keys(level, start_idx);
+    let stairs_reachable = level_utils::all_stairs_reachable(level, &reachable);
+    let room_reachable = reachable
+        .iter()
+        .any(|idx| get_room_index_for_interior_idx(level, *idx) == Some(room_index));
+    if stairs_reachable && !room_reachable {
+        level.secret_rooms.push(room_index);
+    } else {
+        level.secret_doors.retain(|idx| !doors.contains(idx));
+    }
+}
@@ -0,0 +1,42 @@
+use rltk::RGB;
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum SurfaceType {
+    Water,
+    Oil,
+    Blood,
+    Ice,
+}
+
+pub fn get_name_for_surface(surface_type: &SurfaceType) -> String {
+    match surface_type {
+        SurfaceType::Water => "Puddle of Water".to_string(),
+        SurfaceType::Oil => "Oil Slick".to_string(),
+        SurfaceType::Blood => "Pool of Blood".to_string(),
+        SurfaceType::Ice => "Sheet of Ice".to_string(),
+    }
+}
+
+pub fn get_background_color_for_surface(surface_type: &SurfaceType) -> RGB {
+    match surface_type {
+        SurfaceType::Water => RGB::from_f32(0.1, 0.2, 0.5),
+        SurfaceType::Oil => RGB::from_f32(0.2, 0.15, 0.05),
+        SurfaceType::Blood => RGB::from_f32(0.5, 0.0, 0.0),
+        SurfaceType::Ice => RGB::from_f32(0.6, 0.8, 0.9),
+    }
+}
+
+pub fn surface_is_flammable(surface_type: &SurfaceType) -> bool {
+    match surface_type {
+        SurfaceType::Oil => true,
+        _ => false,
+    }
+}
+
+pub fn surface_extinguishes_fire(surface_type: &SurfaceType) -> bool {
+    match surface_type {
+        SurfaceType::Water | SurfaceType::Ice => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,87 @@
+use crate::components::{
+    CombatStats, Encumbered, Equipment, Grabbing, Inventory, Stackable, Weight,
+};
+use crate::inventory::get_stack_quantity;
+use specs::{storage::GenericReadStorage, Entity, World, WorldExt};
+
+const BASE_CARRY_CAPACITY: f32 = 20.0;
+const CARRY_CAPACITY_PER_POWER: f32 = 5.0;
+// furniture is dragged along the floor rather than carried, so only part of its weight counts.
+const DRAGGED_WEIGHT_MULTIPLIER: f32 = 0.5;
+
+fn get_item_weight(
+    item: Entity,
+    weights: &impl GenericReadStorage<Component = Weight>,
+    stackables: &impl GenericReadStorage<Component = Stackable>,
+) -> f32 {
+    match weights.get(item) {
+        Some(w) => w.weight * get_stack_quantity(item, stackables) as f32,
+        None => 0.0,
+    }
+}
+
+pub fn get_carry_capacity(stats: &CombatStats) -> f32 {
+    BASE_CARRY_CAPACITY + stats.power as f32 * CARRY_CAPACITY_PER_POWER
+}
+
+pub fn get_carried_weight(
+    entity: Entity,
+    inventories: &impl GenericReadStorage<Component = Inventory>,
+    equipment: &impl GenericReadStorage<Component = Equipment>,
+    grabbings: &impl GenericReadStorage<Component = Grabbing>,
+    weights: &impl GenericReadStorage<Component = Weight>,
+    stackables: &impl GenericReadStorage<Component = Stackable>,
+) -> f32 {
+    let mut carried = 0.0;
+    if let Some(inventory) = inventories.get(entity) {
+        carried += inventory
+            .items
+            .iter()
+            .map(|item| get_item_weight(*item, weights, stackables))
+            .sum::<f32>();
+    }
+    if let Some(e) = equipment.get(entity) {
+        if let Some(item) = e.dominant_hand {
+            carried += get_item_weight(item, weights, stackables);
+        }
+        // two handed items fill both hands, but are only carried once
+        if let Some(item) = e.off_hand.filter(|item| e.dominant_hand != Some(*item)) {
+            carried += get_item_weight(item, weights, stackables);
+        }
+    }
+    if let Some(grabbing) = grabbings.get(entity) {
+        if let Some(w) = weights.get(grabbing.thing) {
+            carried += w.weight * DRAGGED_WEIGHT_MULTIPLIER;
+        }
+    }
+    carried
+}
+
+pub fn get_player_weight_text(world: &World) -> String {
+    let player_ent = world.fetch::<Entity>();
+    let combat_stats = world.read_storage::<CombatStats>();
+    let carried = get_carried_weight(
+        *player_ent,
+        &world.read_storage::<Inventory>(),
+        &world.read_storage::<Equipment>(),
+        &world.read_storage::<Grabbing>(),
+        &world.read_storage::<Weight>(),
+        &world.read_storage::<Stackable>(),
+    );
+    let capacity = get_carry_capacity(combat_stats.get(*player_ent).unwrap());
+    format!("Carrying {:.1}/{:.1}", carried, capacity)
+}
+
+// an over-burdened player pays for each step with an extra turn, returns true while a turn is
+// still owed.
+pub fn spend_owed_player_turn(world: &World) -> bool {
+    let player_ent = world.fetch::<Entity>();
+    let mut encumbered = world.write_storage::<Encumbered>();
+    match encumbered.get_mut(*player_ent) {
+        Some(e) if e.turns_owed > 0 => {
+            e.turns_owed -= 1;
+            true
+        }
+        _ => false,
+    }
+}
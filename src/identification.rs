@@ -0,0 +1,68 @@
+use crate::utils::get_random_element;
+use rltk::RandomNumberGenerator;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+// only scrolls are disguised for now, with a single kind of potion there's nothing to mistake it
+// for.
+const SCROLL_NAMES: [&str; 4] = [
+    "Scroll of Magic Missile",
+    "Scroll of Fireball",
+    "Scroll of Confusion",
+    "Scroll of Identify",
+];
+const SCROLL_SYLLABLES: [&str; 16] = [
+    "XY", "ZZY", "FOO", "KLA", "BAR", "NAK", "ELB", "ZOT", "MOR", "ITH", "GNU", "VEH", "DAR",
+    "OTH", "LUM", "PRA",
+];
+
+// unidentified items are only known by how they look, which is shuffled for every new game.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Identification {
+    appearances: HashMap<String, String>,
+    identified: HashSet<String>,
+}
+
+fn get_scroll_label(rng: &mut RandomNumberGenerator) -> String {
+    let syllables: Vec<&str> = SCROLL_SYLLABLES.to_vec();
+    let syllable_count = rng.range(2, 4);
+    (0..syllable_count)
+        .map(|_| *get_random_element(rng, &syllables))
+        .collect()
+}
+
+impl Identification {
+    pub fn new(rng: &mut RandomNumberGenerator) -> Self {
+        let mut appearances = HashMap::new();
+        for name in SCROLL_NAMES.iter() {
+            let mut appearance = format!("scroll labelled {}", get_scroll_label(rng));
+            while appearances.values().any(|a| *a == appearance) {
+                appearance = format!("scroll labelled {}", get_scroll_label(rng));
+            }
+            appearances.insert(name.to_string(), appearance);
+        }
+        Identification {
+            appearances,
+            identified: HashSet::new(),
+        }
+    }
+
+    pub fn is_identified(&self, name: &str) -> bool {
+        !self.appearances.contains_key(name) || self.identified.contains(name)
+    }
+
+    pub fn get_display_name(&self, name: &str) -> String {
+        match self.appearances.get(name) {
+            Some(appearance) if !self.identified.contains(name) => appearance.clone(),
+            _ => name.to_string(),
+        }
+    }
+
+    // returns true when the item wasn't already known.
+    pub fn identify(&mut self, name: &str) -> bool {
+        if self.is_identified(name) {
+            return false;
+        }
+        self.identified.insert(name.to_string())
+    }
+}
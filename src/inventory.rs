@@ -1,27 +1,91 @@
-use crate::components::{Container, Inventory, Name};
-use specs::{Entity, World, WorldExt};
+use crate::components::{Container, Inventory, Name, Stackable};
+use crate::identification::Identification;
+use specs::{
+    storage::GenericReadStorage, Entities, Entity, ReadStorage, World, WorldExt, WriteStorage,
+};
 
 pub type InventoryList = Vec<(Entity, String)>;
 
+fn get_item_name(
+    item: &Entity,
+    names: &ReadStorage<Name>,
+    stackables: &ReadStorage<Stackable>,
+    identification: &Identification,
+) -> String {
+    let name = identification.get_display_name(&names.get(*item).unwrap().name);
+    match stackables.get(*item) {
+        Some(stack) if stack.quantity > 1 => format!("{} x{}", name, stack.quantity),
+        _ => name,
+    }
+}
+
 pub fn get_player_inventory_list(ecs: &mut World) -> InventoryList {
     let player_entity = ecs.fetch::<Entity>();
     let inventories = ecs.read_storage::<Inventory>();
     let player_inventory = inventories.get(*player_entity).unwrap();
     let names = ecs.read_storage::<Name>();
+    let stackables = ecs.read_storage::<Stackable>();
+    let identification = ecs.fetch::<Identification>();
     player_inventory
         .items
         .iter()
-        .map(|e| (*e, names.get(*e).unwrap().name.clone()))
+        .map(|e| (*e, get_item_name(e, &names, &stackables, &identification)))
         .collect()
 }
 
 pub fn get_container_inventory_list(ecs: &mut World, container_entity: &Entity) -> InventoryList {
     let names = ecs.read_storage::<Name>();
+    let stackables = ecs.read_storage::<Stackable>();
+    let identification = ecs.fetch::<Identification>();
     let containers = ecs.read_storage::<Container>();
     let container = containers.get(*container_entity).unwrap();
     container
         .items
         .iter()
-        .map(|e| (*e, names.get(*e).unwrap().name.clone()))
+        .map(|e| (*e, get_item_name(e, &names, &stackables, &identification)))
         .collect()
 }
+
+pub fn get_stack_quantity(
+    item: Entity,
+    stackables: &impl GenericReadStorage<Component = Stackable>,
+) -> u32 {
+    match stackables.get(item) {
+        Some(stack) => stack.quantity,
+        None => 1,
+    }
+}
+
+// stackable items with the same name are the same kind of item, and belong in the same stack.
+pub fn get_matching_stack<'a>(
+    item: Entity,
+    candidates: impl Iterator<Item = &'a Entity>,
+    stackables: &impl GenericReadStorage<Component = Stackable>,
+    names: &impl GenericReadStorage<Component = Name>,
+) -> Option<Entity> {
+    stackables.get(item)?;
+    let item_name = &names.get(item)?.name;
+    candidates
+        .filter(|e| **e != item && stackables.get(**e).is_some())
+        .find(|e| match names.get(**e) {
+            Some(name) => &name.name == item_name,
+            None => false,
+        })
+        .cloned()
+}
+
+// uses up a single item from a stack, removing the item entirely when it's the last one.
+pub fn consume_one(
+    item: Entity,
+    inventory: &mut Inventory,
+    stackables: &mut WriteStorage<Stackable>,
+    entities: &Entities,
+) {
+    match stackables.get_mut(item) {
+        Some(stack) if stack.quantity > 1 => stack.quantity -= 1,
+        _ => {
+            inventory.items.remove(&item);
+            entities.delete(item).expect("Delete Failed");
+        }
+    }
+}
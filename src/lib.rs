@@ -2,7 +2,7 @@ use menu::{Menu, MenuOption, MenuOptionState};
 use rltk::{a_star_search, GameState, RandomNumberGenerator, Rltk, RltkBuilder};
 use specs::prelude::*;
 use specs::saveload::{SimpleMarker, SimpleMarkerAllocator};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::iter;
 use std::iter::FromIterator;
 use wasm_bindgen::prelude::*;
@@ -14,13 +14,18 @@ mod artwork;
 mod components;
 mod control;
 mod copy;
+mod crafting;
 #[cfg(debug_assertions)]
 mod debug;
 mod dungeon;
+mod encumbrance;
 mod entity_option;
 mod entity_set;
+mod identification;
 mod inventory;
 mod menu;
+mod noise;
+mod perception;
 mod persistence;
 mod player;
 mod ranged;
@@ -35,21 +40,25 @@ mod ui_components;
 mod user_actions;
 mod utils;
 use components::{
-    door::DoorState, equipable::EquipmentPositions, AreaOfEffect, Armable, BlocksTile, Blood,
-    CausesDamage, CausesFire, CausesLight, CombatStats, Confused, Confusion, Consumable, Container,
-    DamageHistory, Disarmable, Door, Dousable, EntityMoved, EntryTrigger, Equipable, Equipment,
-    Flammable, Furniture, Grabbable, Grabbing, Hidden, Hiding, HidingSpot, Info, Inventory, Item,
-    Lightable, Memory, Monster, Name, Objective, OnFire, ParticleLifetime, Player, Position,
-    Potion, ProvidesHealing, Ranged, Renderable, Saveable, SerializationHelper, SingleActivation,
-    SufferDamage, Trap, Triggered, Viewshed, WantsToCloseDoor, WantsToDisarmTrap, WantsToDouse,
-    WantsToDropItem, WantsToEquip, WantsToGrab, WantsToHide, WantsToLight, WantsToMelee,
-    WantsToMove, WantsToOpenDoor, WantsToPickUpItem, WantsToReleaseGrabbed, WantsToSearchHidden,
-    WantsToTrap, WantsToUse,
+    door::DoorState, equipable::EquipmentPositions, Activated, Ammunition, AreaOfEffect, Armable,
+    BlocksTile, CausesDamage, CausesFire, CausesLight, CombatStats, Confused, Confusion,
+    Consumable, Container, DamageHistory, Disarmable, Door, Dousable, Dripping, Encumbered,
+    EntityMoved, EntryTrigger, Equipable, Equipment, Explosive, Flammable, Fuel, Furniture,
+    Grabbable, Grabbing, Hidden, Hiding, HidingSpot, Identifies, Info, Inventory, Item, Key,
+    Launcher, Lightable, Mechanism, Memory, Monster, Name, Objective, OnFire, ParticleLifetime,
+    Player, Position, Potion, ProvidesBlock, ProvidesHealing, Ranged, Refillable, Renderable,
+    Saveable, SerializationHelper, SingleActivation, Stackable, SufferDamage, Trap, Triggered,
+    TwoHanded, Viewshed, WantsToCloseDoor, WantsToDisarmTrap, WantsToDouse, WantsToDropItem,
+    WantsToEquip, WantsToFire, WantsToGrab, WantsToHide, WantsToLight, WantsToMelee, WantsToMove,
+    WantsToOpenDoor, WantsToPickLock, WantsToPickUpItem, WantsToRefuel, WantsToReleaseGrabbed,
+    WantsToSearchHidden, WantsToSmash, WantsToStudy, WantsToThrow, WantsToTrap, WantsToUse, Weight,
+    WiredTo, Workstation,
 };
 use settings::Settings;
-use types::EquipMenuType;
+use types::{workstation_type, EquipMenuType};
 
-use dungeon::{dungeon::Dungeon, level_builders, tile_type::TileType};
+use dungeon::{dungeon::Dungeon, level_builders, prefabs::Prefabs, tile_type::TileType};
+use identification::Identification;
 use player::{player_action, InteractionType};
 use run_state::RunState;
 use screens::{
@@ -59,18 +68,21 @@ use screens::{
     ScreenSuccess, SCREEN_HEIGHT, SCREEN_WIDTH,
 };
 use services::{
-    BloodSpawner, CorpseSpawner, DebrisSpawner, GameLog, ItemSpawner, ParticleEffectSpawner,
-    TrapSpawner,
+    CorpseSpawner, DebrisSpawner, ExplosionSpawner, GameLog, ItemSpawner, ParticleEffectSpawner,
+    StackSpawner, SurfaceSpawner, TerrainDamage, TrapSpawner,
 };
 use systems::{
-    BloodSpawnSystem, CloseDoorSystem, CorpseSpawnSystem, DamageSystem, DebrisSpawnSystem,
-    DisarmTrapSystem, DouseItemSystem, EquipSystem, FireBurnSystem, FireDieSystem,
-    FireSpreadSystem, GrabSystem, HideSystem, ItemCollectionSystem, ItemDropSystem,
-    ItemSpawnSystem, LightItemSystem, LightSystem, MapIndexingSystem, MeleeCombatSystem,
-    MemoryCullSystem, MonsterAI, MoveSystem, OpenDoorSystem, ParticleSpawnSystem, ReleaseSystem,
-    RemoveParticleEffectsSystem, RemoveTriggeredTrapsSystem, RevealTrapsSystem,
-    SearchForHiddenSystem, SetTrapSystem, TrapSpawnSystem, TriggerSystem, UpdateMemoriesSystem,
-    UpdateParticleEffectsSystem, UseItemSystem, VisibilitySystem,
+    CaveInSystem, CloseDoorSystem, CorpseSpawnSystem, DamageSystem, DebrisSpawnSystem,
+    DisarmTrapSystem, DouseItemSystem, EncumbranceSystem, EquipSystem, ExplosionSystem, FallSystem,
+    FireBurnSystem, FireDieSystem, FireSpreadSystem, GrabSystem, HideSystem, ItemCollectionSystem,
+    ItemDropSystem, ItemSpawnSystem, LightFuelSystem, LightItemSystem, LightSystem,
+    MapIndexingSystem, MechanismSystem, MeleeCombatSystem, MemoryCullSystem, MonsterAI, MoveSystem,
+    OpenDoorSystem, ParticleSpawnSystem, PickLockSystem, RangedCombatSystem, RefuelSystem,
+    ReleaseSystem, RemoveParticleEffectsSystem, RemoveTriggeredTrapsSystem, RevealTrapsSystem,
+    SearchForHiddenSystem, SetTrapSystem, SmashSystem, SmokeSystem, StackSpawnSystem, StudySystem,
+    SurfaceSpawnSystem, SurfaceSystem, TerrainDamageSystem, ThrowSystem, TrapSpawnSystem,
+    TriggerSystem, UpdateMemoriesSystem, UpdateParticleEffectsSystem, UseItemSystem,
+    VisibilitySystem,
 };
 use user_actions::{
     InteractionTargetingAction, MapAction, MenuAction, StaticAction, TargetingAction,
@@ -141,7 +153,10 @@ fn get_openable_doors(world: &World, entities: Box<[Entity]>) -> Box<[Entity]> {
     entities
         .iter()
         .filter(|e| match doors.get(**e) {
-            Some(d) => d.state == DoorState::Closed,
+            Some(d) => match d.state {
+                DoorState::Closed | DoorState::Locked(_) => true,
+                DoorState::Opened => false,
+            },
             None => false,
         })
         .map(|e| *e)
@@ -218,11 +233,18 @@ fn get_interaction_options_for_target(world: &World, target: Entity) -> Vec<Inte
     if world.read_storage::<Container>().get(target).is_some() {
         interactions.push(InteractionType::OpenContainer);
     }
+    if world.read_storage::<Workstation>().get(target).is_some() {
+        interactions.push(InteractionType::Craft);
+    }
     if let Some(door) = world.read_storage::<Door>().get(target) {
-        interactions.push(match door.state {
-            components::door::DoorState::Closed => InteractionType::OpenDoor,
-            components::door::DoorState::Opened => InteractionType::CloseDoor,
-        })
+        match door.state {
+            components::door::DoorState::Closed => interactions.push(InteractionType::OpenDoor),
+            components::door::DoorState::Opened => interactions.push(InteractionType::CloseDoor),
+            components::door::DoorState::Locked(_) => {
+                interactions.push(InteractionType::OpenDoor);
+                interactions.push(InteractionType::PickLock);
+            }
+        }
     }
     interactions
 }
@@ -247,6 +269,8 @@ fn get_menu_from_interaction_options(
                 InteractionType::OpenContainer => copy::MENU_OPTION_OPEN,
                 InteractionType::OpenDoor => copy::MENU_OPTION_OPEN_DOOR,
                 InteractionType::CloseDoor => copy::MENU_OPTION_CLOSE_DOOR,
+                InteractionType::PickLock => copy::MENU_OPTION_PICK_LOCK,
+                InteractionType::Craft => copy::MENU_OPTION_CRAFT,
             };
             let state = match idx == highlighted {
                 true => MenuOptionState::Highlighted,
@@ -259,15 +283,14 @@ fn get_menu_from_interaction_options(
 }
 
 fn generate_dungeon(world: &mut World, levels: u8) -> Dungeon {
-    let levels = (0..levels).fold(HashMap::new(), |mut acc, floor_number| {
-        let is_top_floor = floor_number == levels - 1;
-        let is_bottom_floor = floor_number == 0;
-        let mut level = level_builders::build(floor_number, is_top_floor, is_bottom_floor);
-        spawner::spawn_entities_for_level(world, &mut level);
-        acc.insert(floor_number, level);
-        return acc;
-    });
-    Dungeon { levels }
+    let mut dungeon = {
+        let prefabs = world.fetch::<Prefabs>();
+        level_builders::build_dungeon(levels, &prefabs)
+    };
+    for level in dungeon.levels.values_mut() {
+        spawner::spawn_entities_for_level(world, level);
+    }
+    dungeon
 }
 
 fn initialize_new_game(world: &mut World) {
@@ -290,7 +313,6 @@ fn initialize_new_game(world: &mut World) {
     world.write_storage::<Confused>().clear();
     world.write_storage::<SimpleMarker<Saveable>>().clear();
     world.write_storage::<SerializationHelper>().clear();
-    world.write_storage::<Blood>().clear();
     world.write_storage::<ParticleLifetime>().clear();
     world.write_storage::<Hidden>().clear();
     world.write_storage::<EntryTrigger>().clear();
@@ -320,8 +342,30 @@ fn initialize_new_game(world: &mut World) {
     world.write_storage::<Disarmable>().clear();
     world.write_storage::<DamageHistory>().clear();
     world.write_storage::<Inventory>().clear();
+    world.write_storage::<TwoHanded>().clear();
+    world.write_storage::<ProvidesBlock>().clear();
+    world.write_storage::<Ammunition>().clear();
+    world.write_storage::<Launcher>().clear();
+    world.write_storage::<Stackable>().clear();
+    world.write_storage::<Weight>().clear();
+    world.write_storage::<Encumbered>().clear();
+    world.write_storage::<Identifies>().clear();
+    world.write_storage::<Workstation>().clear();
+    world.write_storage::<Fuel>().clear();
+    world.write_storage::<Refillable>().clear();
+    world.write_storage::<Dripping>().clear();
+    world.write_storage::<Explosive>().clear();
+    world.write_storage::<WiredTo>().clear();
+    world.write_storage::<Mechanism>().clear();
+    world.write_storage::<Key>().clear();
     world.remove::<SimpleMarkerAllocator<Saveable>>();
     world.insert(SimpleMarkerAllocator::<Saveable>::new());
+    let identification = {
+        let mut rng = world.fetch_mut::<RandomNumberGenerator>();
+        Identification::new(&mut rng)
+    };
+    world.remove::<Identification>();
+    world.insert(identification);
     let dungeon = generate_dungeon(world, 10);
     let level = dungeon.get_level(9).unwrap();
     let (player_idx, _) = level
@@ -333,18 +377,33 @@ fn initialize_new_game(world: &mut World) {
     world.remove::<Entity>();
     let player_entity = spawner::spawn_player(world, player_idx, level);
     world.insert(player_entity);
-    let rng = world.get_mut::<RandomNumberGenerator>().unwrap();
-    let objective_floor = utils::get_random_between_numbers(rng, 1, 9) as u8;
+    let objective_floor = {
+        let rng = world.get_mut::<RandomNumberGenerator>().unwrap();
+        utils::get_random_between_numbers(rng, 1, 9) as u8
+    };
     let level = dungeon.get_level(objective_floor).unwrap();
-    let room_idx = utils::get_random_between_numbers(rng, 0, (level.rooms.len() - 1) as i32);
-    let room = level.rooms.get(room_idx as usize).unwrap();
+    // the talisman is never hidden away where only a lucky search would turn it up, or left
+    // behind a gate that could drop in front of it.
+    let mut rooms: Vec<usize> = (0..level.rooms.len())
+        .filter(|idx| !level.secret_rooms.contains(idx))
+        .filter(|idx| !spawner::rect_has_portcullis(world, &level.rooms[*idx].rect, level))
+        .collect();
+    // somewhere has to hold it, even if every room on the floor is out of the way.
+    if rooms.is_empty() {
+        rooms = (0..level.rooms.len()).collect();
+    }
+    let rng = world.get_mut::<RandomNumberGenerator>().unwrap();
+    let room_idx = utils::get_random_between_numbers(rng, 0, (rooms.len() - 1) as i32);
+    let room = level.rooms.get(rooms[room_idx as usize]).unwrap();
     spawner::spawn_objective_for_room(world, &room.rect, &level);
     world.remove::<Dungeon>();
     world.insert(dungeon);
+    let mut entries = vec!["Enter the dungeon apprentice! Bring back the Talisman!".to_owned()];
+    if let Some(error) = &world.fetch::<Prefabs>().load_error {
+        entries.insert(0, format!("The prefab rooms couldn't be loaded, {}", error));
+    }
     world.remove::<GameLog>();
-    world.insert(GameLog {
-        entries: vec!["Enter the dungeon apprentice! Bring back the Talisman!".to_owned()],
-    });
+    world.insert(GameLog { entries });
 }
 
 pub struct State {
@@ -368,6 +427,8 @@ impl State {
         {
             let mut equip_system = EquipSystem {};
             equip_system.run_now(&self.world);
+            let mut encumbrance_system = EncumbranceSystem {};
+            encumbrance_system.run_now(&self.world);
             let mut light = LightSystem {};
             light.run_now(&self.world);
             let mut vis = VisibilitySystem {
@@ -383,12 +444,24 @@ impl State {
         }
         let mut move_system = MoveSystem {};
         move_system.run_now(&self.world);
+        let mut fall_system = FallSystem {};
+        fall_system.run_now(&self.world);
         let mut mapindex = MapIndexingSystem {};
         mapindex.run_now(&self.world);
+        let mut surface_system = SurfaceSystem {};
+        surface_system.run_now(&self.world);
         let mut melee_combat = MeleeCombatSystem {};
         melee_combat.run_now(&self.world);
+        let mut ranged_combat = RangedCombatSystem {};
+        ranged_combat.run_now(&self.world);
+        let mut throw_system = ThrowSystem {};
+        throw_system.run_now(&self.world);
+        let mut smash_system = SmashSystem {};
+        smash_system.run_now(&self.world);
         let mut triggers = TriggerSystem {};
         triggers.run_now(&self.world);
+        let mut mechanisms = MechanismSystem {};
+        mechanisms.run_now(&self.world);
         if self.run_state == RunState::MonsterTurn {
             let mut fire_burn_system = FireBurnSystem {};
             fire_burn_system.run_now(&self.world);
@@ -396,7 +469,17 @@ impl State {
             fire_spread_system.run_now(&self.world);
             let mut fire_die_system = FireDieSystem {};
             fire_die_system.run_now(&self.world);
+            let mut smoke_system = SmokeSystem {};
+            smoke_system.run_now(&self.world);
+            let mut light_fuel_system = LightFuelSystem {};
+            light_fuel_system.run_now(&self.world);
         }
+        let mut explosion_system = ExplosionSystem {};
+        explosion_system.run_now(&self.world);
+        let mut cave_in_system = CaveInSystem {};
+        cave_in_system.run_now(&self.world);
+        let mut terrain_damage_system = TerrainDamageSystem {};
+        terrain_damage_system.run_now(&self.world);
         let mut damage = DamageSystem {
             queued_action: &mut self.queued_action,
         };
@@ -420,6 +503,8 @@ impl State {
         if self.run_state == RunState::PlayerTurn || self.run_state == RunState::MonsterTurn {
             let mut search_for_hidden_system = SearchForHiddenSystem {};
             search_for_hidden_system.run_now(&self.world);
+            let mut study_system = StudySystem {};
+            study_system.run_now(&self.world);
             let mut set_trap_system = SetTrapSystem {};
             set_trap_system.run_now(&self.world);
             let mut disarm_trap_system = DisarmTrapSystem {};
@@ -428,23 +513,29 @@ impl State {
             grab_system.run_now(&self.world);
             let mut open_door_system = OpenDoorSystem {};
             open_door_system.run_now(&self.world);
+            let mut pick_lock_system = PickLockSystem {};
+            pick_lock_system.run_now(&self.world);
             let mut close_door_system = CloseDoorSystem {};
             close_door_system.run_now(&self.world);
             let mut hide_system = HideSystem {};
             hide_system.run_now(&self.world);
             let mut light_item_system = LightItemSystem {};
             light_item_system.run_now(&self.world);
+            let mut refuel_system = RefuelSystem {};
+            refuel_system.run_now(&self.world);
             let mut douse_item_system = DouseItemSystem {};
             douse_item_system.run_now(&self.world);
         }
-        let mut blood_spawn_system = BloodSpawnSystem {};
-        blood_spawn_system.run_now(&self.world);
+        let mut surface_spawn_system = SurfaceSpawnSystem {};
+        surface_spawn_system.run_now(&self.world);
         let mut particle_spawn_system = ParticleSpawnSystem {};
         particle_spawn_system.run_now(&self.world);
         let mut trap_spawn_system = TrapSpawnSystem {};
         trap_spawn_system.run_now(&self.world);
         let mut item_spawn_system = ItemSpawnSystem {};
         item_spawn_system.run_now(&self.world);
+        let mut stack_spawn_system = StackSpawnSystem {};
+        stack_spawn_system.run_now(&self.world);
         let mut debris_spawn_system = DebrisSpawnSystem {};
         debris_spawn_system.run_now(&self.world);
         let mut corpse_spawn_system = CorpseSpawnSystem {};
@@ -489,6 +580,7 @@ impl GameState for State {
                         MapAction::Exit => RunState::SavingScreen { count_down: 15 },
                         MapAction::ShowInventoryMenu => RunState::InventoryMenu { highlighted: 0 },
                         MapAction::ShowDropMenu => RunState::DropItemMenu { highlighted: 0 },
+                        MapAction::Throw => RunState::ThrowItemMenu { highlighted: 0 },
                         MapAction::ShowEquipmentMenu => RunState::EquipmentMenu {
                             highlighted: 0,
                             action_highlighted: 0,
@@ -582,6 +674,16 @@ impl GameState for State {
                         MapAction::Interact => {
                             RunState::InteractiveEntityTargeting { target_idx: 0 }
                         }
+                        MapAction::Fire => match ranged::get_player_fire_range(&self.world) {
+                            Ok(range) => RunState::FireTargeting { range },
+                            Err(message) => {
+                                self.world.fetch_mut::<GameLog>().add(message);
+                                RunState::AwaitingInput {
+                                    offset_x: *offset_x,
+                                    offset_y: *offset_y,
+                                }
+                            }
+                        },
                         _ => {
                             player_action(&mut self.world, *action);
                             RunState::PlayerTurn
@@ -622,6 +724,12 @@ impl GameState for State {
                                                 container: ent,
                                             }
                                         }
+                                        InteractionType::Craft => {
+                                            next_state = RunState::CraftMenu {
+                                                highlighted: 0,
+                                                workstation: ent,
+                                            }
+                                        }
                                         _ => {
                                             player::interact(&mut self.world, ent, interaction);
                                             next_state = RunState::PlayerTurn;
@@ -650,9 +758,12 @@ impl GameState for State {
                         persistence::delete_save();
                         RunState::DeathScreen
                     }
-                    _ => RunState::AwaitingInput {
-                        offset_x: 0,
-                        offset_y: 0,
+                    _ => match encumbrance::spend_owed_player_turn(&self.world) {
+                        true => RunState::MonsterTurn,
+                        false => RunState::AwaitingInput {
+                            offset_x: 0,
+                            offset_y: 0,
+                        },
                     },
                 }
             }
@@ -679,7 +790,10 @@ impl GameState for State {
                         menu.page_number_at_index(*highlighted) + 1,
                         menu.page_count() + 1
                     ),
-                    "Escape to Cancel",
+                    &format!(
+                        "{}. Escape to Cancel",
+                        encumbrance::get_player_weight_text(&self.world)
+                    ),
                 )
                 .draw(ctx, &mut self.world);
                 match self
@@ -714,9 +828,16 @@ impl GameState for State {
                                         _ => None,
                                     }
                                 };
-                                match is_ranged {
-                                    Some(range) => RunState::ItemUseTargeting { range, item: *ent },
-                                    None => {
+                                let is_fuel = self.world.read_storage::<Fuel>().get(*ent).is_some();
+                                match (is_ranged, is_fuel) {
+                                    (Some(range), _) => {
+                                        RunState::ItemUseTargeting { range, item: *ent }
+                                    }
+                                    (None, true) => {
+                                        player::refuel(&mut self.world, *ent);
+                                        RunState::PlayerTurn
+                                    }
+                                    (None, false) => {
                                         player::use_item(&mut self.world, *ent, None);
                                         RunState::PlayerTurn
                                     }
@@ -758,7 +879,7 @@ impl GameState for State {
                         menu.page_number_at_index(*highlighted) + 1,
                         menu.page_count() + 1
                     ),
-                    "Escape to Cancel",
+                    "A to drop the whole stack. Escape to Cancel",
                 )
                 .draw(ctx, &mut self.world);
                 match self
@@ -784,26 +905,95 @@ impl GameState for State {
                         MenuAction::PreviousPage => RunState::DropItemMenu {
                             highlighted: menu.get_previous_page_index(*highlighted),
                         },
-                        MenuAction::Select => match inventory_entities.get(*highlighted) {
-                            Some(ent) => {
-                                let mut intent = self.world.write_storage::<WantsToDropItem>();
-                                intent
-                                    .insert(
-                                        *self.world.fetch::<Entity>(),
-                                        WantsToDropItem { item: *ent },
-                                    )
-                                    .expect("Unable To Insert Drop Item Intent");
-                                RunState::PlayerTurn
+                        MenuAction::Select | MenuAction::SelectAll => {
+                            match inventory_entities.get(*highlighted) {
+                                Some(ent) => {
+                                    let mut intent = self.world.write_storage::<WantsToDropItem>();
+                                    intent
+                                        .insert(
+                                            *self.world.fetch::<Entity>(),
+                                            WantsToDropItem {
+                                                item: *ent,
+                                                whole_stack: *action == MenuAction::SelectAll,
+                                            },
+                                        )
+                                        .expect("Unable To Insert Drop Item Intent");
+                                    RunState::PlayerTurn
+                                }
+                                None => RunState::DropItemMenu {
+                                    highlighted: *highlighted,
+                                },
                             }
-                            None => RunState::DropItemMenu {
+                        }
+                        _ => RunState::DropItemMenu {
+                            highlighted: *highlighted,
+                        },
+                    },
+                    None => RunState::DropItemMenu {
+                        highlighted: *highlighted,
+                    },
+                }
+            }
+            RunState::ThrowItemMenu { highlighted } => {
+                let inventory = inventory::get_player_inventory_list(&mut self.world);
+                let (inventory_entities, inventory_names): (Vec<_>, Vec<_>) =
+                    inventory.into_iter().unzip();
+                let menu_options: Box<[MenuOption<&String>]> = inventory_names
+                    .iter()
+                    .enumerate()
+                    .map(|(index, text)| {
+                        let state = match *highlighted == index {
+                            true => MenuOptionState::Highlighted,
+                            false => MenuOptionState::Normal,
+                        };
+                        MenuOption::new(text, state)
+                    })
+                    .collect();
+                let menu = Menu::new(menu_options, 10);
+                ScreenMapMenu::new(
+                    menu.get_page_at_index(*highlighted),
+                    &format!(
+                        "Throw Item  < {}/{} >",
+                        menu.page_number_at_index(*highlighted) + 1,
+                        menu.page_count() + 1
+                    ),
+                    "Escape to Cancel",
+                )
+                .draw(ctx, &mut self.world);
+                match self
+                    .settings
+                    .control_scheme
+                    .menu
+                    .get_value_with_context(ctx)
+                {
+                    Some(action) => match action {
+                        MenuAction::Exit => RunState::AwaitingInput {
+                            offset_x: 0,
+                            offset_y: 0,
+                        },
+                        MenuAction::MoveHighlightNext => RunState::ThrowItemMenu {
+                            highlighted: menu.get_next_index(*highlighted),
+                        },
+                        MenuAction::MoveHighlightPrev => RunState::ThrowItemMenu {
+                            highlighted: menu.get_previous_index(*highlighted),
+                        },
+                        MenuAction::NextPage => RunState::ThrowItemMenu {
+                            highlighted: menu.get_next_page_index(*highlighted),
+                        },
+                        MenuAction::PreviousPage => RunState::ThrowItemMenu {
+                            highlighted: menu.get_previous_page_index(*highlighted),
+                        },
+                        MenuAction::Select => match inventory_entities.get(*highlighted) {
+                            Some(ent) => RunState::ThrowTargeting { item: *ent },
+                            None => RunState::ThrowItemMenu {
                                 highlighted: *highlighted,
                             },
                         },
-                        _ => RunState::DropItemMenu {
+                        _ => RunState::ThrowItemMenu {
                             highlighted: *highlighted,
                         },
                     },
-                    None => RunState::DropItemMenu {
+                    None => RunState::ThrowItemMenu {
                         highlighted: *highlighted,
                     },
                 }
@@ -897,6 +1087,67 @@ impl GameState for State {
                     },
                 }
             }
+            RunState::FireTargeting { range } => {
+                let visible_tiles = ranged::get_visible_tiles_in_range(&self.world, *range);
+                let target = ranged::get_target(&self.world, ctx, &visible_tiles);
+                ScreenMapTargeting::new(*range, target, Some("Select Target".to_string()))
+                    .draw(ctx, &mut self.world);
+                match self
+                    .settings
+                    .control_scheme
+                    .targeting
+                    .get_value_with_context(ctx)
+                {
+                    Some(action) => match action {
+                        TargetingAction::Exit => RunState::AwaitingInput {
+                            offset_x: 0,
+                            offset_y: 0,
+                        },
+                        TargetingAction::Selected => match target {
+                            Some(idx) => {
+                                player::fire_at(&mut self.world, idx);
+                                RunState::PlayerTurn
+                            }
+                            None => RunState::AwaitingInput {
+                                offset_x: 0,
+                                offset_y: 0,
+                            },
+                        },
+                    },
+                    None => RunState::FireTargeting { range: *range },
+                }
+            }
+            RunState::ThrowTargeting { item } => {
+                let range = ranged::THROW_RANGE;
+                let visible_tiles = ranged::get_visible_tiles_in_range(&self.world, range);
+                let target = ranged::get_target(&self.world, ctx, &visible_tiles);
+                ScreenMapTargeting::new(range, target, Some("Select Target".to_string()))
+                    .draw(ctx, &mut self.world);
+                match self
+                    .settings
+                    .control_scheme
+                    .targeting
+                    .get_value_with_context(ctx)
+                {
+                    Some(action) => match action {
+                        TargetingAction::Exit => RunState::AwaitingInput {
+                            offset_x: 0,
+                            offset_y: 0,
+                        },
+                        TargetingAction::Selected => match target {
+                            Some(idx) => {
+                                player::throw_item(&mut self.world, *item, idx);
+                                RunState::PlayerTurn
+                            }
+                            None => RunState::AwaitingInput {
+                                offset_x: 0,
+                                offset_y: 0,
+                            },
+                        },
+                    },
+                    None => RunState::ThrowTargeting { item: *item },
+                }
+            }
             RunState::InteractMenu {
                 highlighted,
                 target,
@@ -904,13 +1155,9 @@ impl GameState for State {
                 let options: Vec<InteractionType> =
                     get_interaction_options_for_target(&self.world, *target);
                 let menu: Menu<&str> = get_menu_from_interaction_options(*highlighted, &options);
-                let title = self
-                    .world
-                    .read_storage::<Name>()
-                    .get(*target)
-                    .unwrap()
-                    .name
-                    .clone();
+                let title = self.world.fetch::<Identification>().get_display_name(
+                    &self.world.read_storage::<Name>().get(*target).unwrap().name,
+                );
                 ScreenMapInteractMenu::new(
                     menu.get_page_at_index(*highlighted),
                     Some(&title),
@@ -1114,7 +1361,7 @@ impl GameState for State {
                         },
                         MenuAction::Select => match inventory_entities.get(*highlighted) {
                             Some(ent) => {
-                                player::pickup_item(&mut self.world, *ent, Some(*container));
+                                player::take_one_from_container(&mut self.world, *ent, *container);
                                 RunState::PlayerTurn
                             }
                             None => RunState::OpenContainerMenu {
@@ -1138,6 +1385,108 @@ impl GameState for State {
                     },
                 }
             }
+            RunState::CraftMenu {
+                highlighted,
+                workstation,
+            } => {
+                let workstation_type = self
+                    .world
+                    .read_storage::<Workstation>()
+                    .get(*workstation)
+                    .map(|w| w.workstation_type);
+                let recipes = match workstation_type {
+                    Some(workstation_type) => {
+                        crafting::get_recipes_for_workstation(&self.world, workstation_type)
+                    }
+                    None => vec![],
+                };
+                let recipe_texts: Vec<(String, bool)> = recipes
+                    .iter()
+                    .map(|recipe| {
+                        (
+                            crafting::get_recipe_text(&self.world, recipe),
+                            crafting::has_ingredients(&self.world, recipe),
+                        )
+                    })
+                    .collect();
+                let menu_options: Box<[MenuOption<&String>]> = recipe_texts
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (text, craftable))| {
+                        let state = match (*highlighted == index, craftable) {
+                            (true, _) => MenuOptionState::Highlighted,
+                            (false, true) => MenuOptionState::Normal,
+                            (false, false) => MenuOptionState::Disabled,
+                        };
+                        MenuOption::new(text, state)
+                    })
+                    .collect();
+
+                let menu = Menu::new(menu_options, 10);
+                let verb = match workstation_type {
+                    Some(workstation_type) => {
+                        workstation_type::get_verb_for_workstation(&workstation_type)
+                    }
+                    None => "Craft".to_string(),
+                };
+                ScreenMapMenu::new(
+                    menu.get_page_at_index(*highlighted),
+                    &format!(
+                        "{}  < {}/{} >",
+                        verb,
+                        menu.page_number_at_index(*highlighted) + 1,
+                        menu.page_count() + 1
+                    ),
+                    "Escape to Cancel",
+                )
+                .draw(ctx, &mut self.world);
+                match self
+                    .settings
+                    .control_scheme
+                    .menu
+                    .get_value_with_context(ctx)
+                {
+                    Some(action) => match action {
+                        MenuAction::Exit => RunState::AwaitingInput {
+                            offset_x: 0,
+                            offset_y: 0,
+                        },
+                        MenuAction::MoveHighlightNext => RunState::CraftMenu {
+                            highlighted: menu.get_next_index(*highlighted),
+                            workstation: *workstation,
+                        },
+                        MenuAction::MoveHighlightPrev => RunState::CraftMenu {
+                            highlighted: menu.get_previous_index(*highlighted),
+                            workstation: *workstation,
+                        },
+                        MenuAction::NextPage => RunState::CraftMenu {
+                            highlighted: menu.get_next_page_index(*highlighted),
+                            workstation: *workstation,
+                        },
+                        MenuAction::PreviousPage => RunState::CraftMenu {
+                            highlighted: menu.get_previous_page_index(*highlighted),
+                            workstation: *workstation,
+                        },
+                        MenuAction::Select => match recipes.get(*highlighted) {
+                            Some(recipe) if crafting::craft(&mut self.world, recipe) => {
+                                RunState::PlayerTurn
+                            }
+                            _ => RunState::CraftMenu {
+                                highlighted: *highlighted,
+                                workstation: *workstation,
+                            },
+                        },
+                        _ => RunState::CraftMenu {
+                            highlighted: *highlighted,
+                            workstation: *workstation,
+                        },
+                    },
+                    None => RunState::CraftMenu {
+                        highlighted: *highlighted,
+                        workstation: *workstation,
+                    },
+                }
+            }
             RunState::ActionMenu { highlighted } => {
                 let menu_options: Box<[MenuOption<&str>]> = [
                     "Use Item",
@@ -1153,6 +1502,9 @@ impl GameState for State {
                     "Douse",
                     "Light",
                     "Equipment Menu",
+                    "Throw Item",
+                    "Study",
+                    "Craft",
                 ]
                 .iter()
                 .enumerate()
@@ -1255,6 +1607,17 @@ impl GameState for State {
                                 action_highlighted: 0,
                                 action_menu: false,
                             },
+                            13 => RunState::ThrowItemMenu { highlighted: 0 },
+                            14 => {
+                                player::study(&mut self.world);
+                                RunState::PlayerTurn
+                            }
+                            15 => RunState::InteractionTypeEntityTargeting {
+                                target_idx: 0,
+                                targets: get_interaction_type_targets::<Workstation>(&self.world),
+                                interaction_type: InteractionType::Craft,
+                                cta: Some(copy::CTA_INTERACT_CRAFT),
+                            },
                             _ => RunState::ActionMenu {
                                 highlighted: *highlighted,
                             },
@@ -1453,7 +1816,10 @@ impl GameState for State {
                     *action_menu,
                     &description,
                     "Equipment",
-                    "Escape to Cancel",
+                    &format!(
+                        "{}. Escape to Cancel",
+                        encumbrance::get_player_weight_text(&self.world)
+                    ),
                 )
                 .draw(ctx, &mut self.world);
                 match self
@@ -1937,7 +2303,6 @@ pub fn start() {
     gs.world.register::<Confused>();
     gs.world.register::<SimpleMarker<Saveable>>();
     gs.world.register::<SerializationHelper>();
-    gs.world.register::<Blood>();
     gs.world.register::<ParticleLifetime>();
     gs.world.register::<Hidden>();
     gs.world.register::<EntryTrigger>();
@@ -1979,18 +2344,47 @@ pub fn start() {
     gs.world.register::<Armable>();
     gs.world.register::<DamageHistory>();
     gs.world.register::<Inventory>();
+    gs.world.register::<TwoHanded>();
+    gs.world.register::<ProvidesBlock>();
+    gs.world.register::<Ammunition>();
+    gs.world.register::<Launcher>();
+    gs.world.register::<WantsToFire>();
+    gs.world.register::<WantsToThrow>();
+    gs.world.register::<Stackable>();
+    gs.world.register::<Weight>();
+    gs.world.register::<Encumbered>();
+    gs.world.register::<Identifies>();
+    gs.world.register::<WantsToStudy>();
+    gs.world.register::<Workstation>();
+    gs.world.register::<Fuel>();
+    gs.world.register::<Refillable>();
+    gs.world.register::<WantsToRefuel>();
+    gs.world.register::<Dripping>();
+    gs.world.register::<WantsToSmash>();
+    gs.world.register::<Explosive>();
+    gs.world.register::<WiredTo>();
+    gs.world.register::<Mechanism>();
+    gs.world.register::<Activated>();
+    gs.world.register::<Key>();
+    gs.world.register::<WantsToPickLock>();
     gs.world.insert(SimpleMarkerAllocator::<Saveable>::new());
     gs.world.insert(GameLog {
         entries: vec!["Enter the dungeon apprentice! Bring back the Talisman!".to_owned()],
     }); // This needs to get moved to a continue game function I think...
-    let rng = RandomNumberGenerator::new();
+    let mut rng = RandomNumberGenerator::new();
+    gs.world.insert(Identification::new(&mut rng));
     gs.world.insert(rng);
     gs.world.insert(ParticleEffectSpawner::new());
-    gs.world.insert(BloodSpawner::new());
+    gs.world.insert(SurfaceSpawner::new());
+    gs.world.insert(TerrainDamage::new());
+    gs.world.insert(ExplosionSpawner::new());
     gs.world.insert(DebrisSpawner::new());
     gs.world.insert(TrapSpawner::new());
     gs.world.insert(ItemSpawner::new());
     gs.world.insert(CorpseSpawner::new());
+    gs.world.insert(StackSpawner::new());
+    gs.world.insert(crafting::Recipes::new());
+    gs.world.insert(Prefabs::load());
     let context = RltkBuilder::simple(SCREEN_WIDTH, SCREEN_HEIGHT)
         .unwrap()
         .with_title("Apprentice")
@@ -0,0 +1,26 @@
+use crate::components::{memory::MemoryLocation, Memory, Monster, Position};
+use crate::dungeon::{level::Level, level_utils};
+use specs::{storage::MaskedStorage, Entity, Join, ReadStorage, Storage, WriteStorage};
+use std::ops::Deref;
+
+// any monster close enough to hear a noise goes to see what made it.
+pub fn make_noise<D: Deref<Target = MaskedStorage<Position>>>(
+    level: &Level,
+    idx: usize,
+    radius: f32,
+    positions: &Storage<Position, D>,
+    monsters: &ReadStorage<Monster>,
+    memories: &mut WriteStorage<Memory>,
+    exclude: Option<Entity>,
+) {
+    for (monster_ent, _monster, position, memory) in
+        (positions.fetched_entities(), monsters, positions, memories).join()
+    {
+        if Some(monster_ent) == exclude || position.level != level.id {
+            continue;
+        }
+        if level_utils::get_distance_between_idxs(level, position.idx, idx) <= radius {
+            memory.wander_destination = Some(MemoryLocation(level.id as i32, idx));
+        }
+    }
+}
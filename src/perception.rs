@@ -0,0 +1,68 @@
+use crate::dungeon::{level::Level, level_utils};
+use std::collections::HashSet;
+
+// anyone can make out their surroundings this close, no matter how dark it is.
+pub const DARKVISION_RANGE: u32 = 2;
+// tiles lit more dimly than this are too dark to see into from further away.
+pub const MIN_VISIBLE_LIGHT: f32 = 0.1;
+pub const EXPOSED_LIGHT: f32 = 0.5;
+// someone standing in dim light can only be picked out from this close, it takes bright light
+// to stand out from anywhere in sight.
+pub const DIM_SPOTTING_RANGE: f32 = 5.0;
+
+pub enum Exposure {
+    Hidden,
+    Dim,
+    Exposed,
+}
+
+pub fn get_light_level(level: &Level, idx: usize) -> f32 {
+    match level.lit_tiles.get(idx) {
+        Some(intensity) => *intensity,
+        None => 0.0,
+    }
+}
+
+pub fn get_exposure(light_level: f32) -> Exposure {
+    if light_level < MIN_VISIBLE_LIGHT {
+        Exposure::Hidden
+    } else if light_level < EXPOSED_LIGHT {
+        Exposure::Dim
+    } else {
+        Exposure::Exposed
+    }
+}
+
+// every viewer, player or monster, sees the tiles in their line of sight that are lit well
+// enough, plus whatever is close enough to make out in the dark.
+pub fn get_visible_tiles(level: &Level, idx: usize, los_tiles: &HashSet<usize>) -> HashSet<usize> {
+    let nearby_tiles = level_utils::get_field_of_view_from_idx(level, idx, DARKVISION_RANGE);
+    los_tiles
+        .iter()
+        .filter(|tile| get_light_level(level, **tile) >= MIN_VISIBLE_LIGHT)
+        .copied()
+        .chain(nearby_tiles.into_iter())
+        .collect()
+}
+
+// whether a viewer can pick out someone standing on a tile they can see, which gets harder the
+// darker that tile is. The light gem on the HUD shows the same bands.
+pub fn can_spot(
+    level: &Level,
+    viewer_idx: usize,
+    visible_tiles: &HashSet<usize>,
+    target_idx: usize,
+) -> bool {
+    if !visible_tiles.contains(&target_idx) {
+        return false;
+    }
+    match get_exposure(get_light_level(level, target_idx)) {
+        Exposure::Exposed => true,
+        Exposure::Dim => {
+            level_utils::get_distance_between_idxs(level, viewer_idx, target_idx)
+                <= DIM_SPOTTING_RANGE
+        }
+        // only close enough to make out in the dark, or they wouldn't be visible at all.
+        Exposure::Hidden => true,
+    }
+}
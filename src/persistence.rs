@@ -4,14 +4,17 @@
 // It might be good in the future to look into making a custom impl for SerializeComponents
 // to replace the custom macros
 use crate::components::{
-    AreaOfEffect, Armable, BlocksTile, Blood, CausesDamage, CausesFire, CausesLight, CombatStats,
-    Confusion, Consumable, Container, DamageHistory, Disarmable, Door, Dousable, EntityMoved,
-    EntryTrigger, Equipable, Equipment, Flammable, Furniture, Grabbable, Grabbing, Hidden, Hiding,
-    HidingSpot, Info, Inventory, Item, Lightable, Memory, Monster, Name, Objective, OnFire,
-    ParticleLifetime, Player, Position, ProvidesHealing, Ranged, Renderable, Saveable,
-    SerializationHelper, SingleActivation, SufferDamage, Trap, Triggered, Viewshed,
+    Ammunition, AreaOfEffect, Armable, BlocksTile, CausesDamage, CausesFire, CausesLight,
+    CombatStats, Confusion, Consumable, Container, DamageHistory, Disarmable, Door, Dousable,
+    Dripping, Encumbered, EntityMoved, EntryTrigger, Equipable, Equipment, Explosive, Flammable,
+    Fuel, Furniture, Grabbable, Grabbing, Hidden, Hiding, HidingSpot, Identifies, Info, Inventory,
+    Item, Key, Launcher, Lightable, Mechanism, Memory, Monster, Name, Objective, OnFire,
+    ParticleLifetime, Player, Position, Potion, ProvidesBlock, ProvidesHealing, Ranged, Refillable,
+    Renderable, Saveable, SerializationHelper, SingleActivation, Stackable, SufferDamage, Trap,
+    Triggered, TwoHanded, Viewshed, Weight, WiredTo, Workstation,
 };
-use crate::dungeon::{constants::MAP_COUNT, dungeon::Dungeon};
+use crate::dungeon::dungeon::Dungeon;
+use crate::identification::Identification;
 use specs::{
     error::NoError,
     join::Join,
@@ -68,10 +71,12 @@ macro_rules! deserialize_individually {
 
 fn create_save_game_helpers(world: &mut World) {
     let dungeon_copy = world.get_mut::<Dungeon>().unwrap().clone();
+    let identification_copy = world.get_mut::<Identification>().unwrap().clone();
     world
         .create_entity()
         .with(SerializationHelper {
             dungeon: dungeon_copy,
+            identification: identification_copy,
         })
         .marked::<SimpleMarker<Saveable>>()
         .build();
@@ -116,7 +121,6 @@ fn save_game_with_writer<T: Write>(world: &mut World, writer: T) -> serde_json::
             AreaOfEffect,
             Confusion,
             ProvidesHealing,
-            Blood,
             ParticleLifetime,
             Hidden,
             EntryTrigger,
@@ -147,6 +151,23 @@ fn save_game_with_writer<T: Write>(world: &mut World, writer: T) -> serde_json::
             DamageHistory,
             Inventory,
             Door,
+            TwoHanded,
+            ProvidesBlock,
+            Ammunition,
+            Launcher,
+            Potion,
+            Stackable,
+            Weight,
+            Encumbered,
+            Identifies,
+            Workstation,
+            Fuel,
+            Refillable,
+            Dripping,
+            Explosive,
+            WiredTo,
+            Mechanism,
+            Key,
             SerializationHelper
         );
     }
@@ -181,7 +202,6 @@ fn deserialize_from_string(world: &mut World, game_string: String) {
         AreaOfEffect,
         Confusion,
         ProvidesHealing,
-        Blood,
         ParticleLifetime,
         Hidden,
         EntryTrigger,
@@ -212,6 +232,23 @@ fn deserialize_from_string(world: &mut World, game_string: String) {
         DamageHistory,
         Inventory,
         Door,
+        TwoHanded,
+        ProvidesBlock,
+        Ammunition,
+        Launcher,
+        Potion,
+        Stackable,
+        Weight,
+        Encumbered,
+        Identifies,
+        Workstation,
+        Fuel,
+        Refillable,
+        Dripping,
+        Explosive,
+        WiredTo,
+        Mechanism,
+        Key,
         SerializationHelper
     );
 }
@@ -223,7 +260,7 @@ fn get_dungeon(world: &mut World) -> Dungeon {
         .map(|h| {
             let mut cloned_dungeon = h.dungeon.clone();
             for (_i, mut level) in cloned_dungeon.levels.iter_mut() {
-                level.tile_content = vec![Vec::new(); MAP_COUNT];
+                level.tile_content = vec![Vec::new(); level.tiles.len()];
             }
             cloned_dungeon
         })
@@ -236,6 +273,20 @@ fn populate_map_from_helper(world: &mut World) {
     world.insert(dungeon);
 }
 
+fn get_identification(world: &mut World) -> Identification {
+    let serialization_helpers = world.read_storage::<SerializationHelper>();
+    let mut identifications: Vec<Identification> = (serialization_helpers)
+        .join()
+        .map(|h| h.identification.clone())
+        .collect();
+    identifications.remove(0)
+}
+
+fn populate_identification_from_helper(world: &mut World) {
+    let identification = get_identification(world);
+    world.insert(identification);
+}
+
 fn get_player_parts(world: &mut World) -> Entity {
     let entities = world.entities();
     let player = world.read_storage::<Player>();
@@ -253,6 +304,7 @@ fn load_game_from_string(world: &mut World, game_string: String) {
     world.delete_all();
     deserialize_from_string(world, game_string);
     populate_map_from_helper(world);
+    populate_identification_from_helper(world);
     delete_helpers(world);
     populate_player(world);
 }
@@ -1,14 +1,16 @@
 use std::collections::HashSet;
 
 use crate::components::{
-    equipable::EquipmentPositions, Item, Monster, Position, Trap, Viewshed, WantsToCloseDoor,
-    WantsToDisarmTrap, WantsToDouse, WantsToEquip, WantsToGrab, WantsToHide, WantsToLight,
-    WantsToMelee, WantsToMove, WantsToOpenDoor, WantsToPickUpItem, WantsToReleaseGrabbed,
-    WantsToSearchHidden, WantsToTrap, WantsToUse,
+    equipable::EquipmentPositions, CausesDamage, Equipment, Item, Monster, Position, Trap,
+    Viewshed, WantsToCloseDoor, WantsToDisarmTrap, WantsToDouse, WantsToEquip, WantsToFire,
+    WantsToGrab, WantsToHide, WantsToLight, WantsToMelee, WantsToMove, WantsToOpenDoor,
+    WantsToPickLock, WantsToPickUpItem, WantsToRefuel, WantsToReleaseGrabbed, WantsToSearchHidden,
+    WantsToSmash, WantsToStudy, WantsToThrow, WantsToTrap, WantsToUse,
 };
-use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils};
+use crate::dungeon::{branch, dungeon::Dungeon, level::Level, level_utils};
 use crate::entity_option::EntityOption;
 use crate::services::game_log::GameLog;
+use crate::systems::smash_system::get_smashing_damage;
 use crate::user_actions::MapAction;
 use specs::{Component, Entity, Join, World, WorldExt};
 
@@ -30,17 +32,38 @@ fn try_move_player(delta_x: i32, delta_y: i32, world: &mut World) {
     let player_entity = world.fetch::<Entity>();
     let player_position = positions.get(*player_entity).unwrap();
     let level = dungeon.get_level(player_position.level).unwrap();
-    let level_width = level.width as i32;
-    let destination_index = level_utils::add_xy_to_idx(
-        level_width as i32,
-        delta_x as i32,
-        delta_y as i32,
-        player_position.idx as i32,
-    );
-    let target = level.tile_content[destination_index as usize]
+    let (x, y) = level_utils::idx_xy(level.width as u32, player_position.idx);
+    // there's nowhere to go past the edge of the level.
+    let destination_index = match level_utils::get_idx_at_xy(level, x + delta_x, y + delta_y) {
+        Some(idx) => idx,
+        None => return,
+    };
+    let target = level.tile_content[destination_index]
         .iter()
         .filter(|e| monsters.get(**e).is_some())
         .next();
+    // walking into breakable stone with something heavy in hand swings at it.
+    let can_smash = level_utils::tile_is_breakable(level, destination_index)
+        && match world.read_storage::<Equipment>().get(*player_entity) {
+            Some(equipment) => get_smashing_damage(
+                equipment.dominant_hand,
+                &world.read_storage::<CausesDamage>(),
+            )
+            .is_some(),
+            None => false,
+        };
+    if target.is_none() && can_smash {
+        world
+            .write_storage::<WantsToSmash>()
+            .insert(
+                *player_entity,
+                WantsToSmash {
+                    idx: destination_index,
+                },
+            )
+            .expect("couldn't insert player smash intent");
+        return;
+    }
     match target {
         Some(target) => {
             world
@@ -54,7 +77,7 @@ fn try_move_player(delta_x: i32, delta_y: i32, world: &mut World) {
                 .insert(
                     *player_entity,
                     WantsToMove {
-                        idx: destination_index as usize,
+                        idx: destination_index,
                     },
                 )
                 .expect("couldn't insert player move intent");
@@ -93,6 +116,7 @@ fn try_pickup_item(world: &mut World) {
                     WantsToPickUpItem {
                         container: None,
                         items,
+                        whole_stack: true,
                     },
                 )
                 .expect("Unable to insert want to pick up");
@@ -101,61 +125,49 @@ fn try_pickup_item(world: &mut World) {
 }
 
 fn can_go_up(current_level: &Level, player_idx: usize) -> bool {
-    match current_level.stairs_up {
-        Some(idx) => idx == player_idx,
-        None => false,
-    }
+    current_level.stairs_up.contains(&player_idx)
 }
 
 fn can_go_down(current_level: &Level, player_idx: usize) -> bool {
-    match current_level.stairs_down {
-        Some(idx) => idx == player_idx,
-        None => false,
-    }
+    current_level.stairs_down.contains(&player_idx)
 }
 
-// most of this should be moved into a go down stairs system
-fn try_go_down_stairs(world: &mut World) {
+// each flight of stairs leads somewhere different, the dungeon knows where.
+fn take_stairs(world: &mut World, can_take_stairs: fn(&Level, usize) -> bool) {
     let player_entity = world.fetch::<Entity>();
     let mut positions = world.write_storage::<Position>();
     let mut player_position = positions.get_mut(*player_entity).unwrap();
     let dungeon = world.fetch::<Dungeon>();
     let current_level = dungeon.get_level(player_position.level).unwrap();
-    if !can_go_down(current_level, player_position.idx) {
+    if !can_take_stairs(current_level, player_position.idx) {
         return;
     }
-    let next_level_number = player_position.level - 1;
-    let next_map = dungeon.get_level(next_level_number);
-    if let Some(next_map) = next_map {
-        let stairs_up_coords = next_map.stairs_up.unwrap();
-        player_position.idx = stairs_up_coords;
+    let destination = dungeon.get_destination(player_position.level, player_position.idx);
+    if let Some((next_level_number, next_idx)) = destination {
+        player_position.idx = next_idx;
         player_position.level = next_level_number;
         let mut viewsheds = world.write_storage::<Viewshed>();
         let mut player_viewshed = viewsheds.get_mut(*player_entity).unwrap();
         player_viewshed.dirty = true;
+        let next_level = dungeon.get_level(next_level_number).unwrap();
+        if let Some(branch) = next_level.branch {
+            let mut log = world.fetch_mut::<GameLog>();
+            log.add(format!(
+                "You enter the {}.",
+                branch::get_name_for_branch(branch)
+            ));
+        }
     }
 }
 
+// most of this should be moved into a go down stairs system
+fn try_go_down_stairs(world: &mut World) {
+    take_stairs(world, can_go_down);
+}
+
 // most of this should be moved into a go up stairs system
 fn try_go_up_stairs(world: &mut World) {
-    let player_entity = world.fetch::<Entity>();
-    let mut positions = world.write_storage::<Position>();
-    let mut player_position = positions.get_mut(*player_entity).unwrap();
-    let dungeon = world.fetch::<Dungeon>();
-    let current_level = dungeon.get_level(player_position.level).unwrap();
-    if !can_go_up(current_level, player_position.idx) {
-        return;
-    }
-    let next_level_number = player_position.level + 1;
-    let next_map = dungeon.get_level(next_level_number);
-    if let Some(next_map) = next_map {
-        let stairs_down_coords = next_map.stairs_down.unwrap();
-        player_position.idx = stairs_down_coords;
-        player_position.level = next_level_number;
-        let mut viewsheds = world.write_storage::<Viewshed>();
-        let mut player_viewshed = viewsheds.get_mut(*player_entity).unwrap();
-        player_viewshed.dirty = true;
-    }
+    take_stairs(world, can_go_up);
 }
 
 fn insert_intent<T: Component>(
@@ -184,6 +196,14 @@ pub fn use_item(world: &mut World, item: Entity, target: Option<usize>) {
     }
 }
 
+pub fn fire_at(world: &mut World, target: usize) {
+    insert_intent(world, WantsToFire { target }).expect("Unable To Insert Fire Intent");
+}
+
+pub fn throw_item(world: &mut World, item: Entity, target: usize) {
+    insert_intent(world, WantsToThrow { item, target }).expect("Unable To Insert Throw Intent");
+}
+
 pub fn open_door(world: &mut World, door: Entity) {
     insert_intent(world, WantsToOpenDoor { door })
         .expect("could not insert wants to open door for player");
@@ -194,11 +214,20 @@ pub fn close_door(world: &mut World, door: Entity) {
         .expect("could not insert wants to close door for player");
 }
 
+pub fn pick_lock(world: &mut World, door: Entity) {
+    insert_intent(world, WantsToPickLock { door })
+        .expect("could not insert wants to pick lock for player");
+}
+
 pub fn search_hidden(world: &mut World) {
     insert_intent(world, WantsToSearchHidden {})
         .expect("could not insert wants to search hidden for player");
 }
 
+pub fn study(world: &mut World) {
+    insert_intent(world, WantsToStudy {}).expect("could not insert wants to study for player");
+}
+
 pub fn disarm_trap(world: &mut World, item: Entity) {
     insert_intent(world, WantsToDisarmTrap { trap: item })
         .expect("Unable to Insert Disarm Trap Intent");
@@ -263,9 +292,20 @@ pub fn light_item(world: &mut World, item: Entity) {
     insert_intent(world, WantsToLight { item }).expect("Unable to Insert Douse Intent");
 }
 
+pub fn refuel(world: &mut World, fuel: Entity) {
+    insert_intent(world, WantsToRefuel { fuel }).expect("Unable to Insert Refuel Intent");
+}
+
 pub fn pickup_items(world: &mut World, items: HashSet<Entity>, container: Option<Entity>) {
-    insert_intent(world, WantsToPickUpItem { container, items })
-        .expect("Unable to insert want to pick up");
+    insert_intent(
+        world,
+        WantsToPickUpItem {
+            container,
+            items,
+            whole_stack: true,
+        },
+    )
+    .expect("Unable to insert want to pick up");
 }
 
 pub fn pickup_item(world: &mut World, item: Entity, container: Option<Entity>) {
@@ -274,6 +314,21 @@ pub fn pickup_item(world: &mut World, item: Entity, container: Option<Entity>) {
     pickup_items(world, items, container);
 }
 
+// takes a single item out of a container, leaving the rest of its stack behind.
+pub fn take_one_from_container(world: &mut World, item: Entity, container: Entity) {
+    let mut items = HashSet::new();
+    items.insert(item);
+    insert_intent(
+        world,
+        WantsToPickUpItem {
+            container: Some(container),
+            items,
+            whole_stack: false,
+        },
+    )
+    .expect("Unable to insert want to pick up");
+}
+
 #[derive(Copy, Clone, PartialEq)]
 pub enum InteractionType {
     Douse,
@@ -291,7 +346,9 @@ pub enum InteractionType {
     Pickup,
     OpenDoor,
     CloseDoor,
+    PickLock,
     OpenContainer,
+    Craft,
 }
 
 pub fn interact(world: &mut World, object: Entity, interaction_type: InteractionType) {
@@ -306,6 +363,7 @@ pub fn interact(world: &mut World, object: Entity, interaction_type: Interaction
         InteractionType::Pickup => pickup_item(world, object, None),
         InteractionType::OpenDoor => open_door(world, object),
         InteractionType::CloseDoor => close_door(world, object),
+        InteractionType::PickLock => pick_lock(world, object),
         _ => {}
     }
 }
@@ -1,11 +1,14 @@
 use std::collections::HashSet;
 
-use crate::components::{Position, Viewshed};
+use crate::components::{Ammunition, Equipment, Inventory, Launcher, Position, Viewshed};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
-use crate::screens::utils::get_render_offset_for_xy;
+use crate::screens::utils::get_idx_for_screen_xy;
+use crate::systems::ranged_combat_system::get_ammunition_for_launcher;
 use rltk::Rltk;
 use specs::{Entity, World, WorldExt};
 
+pub const THROW_RANGE: u32 = 6;
+
 pub fn get_visible_tiles_in_range(world: &World, range: u32) -> HashSet<usize> {
     let player_ent = world.fetch::<Entity>();
     let positions = world.read_storage::<Position>();
@@ -33,13 +36,35 @@ pub fn get_target<'a>(world: &World, ctx: &mut Rltk, tiles: &'a HashSet<usize>)
     let dungeon = world.fetch::<Dungeon>();
     let positions = world.read_storage::<Position>();
     let player_position = positions.get(*player_ent).unwrap();
-    let level_width = dungeon.get_level(player_position.level).unwrap().width;
-    let (center_x, center_y) = level_utils::idx_xy(level_width as u32, player_position.idx);
+    let level = dungeon.get_level(player_position.level).unwrap();
+    let (center_x, center_y) = level_utils::idx_xy(level.width as u32, player_position.idx);
     let (mouse_x, mouse_y) = ctx.mouse_pos();
-    let (offset_x, offset_y) = get_render_offset_for_xy(center_x, center_y, mouse_x, mouse_y);
-    let idx = level_utils::xy_idx(level_width as u32, offset_x, offset_y);
-    match tiles.contains(&idx) {
-        true => Some(idx),
-        false => None,
+    get_idx_for_screen_xy(level, center_x, center_y, mouse_x, mouse_y)
+        .filter(|idx| tiles.contains(idx))
+}
+
+// returns the range of the player's launcher if it is ready to fire, otherwise a message
+// explaining why it can't be.
+pub fn get_player_fire_range(world: &World) -> Result<u32, String> {
+    let player_ent = world.fetch::<Entity>();
+    let equipment = world.read_storage::<Equipment>();
+    let launchers = world.read_storage::<Launcher>();
+    let inventories = world.read_storage::<Inventory>();
+    let ammunition = world.read_storage::<Ammunition>();
+    let dominant_hand = match equipment.get(*player_ent) {
+        Some(e) => e.dominant_hand,
+        None => None,
+    };
+    let (launcher_ent, launcher) = match dominant_hand {
+        Some(ent) => match launchers.get(ent) {
+            Some(launcher) => (ent, launcher),
+            None => return Err("You aren't holding anything you can fire".to_string()),
+        },
+        None => return Err("You aren't holding anything you can fire".to_string()),
+    };
+    let inventory = inventories.get(*player_ent).unwrap();
+    match get_ammunition_for_launcher(launcher_ent, launcher, inventory, &ammunition) {
+        Some(_) => Ok(launcher.range),
+        None => Err("You have no ammunition for that".to_string()),
     }
 }
@@ -14,6 +14,12 @@ pub enum RunState {
         range: u32,
         item: Entity,
     },
+    FireTargeting {
+        range: u32,
+    },
+    ThrowTargeting {
+        item: Entity,
+    },
     InteractionTypeEntityTargeting {
         target_idx: usize,
         targets: Box<[Entity]>,
@@ -31,12 +37,19 @@ pub enum RunState {
         highlighted: usize,
         container: Entity,
     },
+    CraftMenu {
+        highlighted: usize,
+        workstation: Entity,
+    },
     InventoryMenu {
         highlighted: usize,
     },
     DropItemMenu {
         highlighted: usize,
     },
+    ThrowItemMenu {
+        highlighted: usize,
+    },
     EquipMenu {
         highlighted: usize,
         position: EquipmentPositions,
@@ -1,7 +1,9 @@
 use super::ui::ui_map_screen::UIMapScreen;
-use super::utils::{get_render_data, get_render_offset, get_render_offset_for_xy};
+use super::utils::{get_idx_for_screen_xy, get_render_data, get_render_offset};
 use crate::components::{CombatStats, Hidden, Hiding, Name, Position, Viewshed};
-use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::dungeon::{dungeon::Dungeon, level_utils, surface_type};
+use crate::identification::Identification;
+use crate::perception;
 use crate::services::GameLog;
 use rltk::Rltk;
 use specs::{Entity, Join, World, WorldExt};
@@ -24,6 +26,7 @@ impl ScreenMapGeneric {
         let hiding = world.read_storage::<Hiding>();
         let entities = world.entities();
         let names = world.read_storage::<Name>();
+        let identification = world.fetch::<Identification>();
         let positions = world.read_storage::<Position>();
         let hidden = world.read_storage::<Hidden>();
         let (mouse_x, mouse_y) = ctx.mouse_pos();
@@ -37,11 +40,10 @@ impl ScreenMapGeneric {
         let center_x = center_x + self.offset_x;
         let center_y = center_y + self.offset_y;
         let render_offset = get_render_offset(center_x, center_y);
-        let mouse_offset = get_render_offset_for_xy(center_x, center_y, mouse_x, mouse_y);
-        let mouse_idx = level_utils::xy_idx(level_width, mouse_offset.0, mouse_offset.1);
-        let tool_tip_lines: Box<[String]> = match player_viewshed.visible_tiles.contains(&mouse_idx)
-        {
-            true => (
+        let mouse_idx = get_idx_for_screen_xy(level, center_x, center_y, mouse_x, mouse_y)
+            .filter(|idx| player_viewshed.visible_tiles.contains(idx));
+        let tool_tip_lines: Box<[String]> = match mouse_idx {
+            Some(mouse_idx) => (
                 &names,
                 &positions,
                 (&hidden).maybe(),
@@ -63,13 +65,23 @@ impl ScreenMapGeneric {
                         && position.level == player_position.level
                         && position.idx == mouse_idx as usize
                 })
-                .map(|(name, _position, _hidden, hiding, _entity)| match hiding {
-                    Some(_) => format!("{} (hidden)", name.name),
-                    _ => name.name.clone(),
+                .map(|(name, _position, _hidden, hiding, _entity)| {
+                    let name = identification.get_display_name(&name.name);
+                    match hiding {
+                        Some(_) => format!("{} (hidden)", name),
+                        _ => name,
+                    }
                 })
                 // .map(|s| s.clone())
+                .chain(
+                    level
+                        .surfaces
+                        .get(mouse_idx)
+                        .and_then(|surface| *surface)
+                        .map(|surface| surface_type::get_name_for_surface(&surface)),
+                )
                 .collect(),
-            false => Box::new([]),
+            None => Box::new([]),
         };
         let tool_tip_lines: Box<[&str]> = tool_tip_lines.iter().map(|line| line.as_str()).collect();
         let render_data = get_render_data(world);
@@ -80,9 +92,10 @@ impl ScreenMapGeneric {
             mouse_y,
             &tool_tip_lines,
             &log_entries,
-            player_position.level,
+            level.depth,
             player_stats.hp,
             player_stats.max_hp,
+            perception::get_light_level(level, player_position.idx),
             level,
             &render_data,
             render_offset,
@@ -8,6 +8,7 @@ use super::{
 };
 use crate::components::{Name, Position, Viewshed};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::identification::Identification;
 use crate::ui_components::{Style, UITextLine};
 use rltk::{Rltk, BLACK, YELLOW};
 use specs::{Entity, World, WorldExt};
@@ -63,8 +64,10 @@ impl<'a> ScreenMapInteractTarget<'a> {
             false => UIToolTipPosition::Right,
         };
         let names = world.read_storage::<Name>();
-        let target_name = names.get(target).unwrap();
-        let tool_tip_lines: Box<[&str]> = Box::new([target_name.name.as_str()]);
+        let target_name = world
+            .fetch::<Identification>()
+            .get_display_name(&names.get(target).unwrap().name);
+        let tool_tip_lines: Box<[&str]> = Box::new([target_name.as_str()]);
         UIToolTip::new(focus_x, focus_y, tool_tip_pos, &tool_tip_lines).draw(ctx);
     }
 }
@@ -9,6 +9,7 @@ use crate::dungeon::{
     level_utils,
 };
 use crate::menu::MenuOption;
+use crate::perception;
 use crate::services::GameLog;
 use crate::ui_components::{UILineVertical, UIMenuBox, UIMenuItemGroup, UIParagraph};
 use rltk::Rltk;
@@ -72,9 +73,10 @@ impl<'a, T: Display + Copy, S: Display + Copy> ScreenMapItemMenu<'a, T, S> {
         let log_entries = log.entries.iter().map(String::as_str).collect();
 
         UIHud::new(
-            player_position.level,
+            level.depth,
             player_stats.hp,
             player_stats.max_hp,
+            perception::get_light_level(level, player_position.idx),
             &log_entries,
         )
         .draw(ctx);
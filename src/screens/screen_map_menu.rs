@@ -9,6 +9,7 @@ use crate::dungeon::{
     level_utils,
 };
 use crate::menu::MenuOption;
+use crate::perception;
 use crate::services::GameLog;
 use crate::ui_components::ui_dynamic_menu::UIDynamicMenu;
 use rltk::Rltk;
@@ -47,13 +48,20 @@ impl<'a, T: Display + Copy> ScreenMapMenu<'a, T> {
         let (center_x, center_y) = level_utils::idx_xy(level.width as u32, player_position.idx);
         let render_offset = get_render_offset(center_x, center_y);
 
-        UIMap::new(level, &render_data, render_offset, &player_viewshed.visible_tiles).draw(ctx);
+        UIMap::new(
+            level,
+            &render_data,
+            render_offset,
+            &player_viewshed.visible_tiles,
+        )
+        .draw(ctx);
         let log_entries = log.entries.iter().map(String::as_str).collect();
 
         UIHud::new(
-            player_position.level,
+            level.depth,
             player_stats.hp,
             player_stats.max_hp,
+            perception::get_light_level(level, player_position.idx),
             &log_entries,
         )
         .draw(ctx);
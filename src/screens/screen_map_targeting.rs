@@ -4,6 +4,7 @@ use super::ui::ui_mouse_pos::UIMousePos;
 use super::utils::{get_render_data, get_render_offset};
 use crate::components::{CombatStats, Position, Viewshed};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::perception;
 use crate::ranged;
 use crate::services::GameLog;
 use crate::ui_components::{Style, UITextLine};
@@ -45,9 +46,10 @@ impl ScreenMapTargeting {
         .draw(ctx);
         let log_entries = log.entries.iter().map(String::as_str).collect();
         UIHud::new(
-            player_position.level,
+            level.depth,
             player_stats.hp,
             player_stats.max_hp,
+            perception::get_light_level(level, player_position.idx),
             &log_entries,
         )
         .draw(ctx);
@@ -1,3 +1,4 @@
+use crate::perception::{get_exposure, Exposure};
 use crate::screens::constants::{MAP_HEIGHT, MAP_WIDTH, SCREEN_HEIGHT};
 use crate::ui_components::{Style, UIBox, UITextLine};
 use rltk::{Rltk, BLACK, RED, RGB, WHITE, YELLOW};
@@ -12,24 +13,67 @@ const HUD_HEALTH_BAR_WIDTH: i32 = HUD_WIDTH as i32 - HUD_HEALTH_BAR_LEFT;
 const MESSAGES_TOP: i32 = HUD_TOP + 1;
 const MESSAGES_LEFT: i32 = HUD_LEFT + 1;
 const MESSAGE_COUNT: u8 = HUD_HEIGHT - 2;
+const HUD_LIGHT_GEM_LEFT: i32 = 2;
+const HUD_LIGHT_GEM_TOP: i32 = HUD_TOP + HUD_HEIGHT as i32;
 
 pub struct UIHud<'a, 'b> {
     depth: u8,
     hp: i32,
     max_hp: i32,
+    light_level: f32,
     messages: &'b Box<[&'a str]>,
 }
 
 impl<'a, 'b> UIHud<'a, 'b> {
-    pub fn new(depth: u8, hp: i32, max_hp: i32, messages: &'b Box<[&'a str]>) -> Self {
+    pub fn new(
+        depth: u8,
+        hp: i32,
+        max_hp: i32,
+        light_level: f32,
+        messages: &'b Box<[&'a str]>,
+    ) -> Self {
         Self {
             depth,
             hp,
             max_hp,
+            light_level,
             messages,
         }
     }
 
+    // the light gem glows brighter the more light is falling on the player.
+    fn draw_light_gem(&self, ctx: &mut Rltk) {
+        let exposure = match get_exposure(self.light_level) {
+            Exposure::Hidden => "Hidden",
+            Exposure::Dim => "Dim",
+            Exposure::Exposed => "Exposed",
+        };
+        let dark = RGB::from_f32(0.2, 0.2, 0.2);
+        let bright = RGB::named(YELLOW);
+        let gem_color = RGB::from_f32(
+            dark.r + (bright.r - dark.r) * self.light_level,
+            dark.g + (bright.g - dark.g) * self.light_level,
+            dark.b + (bright.b - dark.b) * self.light_level,
+        );
+        ctx.set(
+            HUD_LIGHT_GEM_LEFT,
+            HUD_LIGHT_GEM_TOP,
+            gem_color,
+            RGB::named(BLACK),
+            rltk::to_cp437('♦'),
+        );
+        UITextLine::new(
+            HUD_LIGHT_GEM_LEFT + 2,
+            HUD_LIGHT_GEM_TOP,
+            exposure,
+            Some(Style {
+                fg: YELLOW,
+                bg: BLACK,
+            }),
+        )
+        .draw(ctx);
+    }
+
     pub fn draw(&self, ctx: &mut Rltk) {
         UIBox::new(HUD_LEFT, HUD_TOP, HUD_WIDTH, HUD_HEIGHT, WHITE, BLACK).draw(ctx);
         UITextLine::new(
@@ -62,6 +106,7 @@ impl<'a, 'b> UIHud<'a, 'b> {
             RGB::named(RED),
             RGB::named(BLACK),
         );
+        self.draw_light_gem(ctx);
         for (i, message) in self
             .messages
             .iter()
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
-use crate::dungeon::{level::Level, level_utils, tile_type::TileType};
-use crate::screens::constants::MAP_WIDTH;
+use crate::dungeon::{level::Level, level_utils, secrets, surface_type, tile_type::TileType};
+use crate::screens::constants::{MAP_HEIGHT, MAP_WIDTH};
 use rltk::{Rltk, RGB};
 
 pub struct RenderData {
@@ -13,19 +13,18 @@ pub struct RenderData {
 }
 
 pub fn is_revealed_and_wall_or_door(level: &Level, x: i32, y: i32) -> bool {
-    let idx = level_utils::xy_idx(level.width as u32, x, y) as usize;
-    match level.tiles.get(idx) {
-        Some(tile) => {
-            (*tile == TileType::Wall || *tile == TileType::Door) && level.revealed_tiles[idx]
+    match level_utils::get_idx_at_xy(level, x, y) {
+        Some(idx) => {
+            let tile = level.tiles[idx];
+            (tile == TileType::Wall || tile == TileType::Door) && level.revealed_tiles[idx]
         }
         None => false,
     }
 }
 
 fn is_revealed_and_ledge(level: &Level, x: i32, y: i32) -> bool {
-    let idx = level_utils::xy_idx(level.width as u32, x, y) as usize;
-    match level.tiles.get(idx) {
-        Some(tile) => (*tile == TileType::Ledge) && level.revealed_tiles[idx],
+    match level_utils::get_idx_at_xy(level, x, y) {
+        Some(idx) => level.tiles[idx] == TileType::Ledge && level.revealed_tiles[idx],
         None => false,
     }
 }
@@ -92,13 +91,42 @@ fn get_ledge_tile(level: &Level, x: i32, y: i32) -> u16 {
     }
 }
 
-fn get_bg_color(tile_type: &TileType) -> RGB {
-    match tile_type {
-        TileType::WaterDeep => RGB::named(rltk::BLUE),
+fn get_bg_color(level: &Level, idx: usize, tile_type: &TileType) -> RGB {
+    match (tile_type, level.surfaces.get(idx)) {
+        (TileType::WaterDeep, _) => RGB::named(rltk::BLUE),
+        (_, Some(Some(surface))) => surface_type::get_background_color_for_surface(surface),
         _ => RGB::named(rltk::BLACK),
     }
 }
 
+fn get_fg_color(level: &Level, idx: usize, tile_type: &TileType) -> RGB {
+    match (tile_type, level.tracks.get(idx)) {
+        (TileType::Floor, Some(track)) if *track > 0 => RGB::named(rltk::DARK_RED),
+        _ => RGB::named(rltk::GREEN),
+    }
+}
+
+// how much of a light's colour bleeds into the tiles it lights, at full intensity.
+const LIGHT_FOREGROUND_STRENGTH: f32 = 0.5;
+const LIGHT_BACKGROUND_STRENGTH: f32 = 0.3;
+const FIRE_BACKGROUND_STRENGTH: f32 = 0.8;
+const SMOKE_STRENGTH_PER_DENSITY: f32 = 0.1;
+
+fn blend_colors(base: RGB, tint: RGB, amount: f32) -> RGB {
+    RGB::from_f32(
+        base.r + (tint.r - base.r) * amount,
+        base.g + (tint.g - base.g) * amount,
+        base.b + (tint.b - base.b) * amount,
+    )
+}
+
+fn get_light_at_idx(level: &Level, idx: usize) -> Option<(f32, RGB)> {
+    match (level.lit_tiles.get(idx), level.light_colors.get(idx)) {
+        (Some(intensity), Some(color)) if *intensity > 0.0 => Some((*intensity, *color)),
+        _ => None,
+    }
+}
+
 pub struct UIMap<'a> {
     level: &'a Level,
     renderables: &'a Vec<RenderData>,
@@ -121,45 +149,100 @@ impl<'a> UIMap<'a> {
         }
     }
 
+    // lit tiles take on the colour of the light falling on them, fading with its intensity.
+    fn get_lit_colors(&self, idx: usize, fg: RGB, bg: RGB) -> (RGB, RGB) {
+        match get_light_at_idx(self.level, idx) {
+            Some((intensity, color)) => (
+                blend_colors(fg, color, intensity * LIGHT_FOREGROUND_STRENGTH),
+                blend_colors(bg, color, intensity * LIGHT_BACKGROUND_STRENGTH),
+            ),
+            None => (fg, bg),
+        }
+    }
+
+    // fire and smoke are only seen while they're happening, they're never remembered.
+    fn get_fire_and_smoke_colors(&self, idx: usize, fg: RGB, bg: RGB) -> (RGB, RGB) {
+        let bg = match self.level.fire.get(idx) {
+            Some(fire) if *fire > 0 => {
+                blend_colors(bg, RGB::named(rltk::ORANGE_RED), FIRE_BACKGROUND_STRENGTH)
+            }
+            _ => bg,
+        };
+        match self.level.smoke.get(idx) {
+            Some(smoke) if *smoke > 0 => {
+                let amount = f32::min(*smoke as f32 * SMOKE_STRENGTH_PER_DENSITY, 1.0);
+                (
+                    blend_colors(fg, RGB::named(rltk::GRAY), amount),
+                    blend_colors(bg, RGB::named(rltk::GRAY), amount),
+                )
+            }
+            _ => (fg, bg),
+        }
+    }
+
     pub fn draw(&mut self, ctx: &mut Rltk) {
         // this could be better, the level knows what ents are there and we can get the renderables from there
         // why bother collecting them beforehand?
-        for (i, tile) in self.level.tiles.iter().enumerate() {
-            if self.level.revealed_tiles[i] {
-                let x = i % MAP_WIDTH as usize;
-                let y = (i - (i % MAP_WIDTH as usize)) / MAP_WIDTH as usize;
+        // only the part of the level under the map view is drawn, the level can be any size.
+        for screen_y in 0..MAP_HEIGHT as i32 {
+            for screen_x in 0..MAP_WIDTH as i32 {
+                let x = screen_x + self.render_offset.0;
+                let y = screen_y + self.render_offset.1;
+                let i = match level_utils::get_idx_at_xy(self.level, x, y) {
+                    Some(i) if self.level.revealed_tiles[i] => i,
+                    _ => continue,
+                };
+                let tile = &self.level.tiles[i];
                 let character = match tile {
+                    TileType::Door if secrets::idx_is_secret(&self.level, i) => {
+                        get_wall_tile(&self.level, x, y)
+                    }
                     TileType::Floor | TileType::Door => rltk::to_cp437('.'),
-                    TileType::Wall => get_wall_tile(&self.level, x as i32, y as i32),
+                    TileType::Wall => get_wall_tile(&self.level, x, y),
                     TileType::Column => 9,
                     TileType::DownStairs => rltk::to_cp437('>'),
                     TileType::UpStairs => rltk::to_cp437('<'),
                     TileType::Exit => 219,
                     TileType::WaterDeep => 176,
-                    TileType::Ledge => get_ledge_tile(&self.level, x as i32, y as i32),
+                    TileType::Ledge => get_ledge_tile(&self.level, x, y),
                 };
-                let foreground_color = match self.visible_tiles.contains(&i) {
-                    true => rltk::GREEN,
-                    false => rltk::WHITE,
+                let is_visible = self.visible_tiles.contains(&i);
+                let bg = get_bg_color(self.level, i, tile);
+                let (foreground_color, background_color) = match is_visible {
+                    true => {
+                        let (fg, bg) =
+                            self.get_lit_colors(i, get_fg_color(self.level, i, tile), bg);
+                        self.get_fire_and_smoke_colors(i, fg, bg)
+                    }
+                    false => (RGB::named(rltk::WHITE), bg),
                 };
                 ctx.set(
-                    x as i32 - self.render_offset.0,
-                    y as i32 - self.render_offset.1,
-                    RGB::named(foreground_color),
-                    get_bg_color(tile),
+                    screen_x,
+                    screen_y,
+                    foreground_color,
+                    background_color,
                     character,
                 )
             }
         }
         for r in self.renderables.iter() {
             let (x, y) = level_utils::idx_xy(self.level.width as u32, r.idx);
-            ctx.set(
-                x - self.render_offset.0,
-                y - self.render_offset.1,
-                r.fg,
-                r.bg,
-                r.glyph,
-            );
+            let (screen_x, screen_y) = (x - self.render_offset.0, y - self.render_offset.1);
+            if screen_x < 0
+                || screen_y < 0
+                || screen_x >= MAP_WIDTH as i32
+                || screen_y >= MAP_HEIGHT as i32
+            {
+                continue;
+            }
+            let background_color = match self.visible_tiles.contains(&r.idx) {
+                true => {
+                    let bg = self.get_lit_colors(r.idx, r.fg, r.bg).1;
+                    self.get_fire_and_smoke_colors(r.idx, r.fg, bg).1
+                }
+                false => r.bg,
+            };
+            ctx.set(screen_x, screen_y, r.fg, background_color, r.glyph);
         }
     }
 }
@@ -18,6 +18,7 @@ pub struct UIMapScreen<'a, 'b> {
     depth: u8,
     hp: i32,
     max_hp: i32,
+    light_level: f32,
     level: &'a Level,
     renderables: &'a Vec<RenderData>,
     render_offset: (i32, i32),
@@ -33,6 +34,7 @@ impl<'a, 'b> UIMapScreen<'a, 'b> {
         depth: u8,
         hp: i32,
         max_hp: i32,
+        light_level: f32,
         level: &'a Level,
         renderables: &'a Vec<RenderData>,
         render_offset: (i32, i32),
@@ -46,6 +48,7 @@ impl<'a, 'b> UIMapScreen<'a, 'b> {
             depth,
             hp,
             max_hp,
+            light_level,
             level,
             renderables,
             render_offset,
@@ -55,7 +58,14 @@ impl<'a, 'b> UIMapScreen<'a, 'b> {
 
     pub fn draw(&self, ctx: &mut Rltk) {
         UIMap::new(self.level, self.renderables, self.render_offset, self.visible_tiles).draw(ctx);
-        UIHud::new(self.depth, self.hp, self.max_hp, self.messages).draw(ctx);
+        UIHud::new(
+            self.depth,
+            self.hp,
+            self.max_hp,
+            self.light_level,
+            self.messages,
+        )
+        .draw(ctx);
         if !self.tool_tip_lines.is_empty() {
             let tool_tip_pos = match self.mouse_x > (SCREEN_WIDTH / 2) as i32 {
                 true => UIToolTipPosition::Left,
@@ -3,6 +3,7 @@ use super::{
     ui::ui_map::RenderData,
 };
 use crate::components::{Hidden, Hiding, OnFire, Position, Renderable, Viewshed};
+use crate::dungeon::{level::Level, level_utils};
 use rltk::{GREY, ORANGE, RGB};
 use specs::{Entity, Join, World, WorldExt};
 
@@ -67,9 +68,15 @@ pub fn get_render_offset(center_x: i32, center_y: i32) -> (i32, i32) {
     (offset_x, offset_y)
 }
 
-pub fn get_render_offset_for_xy(center_x: i32, center_y: i32, x: i32, y: i32) -> (i32, i32) {
+// the level tile under a spot on the screen, if there is one. the view can reach past the edges
+// of a level that's smaller than it, or that the player is standing near the side of.
+pub fn get_idx_for_screen_xy(
+    level: &Level,
+    center_x: i32,
+    center_y: i32,
+    x: i32,
+    y: i32,
+) -> Option<usize> {
     let (center_offset_x, center_offset_y) = get_render_offset(center_x, center_y);
-    let offset_x = x + center_offset_x;
-    let offset_y = y + center_offset_y;
-    (offset_x, offset_y)
+    level_utils::get_idx_at_xy(level, x + center_offset_x, y + center_offset_y)
 }
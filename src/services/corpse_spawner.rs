@@ -61,4 +61,22 @@ impl CorpseSpawner {
             items,
         );
     }
+
+    pub fn request_duergar_corpse(
+        &mut self,
+        idx: usize,
+        level: u8,
+        cause_of_death: String,
+        items: EntitySet,
+    ) {
+        self.request(
+            idx,
+            RGB::named(BLACK),
+            RGB::named(DARK_RED),
+            to_cp437('h'),
+            level,
+            format!("duergar corpse, {}", cause_of_death),
+            items,
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use rltk::{BLACK, DARK_GRAY, RGB, to_cp437};
+use rltk::{BLACK, DARK_GRAY, GREY, RGB, to_cp437};
 
 pub struct DebrisSpawnerRequest {
     pub idx: usize,
@@ -44,4 +44,16 @@ impl DebrisSpawner {
             false
         )
     }
+
+    pub fn request_rubble(&mut self, idx: usize, level: u8) {
+        self.request(
+            idx,
+            RGB::named(GREY),
+            RGB::named(BLACK),
+            to_cp437(','),
+            level,
+            String::from("Rubble"),
+            false
+        )
+    }
 }
@@ -0,0 +1,27 @@
+pub struct ExplosionSpawnerRequest {
+    pub idx: usize,
+    pub level: u8,
+    pub radius: u32,
+    pub damage: i32,
+}
+
+pub struct ExplosionSpawner {
+    pub requests: Vec<ExplosionSpawnerRequest>,
+}
+
+impl ExplosionSpawner {
+    pub fn new() -> Self {
+        ExplosionSpawner {
+            requests: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, idx: usize, level: u8, radius: u32, damage: i32) {
+        self.requests.push(ExplosionSpawnerRequest {
+            idx,
+            level,
+            radius,
+            damage,
+        })
+    }
+}
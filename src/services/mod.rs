@@ -1,15 +1,21 @@
-pub mod blood_spawner;
 pub mod debris_spawner;
 pub mod game_log;
 pub mod item_spawner;
 pub mod particle_effect_spawner;
 pub mod trap_spawner;
 pub mod corpse_spawner;
+pub mod stack_spawner;
+pub mod surface_spawner;
+pub mod terrain_damage;
+pub mod explosion_spawner;
 
-pub use blood_spawner::BloodSpawner;
 pub use debris_spawner::DebrisSpawner;
 pub use game_log::GameLog;
 pub use item_spawner::ItemSpawner;
 pub use particle_effect_spawner::ParticleEffectSpawner;
 pub use trap_spawner::TrapSpawner;
 pub use corpse_spawner::CorpseSpawner;
+pub use stack_spawner::StackSpawner;
+pub use surface_spawner::SurfaceSpawner;
+pub use terrain_damage::TerrainDamage;
+pub use explosion_spawner::ExplosionSpawner;
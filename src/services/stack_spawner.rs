@@ -0,0 +1,39 @@
+use specs::Entity;
+
+// where an item split off a stack ends up, on the floor or in someone's inventory.
+pub enum StackDestination {
+    Floor { idx: usize, level: u8 },
+    Inventory(Entity),
+}
+
+pub struct StackSpawnerRequest {
+    pub destination: StackDestination,
+    pub source: Entity,
+}
+
+pub struct StackSpawner {
+    pub requests: Vec<StackSpawnerRequest>,
+}
+
+impl StackSpawner {
+    pub fn new() -> Self {
+        StackSpawner {
+            requests: Vec::new(),
+        }
+    }
+
+    // spawns a single item split off from the source stack
+    pub fn request(&mut self, idx: usize, level: u8, source: Entity) {
+        self.requests.push(StackSpawnerRequest {
+            destination: StackDestination::Floor { idx, level },
+            source,
+        })
+    }
+
+    pub fn request_into_inventory(&mut self, owner: Entity, source: Entity) {
+        self.requests.push(StackSpawnerRequest {
+            destination: StackDestination::Inventory(owner),
+            source,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use crate::dungeon::surface_type::SurfaceType;
+
+pub struct SurfaceSpawnerRequest {
+    pub idx: usize,
+    pub level: u8,
+    pub surface_type: SurfaceType,
+}
+
+pub struct SurfaceSpawner {
+    pub requests: Vec<SurfaceSpawnerRequest>,
+}
+
+impl SurfaceSpawner {
+    pub fn new() -> Self {
+        SurfaceSpawner {
+            requests: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, idx: usize, level: u8, surface_type: SurfaceType) {
+        self.requests.push(SurfaceSpawnerRequest {
+            idx,
+            level,
+            surface_type,
+        })
+    }
+}
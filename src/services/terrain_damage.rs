@@ -0,0 +1,21 @@
+pub struct TerrainDamageRequest {
+    pub idx: usize,
+    pub level: u8,
+    pub amount: i32,
+}
+
+pub struct TerrainDamage {
+    pub requests: Vec<TerrainDamageRequest>,
+}
+
+impl TerrainDamage {
+    pub fn new() -> Self {
+        TerrainDamage {
+            requests: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, idx: usize, level: u8, amount: i32) {
+        self.requests.push(TerrainDamageRequest { idx, level, amount })
+    }
+}
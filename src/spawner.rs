@@ -1,24 +1,37 @@
 use crate::components::{
-    causes_damage::DamageType, door::DoorState, equipable::EquipmentPositions,
-    monster::MonsterSpecies, Armable, DamageHistory, Disarmable, Door, Inventory, Lightable,
+    ammunition::AmmunitionType, causes_damage::DamageType, door::DoorState,
+    equipable::EquipmentPositions, monster::MonsterSpecies, Ammunition, Armable, DamageHistory,
+    Disarmable, Door, Inventory, Key, Launcher, Lightable, Mechanism, WiredTo,
 };
 use crate::components::{
     AreaOfEffect, BlocksTile, CausesDamage, CausesFire, CausesLight, CombatStats, Confusion,
-    Consumable, Container, Dousable, EntryTrigger, Equipable, Equipment, Flammable, Furniture,
-    Grabbable, Hidden, HidingSpot, Info, Item, Memory, Monster, Name, Objective, Player, Position,
-    ProvidesHealing, Ranged, Renderable, Saveable, SingleActivation, Trap, Viewshed,
+    Consumable, Container, Dousable, EntryTrigger, Equipable, Equipment, Explosive, Flammable,
+    Fuel, Furniture, Grabbable, Hidden, HidingSpot, Identifies, Info, Item, Memory, Monster, Name,
+    Objective, Player, Position, Potion, ProvidesBlock, ProvidesHealing, Ranged, Refillable,
+    Renderable, Saveable, SingleActivation, Stackable, Trap, TwoHanded, Viewshed, Weight,
+    Workstation,
 };
+use crate::crafting::CraftedItem;
 use crate::dungeon::{
-    constants::MAP_HEIGHT,
+    invasion::{Invasion, InvasionSpawn},
     level::Level,
     level_utils,
+    locks::KeyPlacement,
+    prefabs::PrefabSpawn,
     rect::Rect,
     room::Room,
     room_decorators::{RoomPart, RoomType},
+    secrets,
+    surface_type::SurfaceType,
     tile_type::TileType,
 };
 use crate::entity_set::EntitySet;
-use crate::types::{trap_type, TrapType};
+use crate::inventory::{get_matching_stack, get_stack_quantity};
+use crate::screens::constants::MAP_HEIGHT;
+use crate::types::{
+    item_type, mechanism_type, trap_type, ItemType, Material, MechanismType, TrapType,
+    WorkstationType,
+};
 use crate::utils;
 use rltk::{to_cp437, RandomNumberGenerator, RGB};
 use specs::{
@@ -36,6 +49,38 @@ pub const MAX_GOBLIN_GROUPS_PER_LEVEL: i32 = 4;
 pub const MIN_GOBLINS_PER_GROUP: i32 = 3;
 pub const MAX_GOBLINS_PER_GROUP: i32 = 6;
 pub const MAX_GOBLIN_SPACING: i32 = 4;
+pub const MIN_DUERGAR_GROUPS_PER_LEVEL: i32 = 2;
+pub const MAX_DUERGAR_GROUPS_PER_LEVEL: i32 = 3;
+pub const MIN_DUERGAR_PER_GROUP: i32 = 2;
+pub const MAX_DUERGAR_PER_GROUP: i32 = 4;
+pub const TORCH_FUEL_TURNS: u32 = 400;
+pub const MIN_SCONCE_FUEL_TURNS: i32 = 200;
+pub const MAX_SCONCE_FUEL_TURNS: i32 = 800;
+pub const LANTERN_FUEL_CAPACITY: u32 = 600;
+pub const OIL_FUEL_TURNS: u32 = 300;
+pub const BOMB_RADIUS: u32 = 2;
+pub const BOMB_DAMAGE: i32 = 12;
+pub const POWDER_BARREL_RADIUS: u32 = 3;
+pub const POWDER_BARREL_DAMAGE: i32 = 16;
+// every kind of scroll looks the same, so its colour can't give away what an unread one is.
+const SCROLL_COLOR: (u8, u8, u8) = rltk::WHEAT;
+// one in this many barrels is packed with black powder.
+pub const POWDER_BARREL_CHANCE: i32 = 4;
+pub const SURFACE_ROOM_CHANCE: i32 = 4;
+pub const MAX_PUDDLES_PER_ROOM: i32 = 4;
+pub const MAX_PUDDLE_SIZE: u32 = 2;
+// one in this many rooms is rigged with a trap wired to something elsewhere in it.
+pub const LINKED_TRAP_ROOM_CHANCE: i32 = 8;
+pub const MIN_SECRET_ROOM_ITEMS: i32 = 2;
+pub const MAX_SECRET_ROOM_ITEMS: i32 = 4;
+const KEY_NAMES: [&str; 6] = [
+    "Iron Key",
+    "Brass Key",
+    "Bronze Key",
+    "Copper Key",
+    "Silver Key",
+    "Bone Key",
+];
 
 fn get_possible_spawn_points_in_level(level: &Level) -> Vec<usize> {
     level
@@ -81,7 +126,7 @@ fn create_marked_entity_with_position<'a>(
 ) -> EntityBuilder<'a> {
     create_marked_entity(world).with(Position {
         idx: position_idx,
-        level: level.depth,
+        level: level.id,
     })
 }
 
@@ -108,8 +153,30 @@ fn make_entity_weapon<'a>(
         })
 }
 
+fn make_entity_two_handed_weapon<'a>(
+    builder: EntityBuilder<'a>,
+    min: i32,
+    max: i32,
+    bonus: i32,
+    damage_type: Box<[DamageType]>,
+) -> EntityBuilder<'a> {
+    builder
+        .with(CausesDamage {
+            min,
+            max,
+            bonus,
+            damage_type,
+        })
+        .with(Item {})
+        .with(Equipable {
+            positions: Box::new([EquipmentPositions::DominantHand]),
+        })
+        .with(TwoHanded {})
+}
+
 fn make_entity_sword<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     make_entity_weapon(builder, 1, 6, 0, Box::new([DamageType::Slash, DamageType::Stab]))
+        .with(Weight { weight: 3.0 })
         .with(Name {
             name: "Sword".to_string(),
         })
@@ -126,6 +193,7 @@ fn make_entity_sword<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
 
 fn make_entity_club<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     make_entity_weapon(builder, 1, 4, 0, Box::new([DamageType::Blunt]))
+        .with(Weight { weight: 3.0 })
         .with(Name {
             name: "Club".to_string(),
         })
@@ -137,6 +205,273 @@ fn make_entity_club<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
         })
 }
 
+fn make_entity_key<'a>(builder: EntityBuilder<'a>, key_id: u32) -> EntityBuilder<'a> {
+    builder
+        .with(Item {})
+        .with(Key { key_id })
+        .with(Weight { weight: 0.1 })
+        .with(Name {
+            name: KEY_NAMES[key_id as usize % KEY_NAMES.len()].to_string(),
+        })
+        .with(Info {
+            description: String::from("A key. Somewhere on this floor is the door it opens."),
+        })
+        .with(Renderable {
+            glyph: to_cp437('⌐'),
+            fg: RGB::named(rltk::YELLOW),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn make_entity_greatsword<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_two_handed_weapon(
+        builder,
+        3,
+        10,
+        0,
+        Box::new([DamageType::Slash, DamageType::Hack]),
+    )
+    .with(Weight { weight: 6.0 })
+    .with(Name {
+        name: "Greatsword".to_string(),
+    })
+    .with(Info {
+        description: String::from(
+            "A Greatsword, far too heavy to swing with one hand. Whatever it hits stays hit.",
+        ),
+    })
+    .with(Renderable {
+        glyph: to_cp437('/'),
+        fg: RGB::named(rltk::SILVER),
+        bg: RGB::named(rltk::BLACK),
+        layer: 1,
+    })
+}
+
+fn make_entity_shield<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    builder
+        .with(Item {})
+        .with(Equipable {
+            positions: Box::new([EquipmentPositions::OffHand]),
+        })
+        .with(ProvidesBlock { chance: 25 })
+        .with(Weight { weight: 5.0 })
+        .with(Name {
+            name: "Wooden Shield".to_string(),
+        })
+        .with(Info {
+            description: String::from("A Wooden Shield, held in the off hand it will turn aside some of the blows aimed at you."),
+        })
+        .with(Renderable {
+            glyph: to_cp437(')'),
+            fg: RGB::named(rltk::BROWN3),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn make_entity_launcher<'a>(
+    builder: EntityBuilder<'a>,
+    ammunition_type: AmmunitionType,
+    range: u32,
+    accuracy: i32,
+    bonus: i32,
+) -> EntityBuilder<'a> {
+    builder
+        .with(Launcher {
+            ammunition_type,
+            range,
+            accuracy,
+            bonus,
+        })
+        .with(Item {})
+        .with(Equipable {
+            positions: Box::new([EquipmentPositions::DominantHand]),
+        })
+}
+
+fn make_entity_shortbow<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_launcher(builder, AmmunitionType::Arrow, 8, 80, 0)
+        .with(TwoHanded {})
+        .with(Weight { weight: 2.0 })
+        .with(Name {
+            name: "Shortbow".to_string(),
+        })
+        .with(Info {
+            description: String::from(
+                "A Shortbow, it takes both hands to draw and loose arrows at anything you can see.",
+            ),
+        })
+        .with(Renderable {
+            glyph: to_cp437('}'),
+            fg: RGB::named(rltk::BROWN3),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn make_entity_crossbow<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_launcher(builder, AmmunitionType::Bolt, 10, 90, 2)
+        .with(TwoHanded {})
+        .with(Weight { weight: 6.0 })
+        .with(Name {
+            name: "Crossbow".to_string(),
+        })
+        .with(Info {
+            description: String::from(
+                "A Crossbow, heavy and slow but it puts a bolt exactly where you point it.",
+            ),
+        })
+        .with(Renderable {
+            glyph: to_cp437('}'),
+            fg: RGB::named(rltk::GREY),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn make_entity_sling<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_launcher(builder, AmmunitionType::Stone, 6, 70, 0)
+        .with(Weight { weight: 0.5 })
+        .with(Name {
+            name: "Sling".to_string(),
+        })
+        .with(Info {
+            description: String::from(
+                "A Sling, a strip of leather for hurling stones. Leaves a hand free for a torch.",
+            ),
+        })
+        .with(Renderable {
+            glyph: to_cp437('}'),
+            fg: RGB::named(rltk::TAN),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn make_entity_ammunition<'a>(
+    builder: EntityBuilder<'a>,
+    ammunition_type: AmmunitionType,
+    quantity: u32,
+    min: i32,
+    max: i32,
+    damage_type: Box<[DamageType]>,
+) -> EntityBuilder<'a> {
+    builder
+        .with(Ammunition { ammunition_type })
+        .with(Stackable { quantity })
+        .with(CausesDamage {
+            min,
+            max,
+            bonus: 0,
+            damage_type,
+        })
+        .with(Item {})
+}
+
+fn make_entity_arrows<'a>(builder: EntityBuilder<'a>, quantity: u32) -> EntityBuilder<'a> {
+    make_entity_ammunition(
+        builder,
+        AmmunitionType::Arrow,
+        quantity,
+        1,
+        6,
+        Box::new([DamageType::Pierce]),
+    )
+    .with(Weight { weight: 0.1 })
+    .with(Name {
+        name: "Arrow".to_string(),
+    })
+    .with(Info {
+        description: String::from("An Arrow, fired from a bow."),
+    })
+    .with(Renderable {
+        glyph: to_cp437('↑'),
+        fg: RGB::named(rltk::BROWN3),
+        bg: RGB::named(rltk::BLACK),
+        layer: 1,
+    })
+}
+
+fn make_entity_bolts<'a>(builder: EntityBuilder<'a>, quantity: u32) -> EntityBuilder<'a> {
+    make_entity_ammunition(
+        builder,
+        AmmunitionType::Bolt,
+        quantity,
+        2,
+        6,
+        Box::new([DamageType::Pierce]),
+    )
+    .with(Weight { weight: 0.1 })
+    .with(Name {
+        name: "Bolt".to_string(),
+    })
+    .with(Info {
+        description: String::from("A Bolt, short and heavy, fired from a crossbow."),
+    })
+    .with(Renderable {
+        glyph: to_cp437('↑'),
+        fg: RGB::named(rltk::GREY),
+        bg: RGB::named(rltk::BLACK),
+        layer: 1,
+    })
+}
+
+fn make_entity_stones<'a>(builder: EntityBuilder<'a>, quantity: u32) -> EntityBuilder<'a> {
+    make_entity_ammunition(
+        builder,
+        AmmunitionType::Stone,
+        quantity,
+        1,
+        4,
+        Box::new([DamageType::Blunt]),
+    )
+    .with(Weight { weight: 0.2 })
+    .with(Name {
+        name: "Sling Stone".to_string(),
+    })
+    .with(Info {
+        description: String::from("A Sling Stone, smooth and round. Hurts more than you'd think."),
+    })
+    .with(Renderable {
+        glyph: to_cp437('•'),
+        fg: RGB::named(rltk::GREY),
+        bg: RGB::named(rltk::BLACK),
+        layer: 1,
+    })
+}
+
+fn make_entity_throwing_daggers<'a>(
+    builder: EntityBuilder<'a>,
+    quantity: u32,
+) -> EntityBuilder<'a> {
+    make_entity_weapon(builder, 1, 4, 0, Box::new([DamageType::Stab, DamageType::Slash]))
+        .with(Launcher {
+            ammunition_type: AmmunitionType::ThrowingDagger,
+            range: 6,
+            accuracy: 75,
+            bonus: 0,
+        })
+        .with(Ammunition {
+            ammunition_type: AmmunitionType::ThrowingDagger,
+        })
+        .with(Stackable { quantity })
+        .with(Weight { weight: 0.5 })
+        .with(Name {
+            name: "Throwing Dagger".to_string(),
+        })
+        .with(Info {
+            description: String::from("A Throwing Dagger, balanced for throwing. Equip it in your dominant hand and fire to throw one."),
+        })
+        .with(Renderable {
+            glyph: to_cp437('/'),
+            fg: RGB::named(rltk::GREY),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
 fn make_entity_torch<'a>(builder: EntityBuilder<'a>, lit: bool) -> EntityBuilder<'a> {
     builder
         .with(Item {})
@@ -148,9 +483,11 @@ fn make_entity_torch<'a>(builder: EntityBuilder<'a>, lit: bool) -> EntityBuilder
         })
         .with(CausesLight {
             radius: 5,
+            color: RGB::named(rltk::ORANGE),
             lit,
-            turns_remaining: None,
+            turns_remaining: Some(TORCH_FUEL_TURNS),
         })
+        .with(Weight { weight: 1.0 })
         .with(Name {
             name: "Torch".to_string(),
         })
@@ -165,6 +502,113 @@ fn make_entity_torch<'a>(builder: EntityBuilder<'a>, lit: bool) -> EntityBuilder
         })
 }
 
+fn make_entity_lantern<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    builder
+        .with(Item {})
+        .with(Equipable {
+            positions: Box::new([
+                EquipmentPositions::DominantHand,
+                EquipmentPositions::OffHand,
+            ]),
+        })
+        .with(CausesLight {
+            radius: 6,
+            color: RGB::named(rltk::LIGHT_YELLOW),
+            lit: false,
+            turns_remaining: Some(OIL_FUEL_TURNS),
+        })
+        .with(Refillable {
+            capacity: LANTERN_FUEL_CAPACITY,
+        })
+        .with(Lightable {})
+        .with(Weight { weight: 1.5 })
+        .with(Name {
+            name: "Lantern".to_string(),
+        })
+        .with(Info {
+            description: String::from(
+                "A Lantern, it burns brighter than a torch and can be refilled with oil.",
+            ),
+        })
+        .with(Renderable {
+            glyph: to_cp437('Ω'),
+            fg: RGB::named(rltk::GOLD),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn spawn_lantern_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_lantern(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_lantern(world: &mut World) -> Entity {
+    make_entity_lantern(create_marked_entity(world)).build()
+}
+
+fn make_entity_oil_flask<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    builder
+        .with(Item {})
+        .with(Stackable { quantity: 1 })
+        .with(Fuel {
+            turns: OIL_FUEL_TURNS,
+        })
+        .with(Weight { weight: 0.5 })
+        .with(Name {
+            name: "Flask of Oil".to_string(),
+        })
+        .with(Info {
+            description: String::from(
+                "A Flask of Oil, refill a lantern you're holding with it or throw it to spill a slick.",
+            ),
+        })
+        .with(Renderable {
+            glyph: to_cp437('!'),
+            fg: RGB::named(rltk::GOLD),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn spawn_oil_flask_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_oil_flask(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_oil_flask(world: &mut World) -> Entity {
+    make_entity_oil_flask(create_marked_entity(world)).build()
+}
+
+fn make_entity_bomb<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    builder
+        .with(Item {})
+        .with(Stackable { quantity: 1 })
+        .with(Explosive {
+            radius: BOMB_RADIUS,
+            damage: BOMB_DAMAGE,
+        })
+        .with(Weight { weight: 1.0 })
+        .with(Name {
+            name: "Bomb".to_string(),
+        })
+        .with(Info {
+            description: String::from("A Bomb, throw it and it goes off where it lands."),
+        })
+        .with(Renderable {
+            glyph: to_cp437('δ'),
+            fg: RGB::named(rltk::DARK_GRAY),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+}
+
+fn spawn_bomb_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_bomb(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_bomb(world: &mut World) -> Entity {
+    make_entity_bomb(create_marked_entity(world)).build()
+}
+
 fn spawn_sword_as_equipment(world: &mut World) -> Entity {
     make_entity_sword(create_marked_entity(world)).build()
 }
@@ -179,6 +623,78 @@ fn spawn_torch_as_equipment(world: &mut World) -> Entity {
         .build()
 }
 
+fn spawn_greatsword_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_greatsword(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_greatsword(world: &mut World) -> Entity {
+    make_entity_greatsword(create_marked_entity(world)).build()
+}
+
+fn spawn_shield_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_shield(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_shield(world: &mut World) -> Entity {
+    make_entity_shield(create_marked_entity(world)).build()
+}
+
+fn spawn_shortbow_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_shortbow(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_shortbow(world: &mut World) -> Entity {
+    make_entity_shortbow(create_marked_entity(world)).build()
+}
+
+fn spawn_crossbow_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_crossbow(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_crossbow(world: &mut World) -> Entity {
+    make_entity_crossbow(create_marked_entity(world)).build()
+}
+
+fn spawn_sling_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_sling(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_sling(world: &mut World) -> Entity {
+    make_entity_sling(create_marked_entity(world)).build()
+}
+
+fn spawn_arrows_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_arrows(create_marked_entity_with_position(world, idx, level), 12).build()
+}
+
+fn spawn_arrows(world: &mut World, quantity: u32) -> Entity {
+    make_entity_arrows(create_marked_entity(world), quantity).build()
+}
+
+fn spawn_bolts_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_bolts(create_marked_entity_with_position(world, idx, level), 8).build()
+}
+
+fn spawn_bolts(world: &mut World, quantity: u32) -> Entity {
+    make_entity_bolts(create_marked_entity(world), quantity).build()
+}
+
+fn spawn_stones_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_stones(create_marked_entity_with_position(world, idx, level), 10).build()
+}
+
+fn spawn_stones(world: &mut World, quantity: u32) -> Entity {
+    make_entity_stones(create_marked_entity(world), quantity).build()
+}
+
+fn spawn_throwing_daggers_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_throwing_daggers(create_marked_entity_with_position(world, idx, level), 3).build()
+}
+
+fn spawn_throwing_daggers(world: &mut World, quantity: u32) -> Entity {
+    make_entity_throwing_daggers(create_marked_entity(world), quantity).build()
+}
+
 pub fn spawn_player(world: &mut World, idx: usize, level: &Level) -> Entity {
     let sword = spawn_sword_as_equipment(world);
     let torch = spawn_torch_as_equipment(world);
@@ -218,16 +734,22 @@ pub fn spawn_player(world: &mut World, idx: usize, level: &Level) -> Entity {
         .build()
 }
 
-pub fn spawn_monster<S: ToString>(
+// what a monster is spawned with, held in either hand and carried in its pack.
+struct Loadout {
+    dominant_hand: Option<Entity>,
+    off_hand: Option<Entity>,
+    items: EntitySet,
+}
+
+fn spawn_monster_with_equipment<S: ToString>(
     world: &mut World,
     idx: usize,
     glyph: u16,
     name: S,
     level: &Level,
     species: MonsterSpecies,
+    loadout: Loadout,
 ) -> Entity {
-    let club = spawn_club_as_equipment(world);
-    let torch = spawn_torch_as_equipment(world);
     create_marked_entity_with_position(world, idx, level)
         .with(Renderable {
             glyph,
@@ -253,8 +775,8 @@ pub fn spawn_monster<S: ToString>(
             power: 1,
         })
         .with(Equipment {
-            dominant_hand: Some(club),
-            off_hand: Some(torch),
+            dominant_hand: loadout.dominant_hand,
+            off_hand: loadout.off_hand,
         })
         .with(Memory {
             last_known_enemy_positions: HashMap::new(),
@@ -262,7 +784,7 @@ pub fn spawn_monster<S: ToString>(
             wander_destination: None,
         })
         .with(Inventory {
-            items: EntitySet::new(),
+            items: loadout.items,
         })
         .with(DamageHistory {
             events: HashSet::new(),
@@ -270,6 +792,31 @@ pub fn spawn_monster<S: ToString>(
         .build()
 }
 
+pub fn spawn_monster<S: ToString>(
+    world: &mut World,
+    idx: usize,
+    glyph: u16,
+    name: S,
+    level: &Level,
+    species: MonsterSpecies,
+) -> Entity {
+    let club = spawn_club_as_equipment(world);
+    let torch = spawn_torch_as_equipment(world);
+    spawn_monster_with_equipment(
+        world,
+        idx,
+        glyph,
+        name,
+        level,
+        species,
+        Loadout {
+            dominant_hand: Some(club),
+            off_hand: Some(torch),
+            items: EntitySet::new(),
+        },
+    )
+}
+
 fn spawn_objective(world: &mut World, idx: usize, level: &Level) -> Entity {
     create_marked_entity_with_position(world, idx, level)
         .with(Name {
@@ -297,8 +844,102 @@ pub fn spawn_goblin(world: &mut World, idx: usize, level: &Level) -> Entity {
     )
 }
 
+pub fn spawn_goblin_archer(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let shortbow = spawn_shortbow(world);
+    let mut items = EntitySet::new();
+    items.insert(spawn_arrows(world, 8));
+    spawn_monster_with_equipment(
+        world,
+        idx,
+        to_cp437('g'),
+        "Goblin Archer",
+        level,
+        MonsterSpecies::Goblin,
+        Loadout {
+            dominant_hand: Some(shortbow),
+            off_hand: Some(shortbow),
+            items,
+        },
+    )
+}
+
+pub fn spawn_goblin_slinger(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let sling = spawn_sling(world);
+    let torch = spawn_torch_as_equipment(world);
+    let mut items = EntitySet::new();
+    items.insert(spawn_stones(world, 10));
+    spawn_monster_with_equipment(
+        world,
+        idx,
+        to_cp437('g'),
+        "Goblin Slinger",
+        level,
+        MonsterSpecies::Goblin,
+        Loadout {
+            dominant_hand: Some(sling),
+            off_hand: Some(torch),
+            items,
+        },
+    )
+}
+
+fn spawn_random_goblin(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let roll = get_random_from_world(world, 0, 6);
+    match roll {
+        0 => spawn_goblin_archer(world, idx, level),
+        1 => spawn_goblin_slinger(world, idx, level),
+        _ => spawn_goblin(world, idx, level),
+    }
+}
+
+pub fn spawn_duergar(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let club = spawn_club_as_equipment(world);
+    let shield = spawn_shield(world);
+    spawn_monster_with_equipment(
+        world,
+        idx,
+        to_cp437('h'),
+        "Duergar",
+        level,
+        MonsterSpecies::Duergar,
+        Loadout {
+            dominant_hand: Some(club),
+            off_hand: Some(shield),
+            items: EntitySet::new(),
+        },
+    )
+}
+
+pub fn spawn_duergar_crossbowman(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let crossbow = spawn_crossbow(world);
+    let mut items = EntitySet::new();
+    items.insert(spawn_bolts(world, 8));
+    spawn_monster_with_equipment(
+        world,
+        idx,
+        to_cp437('h'),
+        "Duergar Crossbowman",
+        level,
+        MonsterSpecies::Duergar,
+        Loadout {
+            dominant_hand: Some(crossbow),
+            off_hand: Some(crossbow),
+            items,
+        },
+    )
+}
+
+fn spawn_random_duergar(world: &mut World, idx: usize, level: &Level) -> Entity {
+    let roll = get_random_from_world(world, 0, 4);
+    match roll {
+        0 => spawn_duergar_crossbowman(world, idx, level),
+        _ => spawn_duergar(world, idx, level),
+    }
+}
+
 fn make_entity_health_potion<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 0.5 })
         .with(Name {
             name: "Health Potion".to_string(),
         })
@@ -310,6 +951,8 @@ fn make_entity_health_potion<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Potion { heal_amount: 8 })
         .with(ProvidesHealing { amount: 8 })
 }
 
@@ -323,17 +966,19 @@ fn spawn_health_potion(world: &mut World) -> Entity {
 
 fn make_entity_magic_missile_scroll<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 0.1 })
         .with(Name {
             name: "Scroll of Magic Missile".to_string(),
         })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(rltk::CYAN),
+            fg: RGB::named(SCROLL_COLOR),
             bg: RGB::named(rltk::BLACK),
             layer: 1,
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
         .with(Ranged { range: 6 })
         .with(CausesDamage {
             min: 4,
@@ -357,17 +1002,19 @@ fn spawn_magic_missile_scroll(world: &mut World) -> Entity {
 
 fn make_entity_fireball_scroll<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 0.1 })
         .with(Name {
             name: "Scroll of Fireball".to_string(),
         })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(rltk::ORANGE),
+            fg: RGB::named(SCROLL_COLOR),
             bg: RGB::named(rltk::BLACK),
             layer: 1,
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
         .with(Ranged { range: 6 })
         .with(CausesDamage {
             min: 10,
@@ -389,17 +1036,19 @@ fn spawn_fireball_scroll(world: &mut World) -> Entity {
 
 fn make_entity_confusion_scroll<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 0.1 })
         .with(Name {
             name: "Scroll of Confusion".to_string(),
         })
         .with(Renderable {
             glyph: to_cp437(')'),
-            fg: RGB::named(rltk::PINK),
+            fg: RGB::named(SCROLL_COLOR),
             bg: RGB::named(rltk::BLACK),
             layer: 1,
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
         .with(Ranged { range: 6 })
         .with(Confusion { turns: 4 })
 }
@@ -412,10 +1061,37 @@ fn spawn_confusion_scroll(world: &mut World) -> Entity {
     make_entity_confusion_scroll(create_marked_entity(world)).build()
 }
 
+fn make_entity_identify_scroll<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    builder
+        .with(Weight { weight: 0.1 })
+        .with(Name {
+            name: "Scroll of Identify".to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437(')'),
+            fg: RGB::named(SCROLL_COLOR),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .with(Item {})
+        .with(Consumable {})
+        .with(Stackable { quantity: 1 })
+        .with(Identifies {})
+}
+
+fn spawn_identify_scroll_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_identify_scroll(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_identify_scroll(world: &mut World) -> Entity {
+    make_entity_identify_scroll(create_marked_entity(world)).build()
+}
+
 fn make_entity_bear_trap<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 4.0 })
         .with(Name {
-            name: "Bear Trap".to_string(),
+            name: item_type::get_name_for_item(&ItemType::BearTrap),
         })
         .with(Renderable {
             glyph: to_cp437('^'),
@@ -425,6 +1101,7 @@ fn make_entity_bear_trap<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
         .with(Ranged { range: 1 })
         .with(Trap {
             trap_type: TrapType::BearTrap,
@@ -442,8 +1119,9 @@ fn spawn_bear_trap(world: &mut World) -> Entity {
 
 fn make_entity_caltrops<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
     builder
+        .with(Weight { weight: 1.0 })
         .with(Name {
-            name: "Caltrops".to_string(),
+            name: item_type::get_name_for_item(&ItemType::Caltrops),
         })
         .with(Renderable {
             glyph: to_cp437('%'),
@@ -453,6 +1131,7 @@ fn make_entity_caltrops<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
         })
         .with(Item {})
         .with(Consumable {})
+        .with(Stackable { quantity: 1 })
         .with(Ranged { range: 3 })
         .with(Trap {
             trap_type: TrapType::Caltrops,
@@ -468,6 +1147,122 @@ fn spawn_caltrops(world: &mut World) -> Entity {
     make_entity_caltrops(create_marked_entity(world)).build()
 }
 
+fn make_entity_crafting_material<'a>(
+    builder: EntityBuilder<'a>,
+    name: String,
+    description: String,
+    character: char,
+    fg: RGB,
+    weight: f32,
+) -> EntityBuilder<'a> {
+    builder
+        .with(Weight { weight })
+        .with(Name { name })
+        .with(Info { description })
+        .with(Renderable {
+            glyph: to_cp437(character),
+            fg,
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .with(Item {})
+        .with(Stackable { quantity: 1 })
+}
+
+fn make_entity_herbs<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_crafting_material(
+        builder,
+        "Herbs".to_string(),
+        "A bundle of dried Herbs. They could be brewed at a stove.".to_string(),
+        '"',
+        RGB::named(rltk::GREEN),
+        0.1,
+    )
+}
+
+fn spawn_herbs_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_herbs(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_herbs(world: &mut World) -> Entity {
+    make_entity_herbs(create_marked_entity(world)).build()
+}
+
+fn make_entity_empty_vial<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_crafting_material(
+        builder,
+        "Empty Vial".to_string(),
+        "An Empty Vial, waiting to be filled with a potion.".to_string(),
+        '!',
+        RGB::named(rltk::WHITE),
+        0.3,
+    )
+}
+
+fn spawn_empty_vial_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_empty_vial(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_empty_vial(world: &mut World) -> Entity {
+    make_entity_empty_vial(create_marked_entity(world)).build()
+}
+
+fn make_entity_blank_scroll<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_crafting_material(
+        builder,
+        "Blank Scroll".to_string(),
+        "A Blank Scroll. A spell could be written on it at a desk.".to_string(),
+        ')',
+        RGB::named(rltk::WHITE),
+        0.1,
+    )
+}
+
+fn spawn_blank_scroll_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_blank_scroll(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_blank_scroll(world: &mut World) -> Entity {
+    make_entity_blank_scroll(create_marked_entity(world)).build()
+}
+
+fn make_entity_scrap_metal<'a>(builder: EntityBuilder<'a>) -> EntityBuilder<'a> {
+    make_entity_crafting_material(
+        builder,
+        "Scrap Metal".to_string(),
+        "A piece of Scrap Metal. It could be worked into traps and weapons.".to_string(),
+        '*',
+        RGB::named(rltk::GREY),
+        1.0,
+    )
+}
+
+fn spawn_scrap_metal_with_position(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_scrap_metal(create_marked_entity_with_position(world, idx, level)).build()
+}
+
+fn spawn_scrap_metal(world: &mut World) -> Entity {
+    make_entity_scrap_metal(create_marked_entity(world)).build()
+}
+
+pub fn spawn_crafted_item(world: &mut World, crafted_item: &CraftedItem, quantity: u32) -> Entity {
+    let item = match crafted_item {
+        CraftedItem::HealthPotion => spawn_health_potion(world),
+        CraftedItem::MagicMissileScroll => spawn_magic_missile_scroll(world),
+        CraftedItem::ConfusionScroll => spawn_confusion_scroll(world),
+        CraftedItem::IdentifyScroll => spawn_identify_scroll(world),
+        CraftedItem::Trap(ItemType::BearTrap) => spawn_bear_trap(world),
+        CraftedItem::Trap(ItemType::Caltrops) => spawn_caltrops(world),
+        CraftedItem::Arrows => spawn_arrows(world, quantity),
+        CraftedItem::ThrowingDaggers => spawn_throwing_daggers(world, quantity),
+        CraftedItem::Bomb => spawn_bomb(world),
+    };
+    if let Some(stack) = world.write_storage::<Stackable>().get_mut(item) {
+        stack.quantity = quantity;
+    }
+    item
+}
+
 fn make_entity_set_trap<'a>(
     builder: EntityBuilder<'a>,
     type_of_trap: &TrapType,
@@ -503,17 +1298,22 @@ fn make_entity_furniture<'a>(
     name: String,
     character: char,
     fg: RGB,
+    weight: f32,
 ) -> EntityBuilder<'a> {
     builder
         .with(Furniture {})
         .with(Name { name })
+        .with(Weight { weight })
         .with(Renderable {
             glyph: to_cp437(character),
             fg,
             bg: RGB::named(rltk::BLACK),
             layer: 1,
         })
-        .with(Flammable { turns_remaining: 8 })
+        .with(Flammable {
+            turns_remaining: 8,
+            material: Material::Wood,
+        })
         .with(BlocksTile {})
         .with(Grabbable {})
         .with(CombatStats {
@@ -524,9 +1324,12 @@ fn make_entity_furniture<'a>(
         })
 }
 fn spawn_sconce(world: &mut World, idx: usize, level: &Level) {
-    let lit = {
+    let (lit, fuel) = {
         let mut rng = world.write_resource::<RandomNumberGenerator>();
-        rng.range(0, 2) == 1
+        (
+            rng.range(0, 2) == 1,
+            rng.range(MIN_SCONCE_FUEL_TURNS, MAX_SCONCE_FUEL_TURNS) as u32,
+        )
     };
     let sconce = create_marked_entity_with_position(world, idx, level)
         .with(Name {
@@ -540,8 +1343,9 @@ fn spawn_sconce(world: &mut World, idx: usize, level: &Level) {
         })
         .with(CausesLight {
             radius: 5,
+            color: RGB::named(rltk::LIGHT_SALMON),
             lit,
-            turns_remaining: None,
+            turns_remaining: Some(fuel),
         });
     let sconce = match lit {
         true => sconce.with(Dousable {}),
@@ -576,25 +1380,59 @@ fn spawn_set_traps(world: &mut World, idx: usize, level: &Level) {
 }
 
 fn spawn_random_item_with_position(world: &mut World, idx: usize, level: &Level) {
-    let roll = get_random_from_world(world, 0, 7);
+    let roll = get_random_from_world(world, 0, 24);
     match roll {
         1 | 2 => spawn_health_potion_with_position(world, idx, level),
         3 => spawn_fireball_scroll_with_position(world, idx, level),
         4 => spawn_confusion_scroll_with_position(world, idx, level),
         5 => spawn_bear_trap_with_position(world, idx, level),
         6 => spawn_caltrops_with_position(world, idx, level),
+        7 => spawn_shield_with_position(world, idx, level),
+        8 => spawn_greatsword_with_position(world, idx, level),
+        9 => spawn_shortbow_with_position(world, idx, level),
+        10 => spawn_crossbow_with_position(world, idx, level),
+        11 => spawn_sling_with_position(world, idx, level),
+        12 => spawn_arrows_with_position(world, idx, level),
+        13 => spawn_bolts_with_position(world, idx, level),
+        14 => spawn_stones_with_position(world, idx, level),
+        15 => spawn_throwing_daggers_with_position(world, idx, level),
+        16 => spawn_identify_scroll_with_position(world, idx, level),
+        17 => spawn_herbs_with_position(world, idx, level),
+        18 => spawn_empty_vial_with_position(world, idx, level),
+        19 => spawn_blank_scroll_with_position(world, idx, level),
+        20 => spawn_scrap_metal_with_position(world, idx, level),
+        21 => spawn_lantern_with_position(world, idx, level),
+        22 => spawn_oil_flask_with_position(world, idx, level),
+        23 => spawn_bomb_with_position(world, idx, level),
         _ => spawn_magic_missile_scroll_with_position(world, idx, level),
     };
 }
 
 fn spawn_random_item(world: &mut World) -> Entity {
-    let roll = get_random_from_world(world, 0, 7);
+    let roll = get_random_from_world(world, 0, 24);
     match roll {
         1 | 2 => spawn_health_potion(world),
         3 => spawn_fireball_scroll(world),
         4 => spawn_confusion_scroll(world),
         5 => spawn_bear_trap(world),
         6 => spawn_caltrops(world),
+        7 => spawn_shield(world),
+        8 => spawn_greatsword(world),
+        9 => spawn_shortbow(world),
+        10 => spawn_crossbow(world),
+        11 => spawn_sling(world),
+        12 => spawn_arrows(world, 12),
+        13 => spawn_bolts(world, 8),
+        14 => spawn_stones(world, 10),
+        15 => spawn_throwing_daggers(world, 3),
+        16 => spawn_identify_scroll(world),
+        17 => spawn_herbs(world),
+        18 => spawn_empty_vial(world),
+        19 => spawn_blank_scroll(world),
+        20 => spawn_scrap_metal(world),
+        21 => spawn_lantern(world),
+        22 => spawn_oil_flask(world),
+        23 => spawn_bomb(world),
         _ => spawn_magic_missile_scroll(world),
     }
 }
@@ -613,6 +1451,29 @@ fn get_containers_in_room(world: &World, room: &Room, level_width: u32) -> Vec<E
         .collect()
 }
 
+// identical items placed in the same container are merged into a single stack.
+fn add_item_to_container(world: &mut World, item: Entity, container_ent: Entity) {
+    let mut containers = world.write_storage::<Container>();
+    let mut stackables = world.write_storage::<Stackable>();
+    let names = world.read_storage::<Name>();
+    let container = containers.get_mut(container_ent).unwrap();
+    match get_matching_stack(item, container.items.iter(), &stackables, &names) {
+        Some(stack) => {
+            let quantity = get_stack_quantity(item, &stackables);
+            if let Some(stack) = stackables.get_mut(stack) {
+                stack.quantity += quantity;
+            }
+            world
+                .entities()
+                .delete(item)
+                .expect("failed deleting merged stack");
+        }
+        None => {
+            container.items.insert(item);
+        }
+    }
+}
+
 pub fn spawn_item_entities_for_room(world: &mut World, room: &Room, level: &Level) {
     let containers_in_room = get_containers_in_room(world, room, level.width as u32);
     let min_items = match room.room_type {
@@ -642,9 +1503,7 @@ pub fn spawn_item_entities_for_room(world: &mut World, room: &Room, level: &Leve
                 rng.random_slice_entry(containers_in_room.as_slice())
             };
             if let Some(container_ent) = container_ent {
-                let mut containers = world.write_storage::<Container>();
-                let container = containers.get_mut(*container_ent).unwrap();
-                container.items.insert(item);
+                add_item_to_container(world, item, *container_ent);
             }
         }
     }
@@ -656,7 +1515,12 @@ pub fn spawn_bed(world: &mut World, idx: usize, level: &mut Level) {
         "Bed".to_string(),
         'b',
         RGB::named(rltk::LIGHT_BLUE),
+        40.0,
     )
+    .with(Flammable {
+        turns_remaining: 6,
+        material: Material::Cloth,
+    })
     .build();
     level.blocked[idx] = true;
 }
@@ -667,6 +1531,7 @@ pub fn spawn_bedside_table(world: &mut World, idx: usize, level: &mut Level) {
         "Bedside Table".to_string(),
         't',
         RGB::named(rltk::BROWN4),
+        10.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -678,6 +1543,7 @@ pub fn spawn_chair(world: &mut World, idx: usize, level: &mut Level) {
         "Chair".to_string(),
         'c',
         RGB::named(rltk::BROWN4),
+        8.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -689,7 +1555,11 @@ pub fn spawn_desk(world: &mut World, idx: usize, level: &mut Level) {
         "Desk".to_string(),
         'd',
         RGB::named(rltk::BROWN4),
+        30.0,
     )
+    .with(Workstation {
+        workstation_type: WorkstationType::Desk,
+    })
     .build();
     level.blocked[idx] = true;
 }
@@ -700,6 +1570,7 @@ pub fn spawn_armoire(world: &mut World, idx: usize, level: &mut Level) {
         "Armoire".to_string(),
         'a',
         RGB::named(rltk::BROWN4),
+        60.0,
     )
     .with(HidingSpot {})
     .build();
@@ -712,7 +1583,12 @@ pub fn spawn_towel_rack(world: &mut World, idx: usize, level: &mut Level) {
         "Towel Rack".to_string(),
         't',
         RGB::named(rltk::LIGHT_YELLOW),
+        5.0,
     )
+    .with(Flammable {
+        turns_remaining: 4,
+        material: Material::Cloth,
+    })
     .build();
     level.blocked[idx] = true;
 }
@@ -723,6 +1599,7 @@ pub fn spawn_throne(world: &mut World, idx: usize, level: &mut Level) {
         "Throne".to_string(),
         'T',
         RGB::named(rltk::LIGHT_YELLOW),
+        50.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -734,6 +1611,7 @@ pub fn spawn_podium(world: &mut World, idx: usize, level: &mut Level) {
         "Podium".to_string(),
         'P',
         RGB::named(rltk::LIGHT_YELLOW),
+        20.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -745,6 +1623,7 @@ pub fn spawn_dresser(world: &mut World, idx: usize, level: &mut Level) {
         "Dresser".to_string(),
         'd',
         RGB::named(rltk::BROWN3),
+        40.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -756,6 +1635,7 @@ pub fn spawn_shelf(world: &mut World, idx: usize, level: &mut Level) {
         "Shelf".to_string(),
         's',
         RGB::named(rltk::BROWN3),
+        15.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -767,6 +1647,7 @@ pub fn spawn_table(world: &mut World, idx: usize, level: &mut Level) {
         "Table".to_string(),
         't',
         RGB::named(rltk::BROWN3),
+        25.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -778,7 +1659,11 @@ pub fn spawn_counter(world: &mut World, idx: usize, level: &mut Level) {
         "Counter".to_string(),
         'C',
         RGB::named(rltk::BROWN3),
+        40.0,
     )
+    .with(Workstation {
+        workstation_type: WorkstationType::Counter,
+    })
     .build();
     level.blocked[idx] = true;
 }
@@ -789,7 +1674,11 @@ pub fn spawn_stove(world: &mut World, idx: usize, level: &mut Level) {
         "Stove".to_string(),
         'S',
         RGB::named(rltk::BROWN3),
+        80.0,
     )
+    .with(Workstation {
+        workstation_type: WorkstationType::Stove,
+    })
     .build();
     level.blocked[idx] = true;
 }
@@ -800,6 +1689,7 @@ pub fn spawn_cupboard(world: &mut World, idx: usize, level: &mut Level) {
         "Cupboard".to_string(),
         'c',
         RGB::named(rltk::BROWN3),
+        30.0,
     )
     .build();
     level.blocked[idx] = true;
@@ -810,17 +1700,42 @@ pub fn spawn_weapon_rack(world: &mut World, idx: usize, level: &mut Level) {
         "Weapon Rack".to_string(),
         'W',
         RGB::named(rltk::LIGHT_GREY),
+        15.0,
+    )
+    .with(Workstation {
+        workstation_type: WorkstationType::WeaponRack,
+    })
+    .build();
+    level.blocked[idx] = true;
+}
+
+fn spawn_powder_barrel(world: &mut World, idx: usize, level: &mut Level) {
+    make_entity_furniture(
+        create_marked_entity_with_position(world, idx, level),
+        "Powder Barrel".to_string(),
+        'B',
+        RGB::named(rltk::RED),
+        20.0,
     )
+    .with(Explosive {
+        radius: POWDER_BARREL_RADIUS,
+        damage: POWDER_BARREL_DAMAGE,
+    })
     .build();
     level.blocked[idx] = true;
 }
 
 pub fn spawn_barrel(world: &mut World, idx: usize, level: &mut Level) {
+    if get_random_from_world(world, 0, POWDER_BARREL_CHANCE) == 0 {
+        spawn_powder_barrel(world, idx, level);
+        return;
+    }
     make_entity_furniture(
         create_marked_entity_with_position(world, idx, level),
         "Barrel".to_string(),
         'B',
         RGB::named(rltk::YELLOW),
+        20.0,
     )
     .with(HidingSpot {})
     .build();
@@ -833,6 +1748,7 @@ pub fn spawn_treasure_chest(world: &mut World, idx: usize, level: &mut Level) {
         "Trasure Chest".to_string(),
         'T',
         RGB::named(rltk::BROWN3),
+        25.0,
     )
     .with(Container {
         items: EntitySet::new(),
@@ -857,27 +1773,488 @@ pub fn spawn_debris(world: &mut World, idx: usize, level: &mut Level) {
     level.blocked[idx] = true;
 }
 
-pub fn spawn_door(world: &mut World, idx: usize, level: &mut Level) {
+pub fn spawn_barricade(world: &mut World, idx: usize, level: &mut Level) {
+    make_entity_furniture(
+        create_marked_entity_with_position(world, idx, level),
+        "Barricade".to_string(),
+        '#',
+        RGB::named(rltk::BROWN4),
+        40.0,
+    )
+    .build();
+    level.blocked[idx] = true;
+}
+
+pub fn spawn_campfire(world: &mut World, idx: usize, level: &mut Level) {
     create_marked_entity_with_position(world, idx, level)
         .with(Name {
-            name: "Door".to_string(),
+            name: "Campfire".to_string(),
         })
         .with(Renderable {
-            glyph: to_cp437('▲'),
+            glyph: to_cp437('☼'),
+            fg: RGB::named(rltk::ORANGE),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .with(CausesLight {
+            radius: 6,
+            color: RGB::named(rltk::ORANGE),
+            lit: true,
+            turns_remaining: None,
+        })
+        .build();
+}
+
+pub fn spawn_pit(world: &mut World, idx: usize, level: &mut Level) {
+    create_marked_entity_with_position(world, idx, level)
+        .with(Name {
+            name: "Pit".to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437('▼'),
+            fg: RGB::named(rltk::GRAY),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .build();
+}
+
+// a trapdoor is found the same way as any other trap, but it can't be disarmed.
+pub fn spawn_trapdoor(world: &mut World, idx: usize, level: &mut Level) {
+    create_marked_entity_with_position(world, idx, level)
+        .with(Name {
+            name: "Trapdoor".to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437('▼'),
             fg: RGB::named(rltk::BROWN4),
             bg: RGB::named(rltk::BLACK),
             layer: 1,
         })
-        .with(Door {
-            state: DoorState::Closed,
+        .with(Hidden {
+            found_by: EntitySet::new(),
+        })
+        .build();
+}
+
+pub fn spawn_forge(world: &mut World, idx: usize, level: &mut Level) {
+    create_marked_entity_with_position(world, idx, level)
+        .with(Name {
+            name: "Forge".to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437('■'),
+            fg: RGB::named(rltk::ORANGE_RED),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .with(CausesLight {
+            radius: 4,
+            color: RGB::named(rltk::ORANGE_RED),
+            lit: true,
+            turns_remaining: None,
+        })
+        .with(BlocksTile {})
+        .build();
+    level.blocked[idx] = true;
+}
+
+// the dead left behind by a battle fought before the player got here.
+fn spawn_corpse(world: &mut World, idx: usize, level: &Level, name: &str, glyph: char) {
+    create_marked_entity_with_position(world, idx, level)
+        .with(Name {
+            name: format!("{} corpse, slain in battle", name),
+        })
+        .with(Renderable {
+            glyph: to_cp437(glyph),
+            fg: RGB::named(rltk::BLACK),
+            bg: RGB::named(rltk::DARK_RED),
+            layer: 2,
+        })
+        .with(Container {
+            items: EntitySet::new(),
         })
         .build();
+}
+
+pub fn spawn_door(world: &mut World, idx: usize, level: &mut Level) {
+    let state = match level.locked_doors.iter().find(|(i, _)| *i == idx) {
+        Some((_, key_id)) => DoorState::Locked(*key_id),
+        None => DoorState::Closed,
+    };
+    // secret doors are real doors, they just can't be seen or used until someone finds them.
+    let secret = secrets::idx_is_secret(level, idx);
+    let name = match secret {
+        true => "Secret Door",
+        false => "Door",
+    };
+    let mut builder = create_marked_entity_with_position(world, idx, level)
+        .with(Name {
+            name: name.to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437('▲'),
+            fg: RGB::named(rltk::BROWN4),
+            bg: RGB::named(rltk::BLACK),
+            layer: 1,
+        })
+        .with(Door { state })
+        .with(Flammable {
+            turns_remaining: 10,
+            material: Material::Wood,
+        });
+    if secret {
+        builder = builder.with(Hidden {
+            found_by: EntitySet::new(),
+        });
+    }
+    builder.build();
     level.blocked[idx] = true;
     level.opaque[idx] = true
 }
 
+fn get_surface_for_room(world: &mut World, room: &Room) -> SurfaceType {
+    match room.room_type {
+        Some(RoomType::Kitchen) => SurfaceType::Oil,
+        Some(RoomType::Baths) => SurfaceType::Water,
+        _ => match get_random_from_world(world, 0, 4) {
+            0 => SurfaceType::Water,
+            1 => SurfaceType::Oil,
+            2 => SurfaceType::Ice,
+            _ => SurfaceType::Blood,
+        },
+    }
+}
+
+// around one room in every few has something spilled across its floor.
+pub fn spawn_surfaces_for_room(world: &mut World, room: &Room, level: &mut Level) {
+    if get_random_from_world(world, 0, SURFACE_ROOM_CHANCE) != 0 {
+        return;
+    }
+    let surface_type = get_surface_for_room(world, room);
+    let num_puddles = get_random_from_world(world, 1, MAX_PUDDLES_PER_ROOM + 1);
+    let puddle_centers = {
+        let mut rng = world.write_resource::<RandomNumberGenerator>();
+        level_utils::get_spawn_points(&room.rect, level, &mut rng, num_puddles)
+    };
+    for center in puddle_centers {
+        let size = get_random_from_world(world, 0, MAX_PUDDLE_SIZE as i32 + 1) as u32;
+        for idx in level_utils::get_field_of_view_from_idx(level, center, size) {
+            if !level_utils::idx_not_in_map(level, idx) && level.tiles[idx] == TileType::Floor {
+                level.surfaces[idx] = Some(surface_type);
+            }
+        }
+    }
+}
+
+fn make_entity_wired_trigger<'a>(
+    builder: EntityBuilder<'a>,
+    name: &str,
+    character: char,
+    targets: Vec<Entity>,
+) -> EntityBuilder<'a> {
+    let mut wired_targets = EntitySet::with_capacity(targets.len());
+    for target in targets {
+        wired_targets.insert(target);
+    }
+    builder
+        .with(Name {
+            name: name.to_string(),
+        })
+        .with(Renderable {
+            glyph: to_cp437(character),
+            fg: RGB::named(rltk::RED),
+            bg: RGB::named(rltk::BLACK),
+            layer: 2,
+        })
+        .with(Hidden {
+            found_by: EntitySet::new(),
+        })
+        .with(EntryTrigger {})
+        .with(WiredTo {
+            targets: wired_targets,
+        })
+}
+
+fn spawn_pressure_plate(world: &mut World, idx: usize, level: &Level, targets: Vec<Entity>) {
+    make_entity_wired_trigger(
+        create_marked_entity_with_position(world, idx, level),
+        "Pressure Plate",
+        '_',
+        targets,
+    )
+    .build();
+}
+
+fn spawn_tripwire(world: &mut World, idx: usize, level: &Level, targets: Vec<Entity>) {
+    make_entity_wired_trigger(
+        create_marked_entity_with_position(world, idx, level),
+        "Tripwire",
+        '-',
+        targets,
+    )
+    .with(SingleActivation {})
+    .build();
+}
+
+fn make_entity_mechanism<'a>(
+    builder: EntityBuilder<'a>,
+    mechanism_type: MechanismType,
+) -> EntityBuilder<'a> {
+    builder
+        .with(Name {
+            name: mechanism_type::get_name_for_mechanism(&mechanism_type),
+        })
+        .with(Mechanism { mechanism_type })
+}
+
+fn spawn_dart_launcher(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_mechanism(
+        create_marked_entity_with_position(world, idx, level),
+        MechanismType::DartLauncher,
+    )
+    .with(Renderable {
+        glyph: to_cp437('o'),
+        fg: RGB::named(rltk::GREY),
+        bg: RGB::named(rltk::BLACK),
+        layer: 2,
+    })
+    .with(Hidden {
+        found_by: EntitySet::new(),
+    })
+    .build()
+}
+
+fn spawn_alarm_bell(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_mechanism(
+        create_marked_entity_with_position(world, idx, level),
+        MechanismType::AlarmBell,
+    )
+    .with(Renderable {
+        glyph: to_cp437('♪'),
+        fg: RGB::named(rltk::YELLOW),
+        bg: RGB::named(rltk::BLACK),
+        layer: 1,
+    })
+    .build()
+}
+
+fn spawn_collapsing_floor(world: &mut World, idx: usize, level: &Level) -> Entity {
+    make_entity_mechanism(
+        create_marked_entity_with_position(world, idx, level),
+        MechanismType::CollapsingFloor,
+    )
+    .with(Hidden {
+        found_by: EntitySet::new(),
+    })
+    .build()
+}
+
+// launchers are hidden in the walls, looking straight down a row or column at the plate.
+fn get_dart_launcher_idx(world: &mut World, level: &Level, plate_idx: usize) -> Option<usize> {
+    let (plate_x, plate_y) = level_utils::idx_xy(level.width as u32, plate_idx);
+    let candidates: Vec<usize> = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+        .iter()
+        .filter_map(|(delta_x, delta_y)| {
+            let (mut x, mut y) = (plate_x + delta_x, plate_y + delta_y);
+            while let Some(TileType::Floor) = level_utils::get_tile_at_xy(level, x, y) {
+                x += delta_x;
+                y += delta_y;
+            }
+            match level_utils::tile_at_xy_is_wall(level, x, y) {
+                true => Some(level_utils::xy_idx(level.width as u32, x, y)),
+                false => None,
+            }
+        })
+        .collect();
+    let mut rng = world.write_resource::<RandomNumberGenerator>();
+    rng.random_slice_entry(&candidates).cloned()
+}
+
+// some of the room's doors, never all of them, so a dropped gate can't seal the room off.
+fn get_portcullis_doors(world: &mut World, room: &Room, level: &Level) -> Vec<(Entity, usize)> {
+    let mut room_doors: Vec<(Entity, usize)> = {
+        let doors = world.read_storage::<Door>();
+        let positions = world.read_storage::<Position>();
+        let entities = world.entities();
+        (&entities, &doors, &positions)
+            .join()
+            .filter(|(_e, d, p)| {
+                // every level is spawned into the same world, only this one's doors are wanted.
+                if p.level != level.id {
+                    return false;
+                }
+                let (x, y) = level_utils::idx_xy(level.width as u32, p.idx);
+                // a locked door is already keeping people out.
+                let locked = match d.state {
+                    DoorState::Locked(_) => true,
+                    _ => false,
+                };
+                // and a hidden one would give the secret away.
+                let secret = secrets::idx_is_secret(level, p.idx);
+                room.rect.contains(x, y) && !locked && !secret
+            })
+            .map(|(e, _d, p)| (e, p.idx))
+            .collect()
+    };
+    if room_doors.len() < 2 {
+        return vec![];
+    }
+    let mut rng = world.write_resource::<RandomNumberGenerator>();
+    let count = rng.range(1, room_doors.len() as i32) as u32;
+    utils::get_x_random_elements(&mut rng, count, &mut room_doors)
+}
+
+// the floor just outside a door, where someone on their way into the room would step.
+fn get_idx_outside_door(level: &Level, room: &Room, door_idx: usize) -> Option<usize> {
+    let (door_x, door_y) = level_utils::idx_xy(level.width as u32, door_idx);
+    [(1, 0), (-1, 0), (0, 1), (0, -1)]
+        .iter()
+        .filter(|(delta_x, delta_y)| !room.rect.contains(door_x + delta_x, door_y + delta_y))
+        .filter_map(|(delta_x, delta_y)| {
+            level_utils::get_idx_at_xy(level, door_x + delta_x, door_y + delta_y)
+        })
+        .find(|idx| level.tiles[*idx] == TileType::Floor && !level.blocked[*idx])
+}
+
+// the doors are swapped for raised portcullises, ready to drop.
+fn convert_doors_to_portcullises(
+    world: &mut World,
+    room_doors: &[(Entity, usize)],
+    level: &mut Level,
+) -> Vec<Entity> {
+    let mut doors = world.write_storage::<Door>();
+    let mut names = world.write_storage::<Name>();
+    let mut renderables = world.write_storage::<Renderable>();
+    let mut flammables = world.write_storage::<Flammable>();
+    let mut mechanisms = world.write_storage::<Mechanism>();
+    for (door, idx) in room_doors.iter() {
+        doors
+            .insert(
+                *door,
+                Door {
+                    state: DoorState::Opened,
+                },
+            )
+            .expect("failed inserting door state for portcullis");
+        names
+            .insert(
+                *door,
+                Name {
+                    name: mechanism_type::get_name_for_mechanism(&MechanismType::Portcullis),
+                },
+            )
+            .expect("failed inserting name for portcullis");
+        mechanisms
+            .insert(
+                *door,
+                Mechanism {
+                    mechanism_type: MechanismType::Portcullis,
+                },
+            )
+            .expect("failed inserting mechanism for portcullis");
+        if let Some(renderable) = renderables.get_mut(*door) {
+            renderable.fg = RGB::named(rltk::DARK_GRAY);
+        }
+        flammables.remove(*door);
+        level_utils::set_tile_to_floor(level, *idx);
+        level.blocked[*idx] = false;
+        level.opaque[*idx] = false;
+    }
+    room_doors.iter().map(|(door, _idx)| *door).collect()
+}
+
+// some rooms hide a trigger wired to a mechanism in or around the room.
+pub fn spawn_linked_traps_for_room(world: &mut World, room: &Room, level: &mut Level) {
+    if get_random_from_world(world, 0, LINKED_TRAP_ROOM_CHANCE) != 0 {
+        return;
+    }
+    let mut spawn_points = level_utils::filter_water_from_tiles(
+        level_utils::get_walkable_tiles_in_rect(&room.rect, level),
+        level,
+    );
+    if spawn_points.len() < 2 {
+        return;
+    }
+    let (trigger_idx, other_idx) = {
+        let mut rng = world.write_resource::<RandomNumberGenerator>();
+        let points = utils::get_x_random_elements(&mut rng, 2, &mut spawn_points);
+        (points[0], points[1])
+    };
+    let max_roll = match level.has_floor_below {
+        true => 4,
+        false => 3,
+    };
+    match get_random_from_world(world, 0, max_roll) {
+        0 => {
+            if let Some(launcher_idx) = get_dart_launcher_idx(world, level, trigger_idx) {
+                let launcher = spawn_dart_launcher(world, launcher_idx, level);
+                spawn_pressure_plate(world, trigger_idx, level, vec![launcher]);
+            }
+        }
+        // the wire is strung outside, so the gates come down in front of whoever is on their way
+        // in rather than behind them. rooms holding the way on are left open.
+        1 => {
+            if level_utils::rect_has_stairs(level, &room.rect) {
+                return;
+            }
+            let room_doors = get_portcullis_doors(world, room, level);
+            let wire_idx = room_doors
+                .iter()
+                .find_map(|(_door, idx)| get_idx_outside_door(level, room, *idx));
+            if let Some(wire_idx) = wire_idx {
+                let portcullises = convert_doors_to_portcullises(world, &room_doors, level);
+                spawn_tripwire(world, wire_idx, level, portcullises);
+            }
+        }
+        2 => {
+            let bell = spawn_alarm_bell(world, other_idx, level);
+            spawn_tripwire(world, trigger_idx, level, vec![bell]);
+        }
+        _ => {
+            let floor = spawn_collapsing_floor(world, trigger_idx, level);
+            make_entity_wired_trigger(
+                create_marked_entity_with_position(world, trigger_idx, level),
+                "Pressure Plate",
+                '_',
+                vec![floor],
+            )
+            .with(SingleActivation {})
+            .build();
+        }
+    }
+}
+
+// goblins, items and traps that were drawn into a prefab room by hand.
+fn spawn_prefab_spawns_for_room(world: &mut World, room: &Room, level: &Level) {
+    for (idx, spawn) in room.prefab_spawns.iter() {
+        match spawn {
+            PrefabSpawn::Goblin => {
+                spawn_random_goblin(world, *idx, level);
+            }
+            PrefabSpawn::Item => spawn_random_item_with_position(world, *idx, level),
+            PrefabSpawn::Trap => spawn_set_traps(world, *idx, level),
+        }
+    }
+}
+
+// whoever finds their way into a secret room is rewarded for the trouble.
+fn spawn_secret_treasure_for_room(world: &mut World, room: &Room, level: &Level) {
+    let item_count = get_random_from_world(world, MIN_SECRET_ROOM_ITEMS, MAX_SECRET_ROOM_ITEMS + 1);
+    let spawn_points = {
+        let mut rng = world.write_resource::<RandomNumberGenerator>();
+        level_utils::get_spawn_points(&room.rect, level, &mut rng, item_count)
+    };
+    for idx in spawn_points.iter() {
+        spawn_random_item_with_position(world, *idx, level);
+    }
+}
+
 pub fn spawn_entities_for_room(world: &mut World, room: &Room, level: &mut Level) {
+    spawn_prefab_spawns_for_room(world, room, level);
     spawn_item_entities_for_room(world, room, level);
+    spawn_surfaces_for_room(world, room, level);
+    spawn_linked_traps_for_room(world, room, level);
 }
 
 pub fn spawn_entites_from_stamps(
@@ -918,31 +2295,127 @@ fn spawn_set_traps_for_level(world: &mut World, level: &mut Level) {
         .for_each(|idx| spawn_set_traps(world, *idx, level));
 }
 
-fn spawn_goblins_for_level(world: &mut World, level: &mut Level) {
-    get_random_spawn_points_for_level(
+fn spawn_monster_groups_for_level(
+    world: &mut World,
+    level: &mut Level,
+    groups: (i32, i32),
+    group_size: (i32, i32),
+    spawn_monster: fn(&mut World, usize, &Level) -> Entity,
+) {
+    get_random_spawn_points_for_level(world, level, groups.0, groups.1)
+        .iter()
+        .for_each(|idx| {
+            let mut possible_spawn_points_for_group =
+                level_utils::get_all_spawnable_tiles_in_radius(
+                    level,
+                    *idx,
+                    MAX_GOBLIN_SPACING as u32,
+                );
+            let monster_count = get_random_from_world(world, group_size.0, group_size.1);
+            let spawn_points = {
+                let mut rng = world.write_resource::<RandomNumberGenerator>();
+                utils::get_x_random_elements(
+                    &mut rng,
+                    monster_count as u32,
+                    &mut possible_spawn_points_for_group,
+                )
+            };
+            spawn_points.iter().for_each(|idx| {
+                spawn_monster(world, *idx, level);
+            });
+        });
+}
+
+fn spawn_goblins_for_level(world: &mut World, level: &mut Level, extra_groups: i32) {
+    spawn_monster_groups_for_level(
         world,
         level,
-        MIN_GOBLIN_GROUPS_PER_LEVEL,
-        MAX_GOBLIN_GROUPS_PER_LEVEL,
-    )
-    .iter()
-    .for_each(|idx| {
-        let mut possible_spawn_points_for_group =
-            level_utils::get_all_spawnable_tiles_in_radius(level, *idx, MAX_GOBLIN_SPACING as u32);
-        let goblin_count =
-            get_random_from_world(world, MIN_GOBLINS_PER_GROUP, MAX_GOBLINS_PER_GROUP);
-        let spawn_points = {
-            let mut rng = world.write_resource::<RandomNumberGenerator>();
-            utils::get_x_random_elements(
-                &mut rng,
-                goblin_count as u32,
-                &mut possible_spawn_points_for_group,
-            )
+        (
+            MIN_GOBLIN_GROUPS_PER_LEVEL + extra_groups,
+            MAX_GOBLIN_GROUPS_PER_LEVEL + extra_groups,
+        ),
+        (MIN_GOBLINS_PER_GROUP, MAX_GOBLINS_PER_GROUP),
+        spawn_random_goblin,
+    );
+}
+
+fn spawn_duergar_for_level(world: &mut World, level: &mut Level) {
+    spawn_monster_groups_for_level(
+        world,
+        level,
+        (MIN_DUERGAR_GROUPS_PER_LEVEL, MAX_DUERGAR_GROUPS_PER_LEVEL),
+        (MIN_DUERGAR_PER_GROUP, MAX_DUERGAR_PER_GROUP),
+        spawn_random_duergar,
+    );
+}
+
+// invaded floors are held by whoever took them, a goblin horde brings more goblins than usual.
+fn spawn_monsters_for_level(world: &mut World, level: &mut Level) {
+    match level.invasion {
+        None => spawn_goblins_for_level(world, level, 0),
+        Some(Invasion::Goblin) => spawn_goblins_for_level(world, level, 2),
+        Some(Invasion::Duergar) => spawn_duergar_for_level(world, level),
+        Some(Invasion::Both) => {
+            spawn_goblins_for_level(world, level, 0);
+            spawn_duergar_for_level(world, level);
+        }
+    }
+}
+
+// keys go in the chest they were planned for, or in the pocket of a goblin standing guard over
+// the spot.
+fn spawn_keys_for_level(world: &mut World, level: &mut Level) {
+    for (idx, key_id, placement) in level.keys.clone() {
+        let key = make_entity_key(create_marked_entity(world), key_id).build();
+        let chest = match placement {
+            KeyPlacement::Container => {
+                let containers = world.read_storage::<Container>();
+                let positions = world.read_storage::<Position>();
+                let entities = world.entities();
+                (&entities, &containers, &positions)
+                    .join()
+                    .find(|(_, _, position)| position.level == level.id && position.idx == idx)
+                    .map(|(entity, _, _)| entity)
+            }
+            KeyPlacement::Carried => None,
         };
-        spawn_points.iter().for_each(|idx| {
-            spawn_goblin(world, *idx, level);
-        });
-    });
+        match chest {
+            Some(chest) => add_item_to_container(world, key, chest),
+            None => {
+                let goblin = spawn_random_goblin(world, idx, level);
+                if let Some(inventory) = world.write_storage::<Inventory>().get_mut(goblin) {
+                    inventory.items.insert(key);
+                }
+            }
+        }
+    }
+}
+
+fn spawn_pits_for_level(world: &mut World, level: &mut Level) {
+    for idx in level.pits.clone() {
+        spawn_pit(world, idx, level);
+    }
+    for idx in level.trapdoors.clone() {
+        spawn_trapdoor(world, idx, level);
+    }
+}
+
+fn spawn_invasion_spawns_for_level(world: &mut World, level: &mut Level) {
+    for (idx, spawn) in level.invasion_spawns.clone() {
+        match spawn {
+            InvasionSpawn::Barricade => spawn_barricade(world, idx, level),
+            InvasionSpawn::Campfire => spawn_campfire(world, idx, level),
+            InvasionSpawn::Trap => spawn_set_traps(world, idx, level),
+            InvasionSpawn::Forge => spawn_forge(world, idx, level),
+            InvasionSpawn::DuergarGuard => {
+                spawn_random_duergar(world, idx, level);
+            }
+            InvasionSpawn::WeaponRack => spawn_weapon_rack(world, idx, level),
+            InvasionSpawn::Rubble => spawn_debris(world, idx, level),
+            InvasionSpawn::GoblinCorpse => spawn_corpse(world, idx, level, "goblin", 'g'),
+            InvasionSpawn::DuergarCorpse => spawn_corpse(world, idx, level, "duergar", 'h'),
+        }
+    }
 }
 
 pub fn spawn_entities_for_level(world: &mut World, level: &mut Level) {
@@ -970,11 +2443,29 @@ pub fn spawn_entities_for_level(world: &mut World, level: &mut Level) {
     for i in (0..count).skip(1) {
         let room = level.rooms[i].clone();
         spawn_entities_for_room(world, &room, level);
+        if level.secret_rooms.contains(&i) {
+            spawn_secret_treasure_for_room(world, &room, level);
+        }
     }
-    spawn_goblins_for_level(world, level);
+    spawn_keys_for_level(world, level);
+    spawn_pits_for_level(world, level);
+    spawn_invasion_spawns_for_level(world, level);
+    spawn_monsters_for_level(world, level);
     spawn_set_traps_for_level(world, level);
 }
 
+// whether any of the doors around a room have been turned into portcullises.
+pub fn rect_has_portcullis(world: &World, rect: &Rect, level: &Level) -> bool {
+    let positions = world.read_storage::<Position>();
+    let mechanisms = world.read_storage::<Mechanism>();
+    (&positions, &mechanisms).join().any(|(position, mechanism)| {
+        let (x, y) = level_utils::idx_xy(level.width as u32, position.idx);
+        position.level == level.id
+            && mechanism.mechanism_type == MechanismType::Portcullis
+            && rect.contains(x, y)
+    })
+}
+
 pub fn spawn_objective_for_room(ecs: &mut World, rect: &Rect, level: &Level) {
     let idx = {
         let mut rng = ecs.write_resource::<RandomNumberGenerator>();
@@ -0,0 +1,116 @@
+use crate::components::{
+    causes_damage::DamageType, DamageHistory, Position, SufferDamage, Viewshed,
+};
+use crate::dungeon::{
+    dungeon::Dungeon, level::Level, level_utils, room_decorators::RoomType, tile_type::TileType,
+};
+use crate::services::{DebrisSpawner, GameLog, ParticleEffectSpawner, TerrainDamage};
+use rltk::{to_cp437, RandomNumberGenerator, BLACK, GREY, RGB};
+use specs::{
+    storage::GenericWriteStorage, Entities, Entity, Join, ReadExpect, ReadStorage, System,
+    WriteExpect, WriteStorage,
+};
+
+// one in this many blows landed in a collapsed room brings more of the ceiling down.
+const CAVE_IN_CHANCE: i32 = 20;
+const CAVE_IN_RADIUS: u32 = 2;
+const CAVE_IN_DAMAGE: i32 = 6;
+
+fn is_in_collapsed_room(level: &Level, idx: usize) -> bool {
+    let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+    level
+        .rooms
+        .iter()
+        .any(|room| room.room_type == Some(RoomType::Collapsed) && room.rect.contains(x, y))
+}
+
+pub struct CaveInSystem {}
+
+impl<'a> System<'a> for CaveInSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        ReadExpect<'a, Dungeon>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Viewshed>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, DamageHistory>,
+        WriteExpect<'a, DebrisSpawner>,
+        WriteExpect<'a, TerrainDamage>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            dungeon,
+            positions,
+            viewsheds,
+            mut suffer_damage,
+            mut damage_histories,
+            mut debris_spawner,
+            mut terrain_damage,
+            mut particle_spawner,
+            mut rng,
+        ) = data;
+        let cave_ins: Vec<(u8, usize)> = (&suffer_damage, &positions)
+            .join()
+            .filter(|(_damage, position)| {
+                let level = dungeon.get_level(position.level).unwrap();
+                is_in_collapsed_room(level, position.idx)
+            })
+            .filter(|_| rng.range(0, CAVE_IN_CHANCE) == 0)
+            .map(|(_damage, position)| (position.level, position.idx))
+            .collect();
+        let player_position = positions.get(*player_entity).unwrap();
+        for (level_number, center_idx) in cave_ins {
+            let level = dungeon.get_level(level_number).unwrap();
+            let fallen_tiles: Vec<usize> =
+                level_utils::get_field_of_view_from_idx(level, center_idx, CAVE_IN_RADIUS)
+                    .into_iter()
+                    .filter(|idx| !level_utils::idx_not_in_map(level, *idx))
+                    .collect();
+            for idx in fallen_tiles.iter() {
+                particle_spawner.request(
+                    *idx,
+                    RGB::named(GREY),
+                    RGB::named(BLACK),
+                    to_cp437('░'),
+                    200.0,
+                    level_number,
+                );
+                // the falling stone weakens the walls around it, which can bring down more.
+                if level_utils::tile_is_breakable(level, *idx) {
+                    terrain_damage.request(*idx, level_number, CAVE_IN_DAMAGE);
+                } else if level.tiles[*idx] == TileType::Floor && rng.range(0, 2) == 0 {
+                    debris_spawner.request_rubble(*idx, level_number);
+                }
+            }
+            for (entity, position) in (&entities, &positions).join() {
+                if position.level != level_number || !fallen_tiles.contains(&position.idx) {
+                    continue;
+                }
+                if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(entity) {
+                    damage_to_suffer.amount += CAVE_IN_DAMAGE;
+                }
+                if let Some(damage_history) = damage_histories.get_mut(entity) {
+                    damage_history.events.insert(DamageType::Crush);
+                }
+            }
+            let seen_by_player = match viewsheds.get(*player_entity) {
+                Some(viewshed) => {
+                    player_position.level == level_number
+                        && viewshed.visible_tiles.contains(&center_idx)
+                }
+                None => false,
+            };
+            if seen_by_player {
+                log.add("The ceiling caves in!".to_string());
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::components::{door::DoorState, Door, Position, Renderable, Viewshed, WantsToCloseDoor};
+use crate::components::{
+    door::DoorState, viewshed, Door, Position, Renderable, Viewshed, WantsToCloseDoor,
+};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
 use rltk::{BLACK, BROWN4, RGB};
 use specs::{Join, ReadStorage, System, WriteExpect, WriteStorage};
@@ -40,12 +42,7 @@ impl<'a> System<'a> for CloseDoorSystem {
                 door_renderable.bg = RGB::named(BLACK);
             }
         }
-        if levels_with_door_close.len() > 0 {
-            (&positions, &mut viewsheds)
-                .join()
-                .filter(|(p, _)| levels_with_door_close.contains(&p.level))
-                .for_each(|(_, v)| v.dirty = true);
-        }
+        viewshed::mark_dirty_on_levels(&levels_with_door_close, &positions, &mut viewsheds);
         wants_to_close_door.clear();
     }
 }
@@ -1,5 +1,5 @@
-use crate::{components::{CombatStats, Container, DamageHistory, Equipment, Hiding, Inventory, Monster, Name, Player, Position, Renderable, SufferDamage, Viewshed, monster::MonsterSpecies}, player::InteractionType, services::{BloodSpawner, CorpseSpawner, DebrisSpawner, GameLog}};
-use rltk::RGB;
+use crate::{components::{CombatStats, Container, DamageHistory, Equipment, Hiding, Inventory, Monster, Name, Player, Position, Renderable, SufferDamage, Viewshed, monster::MonsterSpecies}, player::InteractionType, services::{CorpseSpawner, DebrisSpawner, GameLog, SurfaceSpawner}};
+use crate::dungeon::surface_type::SurfaceType;
 use specs::{
     Entities, Entity, Join, ReadExpect, ReadStorage, System, World, WorldExt, WriteExpect,
     WriteStorage,
@@ -54,6 +54,14 @@ impl<'a> DamageSystem<'a> {
                                 items,
                             );
                         }
+                        MonsterSpecies::Duergar => {
+                            corpse_spawner.request_duergar_corpse(
+                                position.idx,
+                                position.level,
+                                damage_history.describe_in_past_tense(),
+                                items,
+                            );
+                        }
                     }
                     if visible_to_player {
                         log.add(format!("{} has died", name.name));
@@ -114,7 +122,7 @@ impl<'a> System<'a> for DamageSystem<'a> {
         WriteStorage<'a, CombatStats>,
         WriteStorage<'a, SufferDamage>,
         ReadStorage<'a, Position>,
-        WriteExpect<'a, BloodSpawner>,
+        WriteExpect<'a, SurfaceSpawner>,
         ReadStorage<'a, Monster>,
         ReadStorage<'a, Player>,
         ReadExpect<'a, Entity>,
@@ -126,7 +134,7 @@ impl<'a> System<'a> for DamageSystem<'a> {
             mut stats,
             mut suffer_damage,
             positions,
-            mut blood_spawner,
+            mut surface_spawner,
             monsters,
             players,
             player_ent,
@@ -136,13 +144,7 @@ impl<'a> System<'a> for DamageSystem<'a> {
             // create blood
             if monsters.get(ent).is_some() || players.get(ent).is_some() {
                 let position = positions.get(ent).unwrap();
-                blood_spawner.request(
-                    position.idx,
-                    RGB::from_f32(0.85, 0., 0.),
-                    RGB::from_f32(0.50, 0., 0.),
-                    177,
-                    position.level,
-                );
+                surface_spawner.request(position.idx, position.level, SurfaceType::Blood);
             }
             if ent == *player_ent {
                 self.queued_action.take();
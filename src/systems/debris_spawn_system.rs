@@ -1,5 +1,6 @@
 use crate::components::{Flammable, Grabbable, Name, Position, Renderable, Saveable};
 use crate::services::DebrisSpawner;
+use crate::types::Material;
 use specs::{
     saveload::{MarkerAllocator, SimpleMarker, SimpleMarkerAllocator},
     Entities, System, WriteExpect, WriteStorage,
@@ -66,7 +67,13 @@ impl<'a> System<'a> for DebrisSpawnSystem {
                 .expect("failed inserting grabbable for debris");
             if request.flammable {
                 flammables
-                    .insert(new_debris, Flammable { turns_remaining: 4 })
+                    .insert(
+                        new_debris,
+                        Flammable {
+                            turns_remaining: 4,
+                            material: Material::Wood,
+                        },
+                    )
                     .expect("failed inserting flammable for debris");
             }
             marker_allocator.mark(new_debris, &mut markers);
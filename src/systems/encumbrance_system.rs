@@ -0,0 +1,63 @@
+use crate::components::{
+    CombatStats, Encumbered, Equipment, Grabbing, Inventory, Stackable, Weight,
+};
+use crate::encumbrance::{get_carried_weight, get_carry_capacity};
+use crate::services::GameLog;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+pub struct EncumbranceSystem {}
+
+impl<'a> System<'a> for EncumbranceSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, Grabbing>,
+        ReadStorage<'a, Weight>,
+        ReadStorage<'a, Stackable>,
+        WriteStorage<'a, Encumbered>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            combat_stats,
+            inventories,
+            equipment,
+            grabbings,
+            weights,
+            stackables,
+            mut encumbered,
+        ) = data;
+        for (entity, stats, _inventory) in (&entities, &combat_stats, &inventories).join() {
+            let carried = get_carried_weight(
+                entity,
+                &inventories,
+                &equipment,
+                &grabbings,
+                &weights,
+                &stackables,
+            );
+            let is_encumbered = carried > get_carry_capacity(stats);
+            let was_encumbered = encumbered.get(entity).is_some();
+            if is_encumbered && !was_encumbered {
+                encumbered
+                    .insert(entity, Encumbered { turns_owed: 0 })
+                    .expect("failed inserting encumbered");
+                if entity == *player_entity {
+                    log.add("You are carrying more than you can manage".to_string());
+                }
+            } else if !is_encumbered && was_encumbered {
+                encumbered.remove(entity);
+                if entity == *player_entity {
+                    log.add("You are no longer overburdened".to_string());
+                }
+            }
+        }
+    }
+}
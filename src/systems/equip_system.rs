@@ -1,4 +1,7 @@
-use crate::components::{equipable::EquipmentPositions, Equipment, Inventory, Name, WantsToEquip};
+use crate::components::{
+    equipable::EquipmentPositions, Equipment, Inventory, Name, TwoHanded, WantsToEquip,
+};
+use crate::entity_set::EntitySet;
 use crate::services::GameLog;
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
@@ -25,6 +28,23 @@ fn get_at_position_in_equipment(
     }
 }
 
+fn get_other_hand(position: EquipmentPositions) -> Option<EquipmentPositions> {
+    match position {
+        EquipmentPositions::OffHand => Some(EquipmentPositions::DominantHand),
+        EquipmentPositions::DominantHand => Some(EquipmentPositions::OffHand),
+        _ => None,
+    }
+}
+
+fn remove_from_equipment(equipment: &mut Equipment, equipment_ent: Entity) {
+    if equipment.off_hand == Some(equipment_ent) {
+        equipment.off_hand = None;
+    }
+    if equipment.dominant_hand == Some(equipment_ent) {
+        equipment.dominant_hand = None;
+    }
+}
+
 pub struct EquipSystem {}
 
 impl<'a> System<'a> for EquipSystem {
@@ -34,6 +54,7 @@ impl<'a> System<'a> for EquipSystem {
         WriteStorage<'a, Equipment>,
         WriteStorage<'a, Inventory>,
         ReadStorage<'a, Name>,
+        ReadStorage<'a, TwoHanded>,
         ReadExpect<'a, Entity>,
         WriteExpect<'a, GameLog>,
     );
@@ -45,6 +66,7 @@ impl<'a> System<'a> for EquipSystem {
             mut equipment,
             mut inventories,
             names,
+            two_handed,
             player_entity,
             mut log,
         ) = data;
@@ -57,23 +79,56 @@ impl<'a> System<'a> for EquipSystem {
         )
             .join()
         {
+            let mut to_unequip = EntitySet::new();
             if let Some(old_equipment_ent) =
                 get_at_position_in_equipment(ent_equipment, intent.position)
             {
-                inventory.items.insert(old_equipment_ent);
+                to_unequip.insert(old_equipment_ent);
+            }
+            if let Some(equipment_ent) = intent.equipment {
+                // two handed items need both hands free, and anything going into
+                // a hand needs to displace a two handed item held in the other.
+                if two_handed.get(equipment_ent).is_some() {
+                    for held in ent_equipment.as_items().drain() {
+                        to_unequip.insert(held);
+                    }
+                } else if let Some(other_hand) = get_other_hand(intent.position) {
+                    if let Some(other_ent) = get_at_position_in_equipment(ent_equipment, other_hand)
+                    {
+                        if two_handed.get(other_ent).is_some() {
+                            to_unequip.insert(other_ent);
+                        }
+                    }
+                }
+            }
+            for old_equipment_ent in to_unequip.iter() {
+                remove_from_equipment(ent_equipment, *old_equipment_ent);
+                inventory.items.insert(*old_equipment_ent);
                 if entity == *player_entity {
-                    let name = &names.get(old_equipment_ent).unwrap().name;
+                    let name = &names.get(*old_equipment_ent).unwrap().name;
                     log.add(format!("You unequip the {}", name));
                 }
             }
-            if let Some(equipment_ent) = intent.equipment {
-                inventory.items.remove(&equipment_ent);
-                if entity == *player_entity {
-                    let name = &names.get(equipment_ent).unwrap().name;
-                    log.add(format!("You equip the {}", name));
+            match intent.equipment {
+                Some(equipment_ent) => {
+                    inventory.items.remove(&equipment_ent);
+                    if entity == *player_entity {
+                        let name = &names.get(equipment_ent).unwrap().name;
+                        log.add(format!("You equip the {}", name));
+                    }
+                    if two_handed.get(equipment_ent).is_some() {
+                        ent_equipment.dominant_hand = Some(equipment_ent);
+                        ent_equipment.off_hand = Some(equipment_ent);
+                    } else {
+                        set_at_position_in_equipment(
+                            ent_equipment,
+                            Some(equipment_ent),
+                            intent.position,
+                        );
+                    }
                 }
+                None => set_at_position_in_equipment(ent_equipment, None, intent.position),
             }
-            set_at_position_in_equipment(ent_equipment, intent.equipment, intent.position);
         }
         wants_to_equip.clear();
     }
@@ -0,0 +1,217 @@
+use crate::components::{
+    causes_damage::DamageType, CombatStats, DamageHistory, Explosive, Furniture, OnFire, Position,
+    SufferDamage, Viewshed,
+};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, tile_type::TileType};
+use crate::services::{
+    DebrisSpawner, ExplosionSpawner, GameLog, ParticleEffectSpawner, TerrainDamage,
+};
+use rltk::{to_cp437, ORANGE, RED, RGB, YELLOW};
+use specs::{
+    storage::GenericWriteStorage, Entities, Entity, Join, ReadExpect, ReadStorage, System,
+    WriteExpect, WriteStorage,
+};
+use std::collections::HashSet;
+
+const EXPLOSION_FIRE_TURNS: u8 = 2;
+
+// damage falls away the further something is from the blast.
+fn get_damage_at_distance(damage: i32, radius: u32, distance: f32) -> i32 {
+    let falloff = 1.0 - distance / (radius as f32 + 1.0);
+    f32::max(0.0, (damage as f32 * falloff).ceil()) as i32
+}
+
+// actors caught in the blast are thrown a step away from it, if there's room to land that
+// nobody else in the same blast has already been thrown onto.
+fn get_knockback_idx(
+    level: &Level,
+    center_idx: usize,
+    idx: usize,
+    claimed: &HashSet<usize>,
+) -> Option<usize> {
+    let (center_x, center_y) = level_utils::idx_xy(level.width as u32, center_idx);
+    let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+    let (delta_x, delta_y) = ((x - center_x).signum(), (y - center_y).signum());
+    if delta_x == 0 && delta_y == 0 {
+        return None;
+    }
+    let destination_idx = level_utils::get_idx_at_xy(level, x + delta_x, y + delta_y)?;
+    if claimed.contains(&destination_idx)
+        || level_utils::tile_is_blocked(destination_idx, level)
+        || level.tiles[destination_idx] == TileType::WaterDeep
+    {
+        return None;
+    }
+    Some(destination_idx)
+}
+
+pub struct ExplosionSystem {}
+
+impl<'a> System<'a> for ExplosionSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        WriteExpect<'a, ExplosionSpawner>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteExpect<'a, TerrainDamage>,
+        WriteExpect<'a, DebrisSpawner>,
+        WriteStorage<'a, Position>,
+        ReadStorage<'a, Explosive>,
+        ReadStorage<'a, OnFire>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, DamageHistory>,
+        ReadStorage<'a, Furniture>,
+        WriteStorage<'a, Viewshed>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut dungeon,
+            mut explosion_spawner,
+            mut particle_spawner,
+            mut terrain_damage,
+            mut debris_spawner,
+            mut positions,
+            explosives,
+            on_fires,
+            combat_stats,
+            mut suffer_damage,
+            mut damage_histories,
+            furniture,
+            mut viewsheds,
+        ) = data;
+
+        // explosives go off when they catch fire or take enough damage to break open.
+        let mut detonated: HashSet<Entity> = HashSet::new();
+        for (entity, explosive, position) in (&entities, &explosives, &positions).join() {
+            let burning = on_fires.get(entity).is_some();
+            let broken = match (combat_stats.get(entity), suffer_damage.get(entity)) {
+                (Some(stats), Some(damage)) => stats.hp - damage.amount < 1,
+                _ => false,
+            };
+            if burning || broken {
+                detonated.insert(entity);
+                explosion_spawner.request(
+                    position.idx,
+                    position.level,
+                    explosive.radius,
+                    explosive.damage,
+                );
+            }
+        }
+
+        let player_position = positions.get(*player_entity).unwrap().clone();
+        let mut heard_explosion = false;
+        while let Some(request) = explosion_spawner.requests.pop() {
+            let level = dungeon.get_level_mut(request.level).unwrap();
+            let blast_tiles: Vec<usize> =
+                level_utils::get_field_of_view_from_idx(level, request.idx, request.radius)
+                    .into_iter()
+                    .filter(|idx| !level_utils::idx_not_in_map(level, *idx))
+                    .collect();
+            for idx in blast_tiles.iter() {
+                particle_spawner.request(
+                    *idx,
+                    RGB::named(ORANGE),
+                    RGB::named(RED),
+                    to_cp437('░'),
+                    200.0,
+                    request.level,
+                );
+                if level_utils::tile_is_breakable(level, *idx) {
+                    let distance = level_utils::get_distance_between_idxs(level, request.idx, *idx);
+                    terrain_damage.request(
+                        *idx,
+                        request.level,
+                        get_damage_at_distance(request.damage, request.radius, distance),
+                    );
+                } else if !level.blocked[*idx] && level.tiles[*idx] != TileType::WaterDeep {
+                    level.fire[*idx] = u8::max(level.fire[*idx], EXPLOSION_FIRE_TURNS);
+                }
+            }
+            particle_spawner.request(
+                request.idx,
+                RGB::named(YELLOW),
+                RGB::named(RED),
+                to_cp437('☼'),
+                300.0,
+                request.level,
+            );
+
+            let mut knocked_back: Vec<(Entity, usize)> = vec![];
+            let mut claimed: HashSet<usize> = HashSet::new();
+            for (entity, position) in (&entities, &positions).join() {
+                if position.level != request.level || !blast_tiles.contains(&position.idx) {
+                    continue;
+                }
+                let distance =
+                    level_utils::get_distance_between_idxs(level, request.idx, position.idx);
+                if let Some(explosive) = explosives.get(entity) {
+                    // neighbouring explosives are set off in turn.
+                    if detonated.insert(entity) {
+                        explosion_spawner.request(
+                            position.idx,
+                            position.level,
+                            explosive.radius,
+                            explosive.damage,
+                        );
+                    }
+                    continue;
+                }
+                if let Some(stats) = combat_stats.get(entity) {
+                    let damage = match furniture.get(entity) {
+                        Some(_) => stats.hp,
+                        None => get_damage_at_distance(request.damage, request.radius, distance),
+                    };
+                    if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(entity) {
+                        damage_to_suffer.amount += damage;
+                    }
+                    if let Some(damage_history) = damage_histories.get_mut(entity) {
+                        damage_history.events.insert(DamageType::Blast);
+                    }
+                }
+                if viewsheds.get(entity).is_some() && furniture.get(entity).is_none() {
+                    if let Some(idx) =
+                        get_knockback_idx(level, request.idx, position.idx, &claimed)
+                    {
+                        claimed.insert(idx);
+                        knocked_back.push((entity, idx));
+                    }
+                }
+            }
+            for (entity, idx) in knocked_back {
+                if level_utils::tile_is_blocked(idx, level) {
+                    continue;
+                }
+                if let Some(position) = positions.get_mut(entity) {
+                    level.blocked[position.idx] = false;
+                    position.idx = idx;
+                    level.blocked[idx] = true;
+                }
+                if let Some(viewshed) = viewsheds.get_mut(entity) {
+                    viewshed.dirty = true;
+                }
+            }
+            if request.level == player_position.level {
+                heard_explosion = true;
+            }
+        }
+        for entity in detonated {
+            if let Some(position) = positions.get(entity) {
+                debris_spawner.request_burnt_debris(position.idx, position.level);
+            }
+            entities
+                .delete(entity)
+                .expect("couldn't delete exploded entity");
+        }
+        if heard_explosion {
+            log.add("There is a deafening explosion!".to_string());
+        }
+    }
+}
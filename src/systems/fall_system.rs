@@ -0,0 +1,87 @@
+use crate::components::{EntityMoved, Hidden, Name, Position, SufferDamage, Viewshed};
+use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::services::GameLog;
+use rltk::RandomNumberGenerator;
+use specs::{
+    storage::GenericWriteStorage, Entities, Entity, Join, ReadExpect, ReadStorage, System,
+    WriteExpect, WriteStorage,
+};
+
+const MAX_FALL_DAMAGE: i32 = 4;
+
+// anything that steps onto a pit or trapdoor drops through to the level below.
+pub struct FallSystem {}
+
+impl<'a> System<'a> for FallSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        ReadExpect<'a, Dungeon>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, EntityMoved>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, Hidden>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Name>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            dungeon,
+            mut log,
+            mut rng,
+            moved,
+            mut positions,
+            mut viewsheds,
+            mut hiddens,
+            mut suffer_damage,
+            names,
+        ) = data;
+        let mut falls = vec![];
+        for (entity, _moved, position) in (&entities, &moved, &positions).join() {
+            let level = dungeon.get_level(position.level).unwrap();
+            if !level_utils::idx_has_pit(level, position.idx) {
+                continue;
+            }
+            if let Some(destination) = dungeon.get_destination(position.level, position.idx) {
+                falls.push((entity, position.level, position.idx, destination));
+            }
+        }
+        let player_position = positions.get(*player_entity).unwrap().clone();
+        for (entity, from_level, from_idx, (to_level, to_idx)) in falls {
+            let level = dungeon.get_level(from_level).unwrap();
+            let player_can_see = from_level == player_position.level
+                && viewsheds
+                    .get(*player_entity)
+                    .map_or(false, |viewshed| viewshed.visible_tiles.contains(&from_idx));
+            // whatever was covering the hole is no secret now.
+            for covering in level_utils::entities_at_idx(level, from_idx) {
+                hiddens.remove(covering);
+            }
+            if entity == *player_entity {
+                match level.trapdoors.contains(&from_idx) {
+                    true => log.add("The floor gives way beneath you!".to_string()),
+                    false => log.add("You drop down the pit.".to_string()),
+                }
+            } else if player_can_see {
+                if let Some(name) = names.get(entity) {
+                    log.add(format!("{} falls through the floor.", name.name));
+                }
+            }
+            if let Some(position) = positions.get_mut(entity) {
+                position.level = to_level;
+                position.idx = to_idx;
+            }
+            if let Some(viewshed) = viewsheds.get_mut(entity) {
+                viewshed.dirty = true;
+            }
+            if let Some(damage) = suffer_damage.get_mut_or_default(entity) {
+                damage.amount += rng.range(1, MAX_FALL_DAMAGE + 1);
+            }
+        }
+    }
+}
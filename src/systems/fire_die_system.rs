@@ -1,8 +1,10 @@
 use crate::{
-    components::{Flammable, Position},
+    components::{viewshed, Door, Flammable, Position, Viewshed},
+    dungeon::{dungeon::Dungeon, level_utils},
     services::DebrisSpawner,
 };
-use specs::{Entities, Join, ReadStorage, System, WriteExpect};
+use specs::{Entities, Join, ReadStorage, System, WriteExpect, WriteStorage};
+use std::collections::HashSet;
 
 pub struct FireDieSystem {}
 
@@ -12,16 +14,37 @@ impl<'a> System<'a> for FireDieSystem {
         ReadStorage<'a, Flammable>,
         ReadStorage<'a, Position>,
         WriteExpect<'a, DebrisSpawner>,
+        ReadStorage<'a, Door>,
+        WriteExpect<'a, Dungeon>,
+        WriteStorage<'a, Viewshed>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (entities, flammables, positions, mut debris_spawner) = data;
+        let (
+            entities,
+            flammables,
+            positions,
+            mut debris_spawner,
+            doors,
+            mut dungeon,
+            mut viewsheds,
+        ) = data;
+        let mut levels_with_door_burnt = HashSet::new();
         for (entity, flammable) in (&entities, &flammables).join() {
             if flammable.turns_remaining < 1 {
                 let position = positions.get(entity).unwrap();
                 debris_spawner.request_burnt_debris(position.idx, position.level);
+                if doors.get(entity).is_some() {
+                    // a burnt out door leaves an open doorway behind.
+                    let mut level = dungeon.get_level_mut(position.level).unwrap();
+                    level_utils::set_tile_to_floor(&mut level, position.idx);
+                    level.blocked[position.idx] = false;
+                    level.opaque[position.idx] = false;
+                    levels_with_door_burnt.insert(position.level);
+                }
                 entities.delete(entity).expect("couldn't delete entity");
             }
         }
+        viewshed::mark_dirty_on_levels(&levels_with_door_burnt, &positions, &mut viewsheds);
     }
 }
@@ -1,77 +1,182 @@
-use crate::components::{CausesLight, CombatStats, DamageHistory, Flammable, OnFire, Position, SufferDamage, causes_damage::DamageType};
-use crate::dungeon::{dungeon::Dungeon, level_utils};
-use rltk::RandomNumberGenerator;
+use crate::components::{
+    causes_damage::DamageType, CausesLight, CombatStats, DamageHistory, Flammable, OnFire,
+    Position, SufferDamage,
+};
+use crate::dungeon::{
+    dungeon::Dungeon,
+    level::Level,
+    level_utils,
+    surface_type::{self, SurfaceType},
+    tile_type::TileType,
+};
+use crate::services::ParticleEffectSpawner;
+use crate::types::material;
+use rltk::{to_cp437, RandomNumberGenerator, BLACK, ORANGE, RGB};
 use specs::{
-    storage::GenericWriteStorage, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect,
-    WriteStorage,
+    storage::GenericWriteStorage, Entities, Join, ReadStorage, System, WriteExpect, WriteStorage,
 };
+use std::collections::{HashMap, HashSet};
+
+const FIRE_DAMAGE: i32 = 2;
+const OIL_BURN_DAMAGE: i32 = 4;
+const BURNING_ENTITY_FIRE_TURNS: u8 = 2;
+const OIL_FIRE_TURNS: u8 = 4;
+
+fn tile_is_wet(level: &Level, idx: usize) -> bool {
+    match (level.tiles.get(idx), level.surfaces.get(idx)) {
+        (Some(TileType::WaterDeep), _) => true,
+        (_, Some(Some(surface))) => surface_type::surface_extinguishes_fire(surface),
+        _ => false,
+    }
+}
+
+// fire reaching an oil slick races across all of it at once.
+fn get_connected_oil(level: &Level, start_idx: usize) -> HashSet<usize> {
+    let mut burning = HashSet::new();
+    let mut to_visit = vec![start_idx];
+    while let Some(idx) = to_visit.pop() {
+        let is_oil = match level.surfaces.get(idx) {
+            Some(Some(surface)) => surface_type::surface_is_flammable(surface),
+            _ => false,
+        };
+        if is_oil && burning.insert(idx) {
+            to_visit.append(&mut level_utils::get_neighbor_idxs(level, idx));
+        }
+    }
+    burning
+}
 
 pub struct FireSpreadSystem {}
 
 impl<'a> System<'a> for FireSpreadSystem {
     type SystemData = (
+        Entities<'a>,
         WriteStorage<'a, OnFire>,
         ReadStorage<'a, Position>,
         ReadStorage<'a, Flammable>,
         ReadStorage<'a, CombatStats>,
-        ReadExpect<'a, Dungeon>,
+        WriteExpect<'a, Dungeon>,
         WriteStorage<'a, SufferDamage>,
         WriteExpect<'a, RandomNumberGenerator>,
         WriteStorage<'a, CausesLight>,
-        WriteStorage<'a, DamageHistory>
+        WriteStorage<'a, DamageHistory>,
+        WriteExpect<'a, ParticleEffectSpawner>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
         let (
+            entities,
             mut on_fires,
             positions,
             flammables,
             combat_stats,
-            dungeon,
+            mut dungeon,
             mut suffer_damage,
             mut rng,
             mut causes_light,
-            mut damage_histories
+            mut damage_histories,
+            mut particle_spawner,
         ) = data;
 
-        let affected_entities: Vec<Entity> = (&mut on_fires, &positions)
-            .join()
-            .map(|(_, position)| {
-                let level = dungeon.get_level(position.level).unwrap();
-                level_utils::get_neighbors_for_idx(level.width as i32, position.idx as i32)
+        // anything alight keeps the ground beneath it burning.
+        for (_on_fire, position) in (&on_fires, &positions).join() {
+            let level = dungeon.get_level_mut(position.level).unwrap();
+            level.fire[position.idx] = u8::max(level.fire[position.idx], BURNING_ENTITY_FIRE_TURNS);
+        }
+
+        let burning_tiles: Vec<(u8, usize)> = dungeon
+            .levels
+            .iter()
+            .flat_map(|(level_number, level)| {
+                level
+                    .fire
                     .iter()
-                    .map(|idx| level_utils::entities_at_idx(level, *idx as usize))
-                    .flatten()
-                    .collect::<Vec<Entity>>()
+                    .enumerate()
+                    .filter(|(_idx, fire)| **fire > 0)
+                    .map(move |(idx, _fire)| (*level_number, idx))
             })
-            .flatten()
             .collect();
-        affected_entities.iter().for_each(|e| {
-            if let Some(f) = flammables.get(*e) {
-                if rng.range(0, 2) == 1 {
-                    on_fires
-                        .insert(*e, OnFire {})
-                        .expect("couldn't light entity on fire");
-                    causes_light
-                        .insert(
-                            *e,
-                            CausesLight {
-                                radius: 3,
-                                lit: true,
-                                turns_remaining: Some(f.turns_remaining as u32),
-                            },
-                        )
-                        .expect("couldn't insert cause light for target");
+
+        // every burning tile heats itself and its neighbours, the more heat a tile takes the
+        // more likely whatever is on it is to catch.
+        let mut heat: HashMap<(u8, usize), u32> = HashMap::new();
+        let mut flashed: HashSet<(u8, usize)> = HashSet::new();
+        for (level_number, idx) in burning_tiles {
+            let level = dungeon.get_level_mut(level_number).unwrap();
+            let neighbors = level_utils::get_neighbor_idxs(level, idx);
+            for heated_idx in neighbors.into_iter().chain(std::iter::once(idx)) {
+                if tile_is_wet(level, heated_idx) {
+                    // fire melts ice, but can't catch on anything sitting in water.
+                    if level.surfaces[heated_idx] == Some(SurfaceType::Ice) {
+                        level.surfaces[heated_idx] = Some(SurfaceType::Water);
+                    }
+                    continue;
                 }
-            }
-            if let Some(_) = combat_stats.get(*e) {
-                if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(*e) {
-                    damage_to_suffer.amount += 2;
+                for oil_idx in get_connected_oil(level, heated_idx) {
+                    level.surfaces[oil_idx] = None;
+                    level.fire[oil_idx] = u8::max(level.fire[oil_idx], OIL_FIRE_TURNS);
+                    particle_spawner.request(
+                        oil_idx,
+                        RGB::named(ORANGE),
+                        RGB::named(BLACK),
+                        to_cp437('▲'),
+                        200.0,
+                        level_number,
+                    );
+                    flashed.insert((level_number, oil_idx));
                 }
+                *heat.entry((level_number, heated_idx)).or_insert(0) += 1;
             }
-            if let Some(damage_history) = damage_histories.get_mut(*e) {
+        }
+
+        let mut ignited = vec![];
+        for (entity, flammable, position, _not_on_fire) in
+            (&entities, &flammables, &positions, !&on_fires).join()
+        {
+            let tile = (position.level, position.idx);
+            let chance = material::get_ignition_chance(&flammable.material);
+            let heat_taken = *heat.get(&tile).unwrap_or(&0);
+            if flashed.contains(&tile) || (0..heat_taken).any(|_| rng.range(0, 100) < chance) {
+                ignited.push((entity, flammable.turns_remaining));
+            }
+        }
+        for (entity, turns_remaining) in ignited {
+            on_fires
+                .insert(entity, OnFire {})
+                .expect("couldn't light entity on fire");
+            causes_light
+                .insert(
+                    entity,
+                    CausesLight {
+                        radius: 3,
+                        color: RGB::named(rltk::RED),
+                        lit: true,
+                        turns_remaining: Some(turns_remaining as u32),
+                    },
+                )
+                .expect("couldn't insert cause light for target");
+        }
+
+        for (entity, _combat_stats, position) in (&entities, &combat_stats, &positions).join() {
+            let level = dungeon.get_level(position.level).unwrap();
+            if level.fire[position.idx] == 0 {
+                continue;
+            }
+            if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(entity) {
+                damage_to_suffer.amount += match flashed.contains(&(position.level, position.idx)) {
+                    true => OIL_BURN_DAMAGE,
+                    false => FIRE_DAMAGE,
+                };
+            }
+            if let Some(damage_history) = damage_histories.get_mut(entity) {
                 damage_history.events.insert(DamageType::Burn);
             }
-        });
+        }
+
+        for level in dungeon.levels.values_mut() {
+            for fire in level.fire.iter_mut() {
+                *fire = fire.saturating_sub(1);
+            }
+        }
     }
 }
@@ -1,5 +1,7 @@
-use crate::components::{Container, Inventory, Name, Position, WantsToPickUpItem};
-use crate::services::GameLog;
+use crate::components::{Container, Inventory, Name, Position, Stackable, WantsToPickUpItem};
+use crate::identification::Identification;
+use crate::inventory::{get_matching_stack, get_stack_quantity};
+use crate::services::{GameLog, StackSpawner};
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
 pub struct ItemCollectionSystem {}
@@ -13,7 +15,10 @@ impl<'a> System<'a> for ItemCollectionSystem {
         WriteStorage<'a, Position>,
         ReadStorage<'a, Name>,
         WriteStorage<'a, Container>,
-        WriteStorage<'a, Inventory>
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
+        ReadExpect<'a, Identification>,
+        WriteExpect<'a, StackSpawner>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -26,23 +31,55 @@ impl<'a> System<'a> for ItemCollectionSystem {
             names,
             mut containers,
             mut inventories,
+            mut stackables,
+            identification,
+            mut stack_spawner,
         ) = data;
 
         for (ent, pick_up, inventory) in (&entities, &wants_to_pick_up, &mut inventories).join() {
             for item in pick_up.items.iter() {
-                positions.remove(*item);
-                if let Some(container_ent) = pick_up.container {
-                    if let Some(container) = containers.get_mut(container_ent) {
-                      container.items.remove(&item);
+                let quantity = get_stack_quantity(*item, &stackables);
+                // taking one from a stack leaves the rest of it where it was.
+                let splitting = !pick_up.whole_stack && quantity > 1;
+                if splitting {
+                    if let Some(stack) = stackables.get_mut(*item) {
+                        stack.quantity -= 1;
+                    }
+                } else {
+                    positions.remove(*item);
+                    if let Some(container_ent) = pick_up.container {
+                        if let Some(container) = containers.get_mut(container_ent) {
+                            container.items.remove(&item);
+                        }
                     }
                 }
-                inventory.items.insert(*item);
                 if ent == *player_entity {
                     game_log.add(format!(
                         "you pick up the {}",
-                        names.get(*item).unwrap().name
+                        identification.get_display_name(&names.get(*item).unwrap().name)
                     ))
                 }
+                // items of the same kind are gathered into a single stack
+                match get_matching_stack(*item, inventory.items.iter(), &stackables, &names) {
+                    Some(stack) => {
+                        let picked_up_quantity = match splitting {
+                            true => 1,
+                            false => quantity,
+                        };
+                        if let Some(stack) = stackables.get_mut(stack) {
+                            stack.quantity += picked_up_quantity;
+                        }
+                        if !splitting {
+                            entities
+                                .delete(*item)
+                                .expect("failed deleting merged stack");
+                        }
+                    }
+                    None if splitting => stack_spawner.request_into_inventory(ent, *item),
+                    None => {
+                        inventory.items.insert(*item);
+                    }
+                }
             }
         }
         wants_to_pick_up.clear();
@@ -1,5 +1,7 @@
-use crate::components::{Inventory, Name, Position, WantsToDropItem};
-use crate::services::GameLog;
+use crate::components::{Inventory, Name, Position, Stackable, WantsToDropItem};
+use crate::identification::Identification;
+use crate::inventory::{get_matching_stack, get_stack_quantity};
+use crate::services::{GameLog, StackSpawner};
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
 pub struct ItemDropSystem {}
@@ -13,6 +15,9 @@ impl<'a> System<'a> for ItemDropSystem {
         ReadStorage<'a, Name>,
         WriteStorage<'a, Position>,
         WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
+        ReadExpect<'a, Identification>,
+        WriteExpect<'a, StackSpawner>,
     );
     fn run(&mut self, data: Self::SystemData) {
         let (
@@ -23,6 +28,9 @@ impl<'a> System<'a> for ItemDropSystem {
             names,
             mut positions,
             mut inventories,
+            mut stackables,
+            identification,
+            mut stack_spawner,
         ) = data;
         for (to_drop, dropping_ent, inventory) in
             (&wants_to_drop, &entities, &mut inventories).join()
@@ -32,21 +40,61 @@ impl<'a> System<'a> for ItemDropSystem {
                 let pos = positions.get(dropping_ent).unwrap();
                 (pos.idx, pos.level)
             };
-            positions
-                .insert(
-                    dropped_ent,
-                    Position {
-                        idx: dropping_ent_idx,
-                        level: dropping_ent_level,
-                    },
-                )
-                .expect("failed to add dropped_ent to positions");
-            inventory.items.remove(&dropped_ent);
+            let quantity = get_stack_quantity(dropped_ent, &stackables);
+            // dropping one from a stack leaves the rest of it in the inventory.
+            let splitting = !to_drop.whole_stack && quantity > 1;
+            if !splitting {
+                inventory.items.remove(&dropped_ent);
+            }
             if dropping_ent == *player_entity {
-                game_log.entries.insert(
-                    0,
-                    format!("You drop the {}.", names.get(dropped_ent).unwrap().name),
-                )
+                let dropped_name =
+                    identification.get_display_name(&names.get(dropped_ent).unwrap().name);
+                game_log
+                    .entries
+                    .insert(0, format!("You drop the {}.", dropped_name))
+            }
+            // a dropped stack joins any stack of the same kind already lying on the floor
+            let floor_items: Vec<Entity> = (&entities, &positions)
+                .join()
+                .filter(|(_, p)| p.idx == dropping_ent_idx && p.level == dropping_ent_level)
+                .map(|(e, _)| e)
+                .collect();
+            let floor_stack =
+                get_matching_stack(dropped_ent, floor_items.iter(), &stackables, &names);
+            if splitting {
+                if let Some(stack) = stackables.get_mut(dropped_ent) {
+                    stack.quantity -= 1;
+                }
+            }
+            match floor_stack {
+                Some(floor_stack) => {
+                    let dropped_quantity = match splitting {
+                        true => 1,
+                        false => quantity,
+                    };
+                    if let Some(floor_stack) = stackables.get_mut(floor_stack) {
+                        floor_stack.quantity += dropped_quantity;
+                    }
+                    if !splitting {
+                        entities
+                            .delete(dropped_ent)
+                            .expect("failed deleting merged stack");
+                    }
+                }
+                None if splitting => {
+                    stack_spawner.request(dropping_ent_idx, dropping_ent_level, dropped_ent);
+                }
+                None => {
+                    positions
+                        .insert(
+                            dropped_ent,
+                            Position {
+                                idx: dropping_ent_idx,
+                                level: dropping_ent_level,
+                            },
+                        )
+                        .expect("failed to add dropped_ent to positions");
+                }
             }
         }
         wants_to_drop.clear();
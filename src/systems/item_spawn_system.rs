@@ -1,5 +1,6 @@
 use crate::components::{
-    Armable, Consumable, Item, Name, Position, Ranged, Renderable, Saveable, Trap,
+    Armable, Consumable, Item, Name, Position, Ranged, Renderable, Saveable, Stackable, Trap,
+    Weight,
 };
 use crate::services::ItemSpawner;
 use crate::types::item_type;
@@ -21,6 +22,8 @@ impl<'a> System<'a> for ItemSpawnSystem {
         WriteStorage<'a, Ranged>,
         WriteStorage<'a, Trap>,
         WriteStorage<'a, Armable>,
+        WriteStorage<'a, Stackable>,
+        WriteStorage<'a, Weight>,
         WriteExpect<'a, ItemSpawner>,
         WriteExpect<'a, SimpleMarkerAllocator<Saveable>>,
         WriteStorage<'a, SimpleMarker<Saveable>>,
@@ -37,6 +40,8 @@ impl<'a> System<'a> for ItemSpawnSystem {
             mut ranged,
             mut traps,
             mut armables,
+            mut stackables,
+            mut weights,
             mut spawner,
             mut marker_allocator,
             mut markers,
@@ -74,10 +79,21 @@ impl<'a> System<'a> for ItemSpawnSystem {
             items
                 .insert(new_item, Item {})
                 .expect("failed inserting item for new item");
+            weights
+                .insert(
+                    new_item,
+                    Weight {
+                        weight: item_type::get_weight_for_item(&request.item_type),
+                    },
+                )
+                .expect("failed inserting weight for new item");
             if item_type::item_is_consumable(&request.item_type) {
                 consumables
                     .insert(new_item, Consumable {})
                     .expect("failed inserting consumable for new item");
+                stackables
+                    .insert(new_item, Stackable { quantity: 1 })
+                    .expect("failed inserting stackable for new item");
             }
 
             if let Some(range) = item_type::get_range_for_item(&request.item_type) {
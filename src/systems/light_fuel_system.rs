@@ -0,0 +1,64 @@
+use crate::components::{CausesLight, Dousable, Equipment, Lightable, Name, OnFire};
+use crate::services::GameLog;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+pub struct LightFuelSystem {}
+
+impl<'a> System<'a> for LightFuelSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, OnFire>,
+        WriteStorage<'a, CausesLight>,
+        WriteStorage<'a, Dousable>,
+        WriteStorage<'a, Lightable>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut game_log,
+            names,
+            equipment,
+            on_fires,
+            mut causes_light,
+            mut dousables,
+            mut lightables,
+        ) = data;
+        let player_equipment = equipment.get(*player_entity);
+        // burning objects are handled by the fire systems, only fuelled lights run dry here.
+        for (entity, light, _) in (&entities, &mut causes_light, !&on_fires).join() {
+            if !light.lit {
+                continue;
+            }
+            if let Some(turns) = light.turns_remaining.as_mut() {
+                *turns = turns.saturating_sub(1);
+                if *turns == 0 {
+                    light.lit = false;
+                    dousables.remove(entity);
+                    lightables
+                        .insert(entity, Lightable {})
+                        .expect("could not insert lightable");
+                    let is_held_by_player = match player_equipment {
+                        Some(player_equipment) => {
+                            player_equipment.dominant_hand == Some(entity)
+                                || player_equipment.off_hand == Some(entity)
+                        }
+                        None => false,
+                    };
+                    if is_held_by_player {
+                        let item_name = match names.get(entity) {
+                            Some(name) => name.name.clone(),
+                            _ => "light".to_string(),
+                        };
+                        game_log.add(format!("Your {} burns out.", item_name));
+                    }
+                }
+            }
+        }
+    }
+}
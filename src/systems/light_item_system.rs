@@ -28,7 +28,15 @@ impl<'a> System<'a> for LightItemSystem {
         ) = data;
         for (intent, entity) in (&wants_to_light, &entities).join() {
             if let Some(light) = causes_light.get_mut(intent.item) {
-                if lightables.get(intent.item).is_some() {
+                if light.turns_remaining == Some(0) {
+                    if entity == *player_entity {
+                        let item_name = match names.get(intent.item) {
+                            Some(name) => name.name.clone(),
+                            _ => "unknown".to_string(),
+                        };
+                        game_log.add(format!("the {} has no fuel left", item_name));
+                    }
+                } else if lightables.get(intent.item).is_some() {
                     light.lit = true;
                     lightables.remove(intent.item);
                     dousables
@@ -1,20 +1,33 @@
 use crate::components::{CausesLight, Equipment, Position};
-use crate::dungeon::{constants::MAP_COUNT, dungeon::Dungeon, level_utils};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils};
+use rltk::RGB;
 use specs::{Entity, Join, ReadStorage, System, WriteExpect, WriteStorage};
 
-fn get_light_radius_from_equipment(
+const FIRE_LIGHT_RADIUS: u32 = 2;
+
+fn get_light_from_equipment(
     equipment: Entity,
     causes_light: &ReadStorage<CausesLight>,
-) -> u32 {
+) -> Option<(u32, RGB)> {
     match causes_light.get(equipment) {
-        Some(light) => match light.lit {
-            true => light.radius as u32,
-            false => 0,
-        },
-        None => 0,
+        Some(light) if light.lit => Some((light.get_current_radius() as u32, light.color)),
+        _ => None,
     }
 }
 
+// mixes a new light into whatever is already lighting the tile, brighter lights tint it more.
+fn add_light_to_tile(level: &mut Level, idx: usize, intensity: f32, color: RGB) {
+    let existing_intensity = level.lit_tiles[idx];
+    let existing_color = level.light_colors[idx];
+    let total = existing_intensity + intensity;
+    level.light_colors[idx] = RGB::from_f32(
+        (existing_color.r * existing_intensity + color.r * intensity) / total,
+        (existing_color.g * existing_intensity + color.g * intensity) / total,
+        (existing_color.b * existing_intensity + color.b * intensity) / total,
+    );
+    level.lit_tiles[idx] = f32::min(total, 1.0);
+}
+
 pub struct LightSystem {}
 
 impl<'a> System<'a> for LightSystem {
@@ -26,58 +39,65 @@ impl<'a> System<'a> for LightSystem {
     );
     fn run(&mut self, data: Self::SystemData) {
         let (mut dungeon, position, equipment, causes_light) = data;
-        dungeon
+        dungeon.levels.iter_mut().for_each(|(_number, level)| {
+            let count = level.tiles.len();
+            level.lit_tiles = vec![0.0; count].into_boxed_slice();
+            level.light_colors = vec![RGB::named(rltk::BLACK); count].into_boxed_slice();
+        });
+        // burning tiles light up their surroundings whether or not anything is burning on them.
+        let fire_lights: Vec<(u32, RGB, u8, usize)> = dungeon
             .levels
-            .iter_mut()
-            .for_each(|(_number, level)| level.lit_tiles = Box::new([false; MAP_COUNT]));
+            .iter()
+            .flat_map(|(number, level)| {
+                level
+                    .fire
+                    .iter()
+                    .enumerate()
+                    .filter(|(_idx, fire)| **fire > 0)
+                    .map(move |(idx, _fire)| {
+                        (FIRE_LIGHT_RADIUS, RGB::named(rltk::ORANGE), *number, idx)
+                    })
+            })
+            .collect();
 
         (&equipment, &position)
             .join()
-            .map(|(equipment, position)| {
-                let light_radius = {
-                    let dominant_hand_light_range = match equipment.dominant_hand {
-                        Some(item) => get_light_radius_from_equipment(item, &causes_light),
-                        _ => 0,
-                    };
-                    let off_hand_light_range = match equipment.off_hand {
-                        Some(item) => get_light_radius_from_equipment(item, &causes_light),
-                        _ => 0,
-                    };
-                    i32::max(
-                        dominant_hand_light_range as i32,
-                        off_hand_light_range as i32,
-                    )
-                };
-                (light_radius as i32, position.level as u8, position.idx as i32)
+            .filter_map(|(equipment, position)| {
+                // only the brightest of the lights being held is used.
+                let light = [equipment.dominant_hand, equipment.off_hand]
+                    .iter()
+                    .filter_map(|item| *item)
+                    .filter_map(|item| get_light_from_equipment(item, &causes_light))
+                    .max_by_key(|(radius, _color)| *radius);
+                light.map(|(radius, color)| (radius, color, position.level, position.idx))
             })
             .chain(
                 (&position, &causes_light)
                     .join()
                     .filter(|(_, causes_light)| causes_light.lit)
                     .map(|(position, causes_light)| {
-                        (causes_light.radius as i32, position.level as u8, position.idx as i32)
+                        (
+                            causes_light.get_current_radius() as u32,
+                            causes_light.color,
+                            position.level,
+                            position.idx,
+                        )
                     }),
             )
-            .for_each(|(radius, level, pos_idx)| {
+            .chain(fire_lights.into_iter())
+            .filter(|(radius, _color, _level, _idx)| *radius > 0)
+            .for_each(|(radius, color, level, pos_idx)| {
                 let level = dungeon.get_level_mut(level).unwrap();
-                let level_width = level.width as i32;
-                let lit_points = (-radius..=radius)
-                    .map(|y| {
-                        (-radius..=radius)
-                            .map(move |x| level_utils::add_xy_to_idx(level_width, x, y, pos_idx))
-                    })
-                    .flatten()
-                    .filter(|idx| {
-                        level_utils::get_distance_between_idxs(level, pos_idx as usize, *idx as usize)
-                            < radius as f32
-                    })
-                    .collect::<Vec<i32>>();
+                // lights use field of view so that walls cast shadows.
+                let lit_points = level_utils::get_field_of_view_from_idx(level, pos_idx, radius);
                 for index in lit_points {
-                    match level.lit_tiles.get_mut(index as usize) {
-                        Some(i) => {
-                            *i = true;
-                        }
-                        _ => {}
+                    if index >= level.lit_tiles.len() {
+                        continue;
+                    }
+                    let distance = level_utils::get_distance_between_idxs(level, pos_idx, index);
+                    if distance < radius as f32 {
+                        let intensity = 1.0 - distance / radius as f32;
+                        add_light_to_tile(level, index, intensity, color);
                     }
                 }
             });
@@ -0,0 +1,315 @@
+use crate::components::{
+    causes_damage::DamageType, door::DoorState, viewshed, Activated, CombatStats, DamageHistory,
+    Door, Mechanism, Memory, Monster, Position, Renderable, SufferDamage, Triggered, Viewshed,
+};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, tile_type::TileType};
+use crate::noise::make_noise;
+use crate::services::{DebrisSpawner, GameLog, ParticleEffectSpawner};
+use crate::types::MechanismType;
+use rltk::{to_cp437, RandomNumberGenerator, BLACK, GREY, RGB, WHITE, YELLOW};
+use specs::{
+    storage::GenericWriteStorage, Entities, Entity, Join, ReadExpect, ReadStorage, System,
+    WriteExpect, WriteStorage,
+};
+use std::collections::HashSet;
+
+const DART_MIN_DAMAGE: i32 = 2;
+const DART_MAX_DAMAGE: i32 = 6;
+const PORTCULLIS_DAMAGE: i32 = 8;
+const ALARM_NOISE_RADIUS: f32 = 30.0;
+const COLLAPSE_RADIUS: f32 = 1.5;
+const FALL_DAMAGE: i32 = 5;
+const FALL_LANDING_RADIUS: u32 = 3;
+
+fn stops_dart(level: &Level, idx: usize) -> bool {
+    match level.tiles[idx] {
+        TileType::Wall | TileType::Column | TileType::Door => true,
+        _ => false,
+    }
+}
+
+fn deal_damage(
+    entity: Entity,
+    amount: i32,
+    damage_type: DamageType,
+    suffer_damage: &mut WriteStorage<SufferDamage>,
+    damage_histories: &mut WriteStorage<DamageHistory>,
+) {
+    if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(entity) {
+        damage_to_suffer.amount += amount;
+    }
+    if let Some(damage_history) = damage_histories.get_mut(entity) {
+        damage_history.events.insert(damage_type);
+    }
+}
+
+// whatever falls through lands on the same spot below, or as near to it as there is room.
+fn get_landing_idx(
+    upper_level: &Level,
+    lower_level: &Level,
+    idx: usize,
+    rng: &mut RandomNumberGenerator,
+) -> Option<usize> {
+    let (x, y) = level_utils::idx_xy(upper_level.width as u32, idx);
+    if x >= lower_level.width as i32 || y >= lower_level.height as i32 {
+        return level_utils::get_random_unblocked_floor_point(lower_level, rng);
+    }
+    let below_idx = level_utils::xy_idx(lower_level.width as u32, x, y);
+    if lower_level.tiles[below_idx] == TileType::Floor && !lower_level.blocked[below_idx] {
+        return Some(below_idx);
+    }
+    let nearby =
+        level_utils::get_all_spawnable_tiles_in_radius(lower_level, below_idx, FALL_LANDING_RADIUS);
+    match rng.random_slice_entry(&nearby) {
+        Some(landing_idx) => Some(*landing_idx),
+        None => level_utils::get_random_unblocked_floor_point(lower_level, rng),
+    }
+}
+
+pub struct MechanismSystem {}
+
+impl<'a> System<'a> for MechanismSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteExpect<'a, DebrisSpawner>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteStorage<'a, Activated>,
+        ReadStorage<'a, Mechanism>,
+        WriteStorage<'a, Position>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, DamageHistory>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, Door>,
+        WriteStorage<'a, Renderable>,
+        WriteStorage<'a, Triggered>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Memory>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut dungeon,
+            mut particle_spawner,
+            mut debris_spawner,
+            mut rng,
+            mut activated,
+            mechanisms,
+            mut positions,
+            combat_stats,
+            mut suffer_damage,
+            mut damage_histories,
+            mut viewsheds,
+            mut doors,
+            mut renderables,
+            mut triggered,
+            monsters,
+            mut memories,
+        ) = data;
+        let activations: Vec<(Entity, Entity, MechanismType)> =
+            (&entities, &activated, &mechanisms)
+                .join()
+                .map(|(entity, activation, mechanism)| {
+                    (entity, activation.by, mechanism.mechanism_type)
+                })
+                .collect();
+        activated.clear();
+
+        let player_level = positions.get(*player_entity).unwrap().level;
+        let mut levels_with_changed_sight = HashSet::new();
+        for (mechanism, actor, mechanism_type) in activations {
+            let mechanism_position = match positions.get(mechanism) {
+                Some(position) => position.clone(),
+                None => continue,
+            };
+            let level_number = mechanism_position.level;
+            match mechanism_type {
+                MechanismType::DartLauncher => {
+                    // the dart flies from the wall towards whoever stepped on the plate and
+                    // hits the first thing in its way.
+                    let target_idx = match positions.get(actor) {
+                        Some(position) if position.level == level_number => position.idx,
+                        _ => continue,
+                    };
+                    let level = dungeon.get_level(level_number).unwrap();
+                    let path = level_utils::get_line_between_idxs(
+                        level,
+                        mechanism_position.idx,
+                        target_idx,
+                    );
+                    for idx in path {
+                        if stops_dart(level, idx) {
+                            break;
+                        }
+                        particle_spawner.request(
+                            idx,
+                            RGB::named(WHITE),
+                            RGB::named(BLACK),
+                            to_cp437('-'),
+                            100.0,
+                            level_number,
+                        );
+                        let hit = (&entities, &positions, &combat_stats)
+                            .join()
+                            .find(|(_, position, _)| {
+                                position.level == level_number && position.idx == idx
+                            })
+                            .map(|(entity, _, _)| entity);
+                        if let Some(hit) = hit {
+                            let damage = rng.range(DART_MIN_DAMAGE, DART_MAX_DAMAGE + 1);
+                            deal_damage(
+                                hit,
+                                damage,
+                                DamageType::Pierce,
+                                &mut suffer_damage,
+                                &mut damage_histories,
+                            );
+                            if hit == *player_entity {
+                                log.add(format!("A dart strikes you for {} damage", damage));
+                            }
+                            break;
+                        }
+                    }
+                }
+                MechanismType::Portcullis => {
+                    let is_open = match doors.get(mechanism) {
+                        Some(door) => door.state == DoorState::Opened,
+                        None => false,
+                    };
+                    if !is_open {
+                        continue;
+                    }
+                    let beneath: Vec<Entity> = (&entities, &positions, &combat_stats)
+                        .join()
+                        .filter(|(_, position, _)| {
+                            position.level == level_number && position.idx == mechanism_position.idx
+                        })
+                        .map(|(entity, _, _)| entity)
+                        .collect();
+                    if beneath.len() > 0 {
+                        // anything standing in the doorway takes the weight of the gate and
+                        // keeps it from closing.
+                        for entity in beneath {
+                            deal_damage(
+                                entity,
+                                PORTCULLIS_DAMAGE,
+                                DamageType::Crush,
+                                &mut suffer_damage,
+                                &mut damage_histories,
+                            );
+                        }
+                        if level_number == player_level {
+                            log.add("A portcullis crashes down on something!".to_string());
+                        }
+                        continue;
+                    }
+                    if let Some(door) = doors.get_mut(mechanism) {
+                        door.state = DoorState::Closed;
+                    }
+                    if let Some(renderable) = renderables.get_mut(mechanism) {
+                        renderable.fg = RGB::named(GREY);
+                    }
+                    let mut level = dungeon.get_level_mut(level_number).unwrap();
+                    level_utils::set_tile_to_door(&mut level, mechanism_position.idx);
+                    level.blocked[mechanism_position.idx] = true;
+                    level.opaque[mechanism_position.idx] = true;
+                    levels_with_changed_sight.insert(level_number);
+                    triggered
+                        .insert(mechanism, Triggered {})
+                        .expect("could not insert triggered for portcullis");
+                    if level_number == player_level {
+                        log.add("A portcullis slams shut!".to_string());
+                    }
+                }
+                MechanismType::AlarmBell => {
+                    let level = dungeon.get_level(level_number).unwrap();
+                    particle_spawner.request(
+                        mechanism_position.idx,
+                        RGB::named(YELLOW),
+                        RGB::named(BLACK),
+                        to_cp437('!'),
+                        300.0,
+                        level_number,
+                    );
+                    // every goblin in earshot comes to see what set the bell off.
+                    make_noise(
+                        level,
+                        mechanism_position.idx,
+                        ALARM_NOISE_RADIUS,
+                        &positions,
+                        &monsters,
+                        &mut memories,
+                        None,
+                    );
+                    if level_number == player_level {
+                        log.add("An alarm bell rings out!".to_string());
+                    }
+                }
+                MechanismType::CollapsingFloor => {
+                    let lower_level_number = match dungeon.get_level_below(level_number) {
+                        Some(lower_level_number) => lower_level_number,
+                        None => continue,
+                    };
+                    let (upper_level, lower_level) = match (
+                        dungeon.get_level(level_number),
+                        dungeon.get_level(lower_level_number),
+                    ) {
+                        (Some(upper), Some(lower)) => (upper, lower),
+                        _ => continue,
+                    };
+                    let falling: Vec<(Entity, usize)> = (&entities, &positions, &viewsheds)
+                        .join()
+                        .filter(|(_, position, _)| {
+                            position.level == level_number
+                                && level_utils::get_distance_between_idxs(
+                                    upper_level,
+                                    position.idx,
+                                    mechanism_position.idx,
+                                ) <= COLLAPSE_RADIUS
+                        })
+                        .map(|(entity, position, _)| (entity, position.idx))
+                        .collect();
+                    for (entity, idx) in falling {
+                        let landing_idx =
+                            match get_landing_idx(upper_level, lower_level, idx, &mut rng) {
+                                Some(landing_idx) => landing_idx,
+                                None => continue,
+                            };
+                        if let Some(position) = positions.get_mut(entity) {
+                            position.level = lower_level_number;
+                            position.idx = landing_idx;
+                        }
+                        if let Some(viewshed) = viewsheds.get_mut(entity) {
+                            viewshed.dirty = true;
+                        }
+                        deal_damage(
+                            entity,
+                            FALL_DAMAGE,
+                            DamageType::Crush,
+                            &mut suffer_damage,
+                            &mut damage_histories,
+                        );
+                        debris_spawner.request_rubble(landing_idx, lower_level_number);
+                        if entity == *player_entity {
+                            log.add("The floor gives way beneath you!".to_string());
+                        }
+                    }
+                    if level_number == player_level {
+                        log.add("The floor collapses with a roar!".to_string());
+                    }
+                    entities
+                        .delete(mechanism)
+                        .expect("couldn't delete collapsed floor");
+                }
+            }
+        }
+        viewshed::mark_dirty_on_levels(&levels_with_changed_sight, &positions, &mut viewsheds);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::components::{
-    causes_damage::DamageType, CausesDamage, CombatStats, DamageHistory, Equipment, Name, Position,
-    SufferDamage, Viewshed, WantsToMelee,
+    causes_damage::DamageType, CausesDamage, CausesLight, CombatStats, DamageHistory, Equipment,
+    Name, Position, ProvidesBlock, SufferDamage, Viewshed, WantsToMelee,
 };
 use crate::services::{GameLog, ParticleEffectSpawner};
 use rltk::RandomNumberGenerator;
@@ -9,6 +9,10 @@ use specs::{
     WriteExpect, WriteStorage,
 };
 
+const IMPROVISED_DAMAGE_MIN: i32 = 1;
+const IMPROVISED_DAMAGE_MAX: i32 = 2;
+const BURNING_IMPROVISED_DAMAGE_MAX: i32 = 4;
+
 fn format_damage_text(attacker: &str, target: &str, weapon: &str, damage: i32) -> String {
     format!(
         "{} hits {} with {}, for {} hp",
@@ -20,6 +24,75 @@ fn format_no_damage_text(attacker: &str, target: &str, weapon: &str) -> String {
     format!("{} is unable to hurt {} with {}", attacker, target, weapon)
 }
 
+fn format_block_text(target: &str, attacker: &str, shield: &str) -> String {
+    format!("{} blocks {}'s attack with {}", target, attacker, shield)
+}
+
+fn fist_damage() -> CausesDamage {
+    CausesDamage {
+        min: 0,
+        max: 0,
+        bonus: 0,
+        damage_type: Box::new([DamageType::Blunt]),
+    }
+}
+
+// Anything held in a hand can be swung at someone. Weapons use their own damage, lit light
+// sources burn, shields are used to block rather than attack, and other items in the dominant
+// hand are used as a crude club. An empty dominant hand is a fist.
+fn get_attack_for_hand(
+    held: Option<Entity>,
+    is_dominant_hand: bool,
+    names: &ReadStorage<Name>,
+    causes_damage: &ReadStorage<CausesDamage>,
+    causes_light: &ReadStorage<CausesLight>,
+    provides_block: &ReadStorage<ProvidesBlock>,
+) -> Option<(String, CausesDamage)> {
+    match held {
+        Some(item) => {
+            let name = match names.get(item) {
+                Some(name) => name.name.clone(),
+                None => String::from("something"),
+            };
+            if let Some(damage) = causes_damage.get(item) {
+                return Some((name, damage.clone()));
+            }
+            if provides_block.get(item).is_some() {
+                return None;
+            }
+            if let Some(light) = causes_light.get(item) {
+                if light.lit {
+                    return Some((
+                        name,
+                        CausesDamage {
+                            min: IMPROVISED_DAMAGE_MIN,
+                            max: BURNING_IMPROVISED_DAMAGE_MAX,
+                            bonus: 0,
+                            damage_type: Box::new([DamageType::Burn]),
+                        },
+                    ));
+                }
+            }
+            match is_dominant_hand {
+                true => Some((
+                    name,
+                    CausesDamage {
+                        min: IMPROVISED_DAMAGE_MIN,
+                        max: IMPROVISED_DAMAGE_MAX,
+                        bonus: 0,
+                        damage_type: Box::new([DamageType::Blunt]),
+                    },
+                )),
+                false => None,
+            }
+        }
+        None => match is_dominant_hand {
+            true => Some((String::from("fist"), fist_damage())),
+            false => None,
+        },
+    }
+}
+
 pub struct MeleeCombatSystem {}
 
 impl<'a> System<'a> for MeleeCombatSystem {
@@ -31,6 +104,8 @@ impl<'a> System<'a> for MeleeCombatSystem {
         ReadStorage<'a, CombatStats>,
         ReadStorage<'a, Equipment>,
         ReadStorage<'a, CausesDamage>,
+        ReadStorage<'a, CausesLight>,
+        ReadStorage<'a, ProvidesBlock>,
         WriteExpect<'a, GameLog>,
         WriteExpect<'a, ParticleEffectSpawner>,
         ReadStorage<'a, Position>,
@@ -49,6 +124,8 @@ impl<'a> System<'a> for MeleeCombatSystem {
             combat_stats,
             equipment,
             causes_damage,
+            causes_light,
+            provides_block,
             mut log,
             mut particle_effect_spawner,
             positions,
@@ -58,7 +135,7 @@ impl<'a> System<'a> for MeleeCombatSystem {
             mut rng,
         ) = data;
         let player_viewshed = viewsheds.get(*player_ent).unwrap();
-        for (entity, wants_to_melee, name, stats, equipment) in (
+        for (entity, wants_to_melee, name, stats, attacker_equipment) in (
             &entities,
             &wants_to_melee,
             &names,
@@ -75,86 +152,84 @@ impl<'a> System<'a> for MeleeCombatSystem {
                     let position = positions.get(entity).unwrap();
                     let in_player_sight = player_viewshed.visible_tiles.contains(&position.idx);
                     particle_effect_spawner.request_attack_particle(position.idx, position.level);
-                    // dominant hand attack
-                    let (dominant_weapon_name, dominant_weapon_damage) =
-                        match equipment.dominant_hand {
-                            Some(e) => (names.get(e), causes_damage.get(e)),
-                            _ => (None, None),
-                        };
-                    let dominant_hand_damage_dealt = match dominant_weapon_damage {
-                        Some(damage) => i32::max(
-                            0,
-                            rng.range(damage.min, damage.max + 1) + damage.bonus + stats.power
-                                - target_stats.defense,
-                        ),
-                        None => i32::max(0, stats.power - target_stats.defense), // this could be wrong, what if the hand holds a shield or torch?
+                    let target_shield = match equipment.get(wants_to_melee.target) {
+                        Some(target_equipment) => match target_equipment.off_hand {
+                            Some(off_hand) => match provides_block.get(off_hand) {
+                                Some(block) => Some((off_hand, block.chance)),
+                                None => None,
+                            },
+                            None => None,
+                        },
+                        None => None,
                     };
-                    let dominant_weapon_name = match dominant_weapon_name {
-                        Some(name) => &name.name,
-                        None => "fist",
-                    };
-                    if dominant_hand_damage_dealt == 0 && in_player_sight {
-                        log.add(format_no_damage_text(
-                            &name.name,
-                            &target_name.name,
-                            dominant_weapon_name,
-                        ));
-                    } else {
-                        total_damage += dominant_hand_damage_dealt;
-                        let dominant_weapon_damage_type = match dominant_weapon_damage {
-                            Some(d) => rng.random_slice_entry(&d.damage_type).unwrap().clone(),
-                            None => DamageType::Blunt,
-                        };
-                        let target_damage_history = damage_histories.get_mut(wants_to_melee.target);
-                        if let Some(history) = target_damage_history {
-                            history.events.insert(dominant_weapon_damage_type);
-                        }
-                        if in_player_sight {
-                            log.add(format_damage_text(
-                                &name.name,
-                                &target_name.name,
-                                dominant_weapon_name,
-                                dominant_hand_damage_dealt,
-                            ));
+                    let mut attacks = vec![];
+                    if let Some(attack) = get_attack_for_hand(
+                        attacker_equipment.dominant_hand,
+                        true,
+                        &names,
+                        &causes_damage,
+                        &causes_light,
+                        &provides_block,
+                    ) {
+                        attacks.push(attack);
+                    }
+                    // a two handed item fills both hands but is only swung once
+                    if attacker_equipment.off_hand != attacker_equipment.dominant_hand {
+                        if let Some(attack) = get_attack_for_hand(
+                            attacker_equipment.off_hand,
+                            false,
+                            &names,
+                            &causes_damage,
+                            &causes_light,
+                            &provides_block,
+                        ) {
+                            attacks.push(attack);
                         }
                     }
-                    // off hand attack
-                    let off_weapon_damage = match equipment.off_hand {
-                        Some(e) => causes_damage.get(e),
-                        _ => None,
-                    };
-                    if let Some(damage) = off_weapon_damage {
-                        let off_hand_damage_dealt = i32::max(
+                    for (weapon_name, damage) in attacks.iter() {
+                        if let Some((shield, chance)) = target_shield {
+                            if rng.range(0, 100) < chance {
+                                if in_player_sight {
+                                    let shield_name = match names.get(shield) {
+                                        Some(name) => name.name.clone(),
+                                        None => String::from("a shield"),
+                                    };
+                                    log.add(format_block_text(
+                                        &target_name.name,
+                                        &name.name,
+                                        &shield_name,
+                                    ));
+                                }
+                                continue;
+                            }
+                        }
+                        let damage_dealt = i32::max(
                             0,
                             rng.range(damage.min, damage.max + 1) + damage.bonus + stats.power
                                 - target_stats.defense,
                         );
-                        let off_weapon_name = &names.get(equipment.off_hand.unwrap()).unwrap().name;
-                        if off_hand_damage_dealt == 0 && in_player_sight {
-                            log.add(format_no_damage_text(
-                                &name.name,
-                                &target_name.name,
-                                off_weapon_name,
-                            ));
-                        } else {
-                            total_damage += off_hand_damage_dealt;
-                            let off_weapon_damage_type = match off_weapon_damage {
-                                Some(d) => rng.random_slice_entry(&d.damage_type).unwrap().clone(),
-                                None => DamageType::Blunt,
-                            };
-                            let target_damage_history =
-                                damage_histories.get_mut(wants_to_melee.target);
-                            if let Some(history) = target_damage_history {
-                                history.events.insert(off_weapon_damage_type);
-                            }
+                        if damage_dealt == 0 {
                             if in_player_sight {
-                                log.add(format_damage_text(
+                                log.add(format_no_damage_text(
                                     &name.name,
                                     &target_name.name,
-                                    off_weapon_name,
-                                    off_hand_damage_dealt,
+                                    weapon_name,
                                 ));
                             }
+                            continue;
+                        }
+                        total_damage += damage_dealt;
+                        let damage_type = rng.random_slice_entry(&damage.damage_type).unwrap();
+                        if let Some(history) = damage_histories.get_mut(wants_to_melee.target) {
+                            history.events.insert(*damage_type);
+                        }
+                        if in_player_sight {
+                            log.add(format_damage_text(
+                                &name.name,
+                                &target_name.name,
+                                weapon_name,
+                                damage_dealt,
+                            ));
                         }
                     }
                     if total_damage > 0 {
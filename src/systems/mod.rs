@@ -1,4 +1,3 @@
-pub mod blood_spawn_system;
 pub mod close_door_system;
 pub mod corpse_spawn_system;
 pub mod damage_system;
@@ -35,7 +34,23 @@ pub mod update_particle_effects_system;
 pub mod use_item_system;
 pub mod visibility_system;
 pub mod memory_cull_system;
-pub use blood_spawn_system::BloodSpawnSystem;
+pub mod stack_spawn_system;
+pub mod ranged_combat_system;
+pub mod throw_system;
+pub mod encumbrance_system;
+pub mod study_system;
+pub mod light_fuel_system;
+pub mod refuel_system;
+pub mod surface_spawn_system;
+pub mod surface_system;
+pub mod smoke_system;
+pub mod smash_system;
+pub mod terrain_damage_system;
+pub mod cave_in_system;
+pub mod explosion_system;
+pub mod mechanism_system;
+pub mod pick_lock_system;
+pub mod fall_system;
 pub use close_door_system::CloseDoorSystem;
 pub use corpse_spawn_system::CorpseSpawnSystem;
 pub use damage_system::DamageSystem;
@@ -71,4 +86,21 @@ pub use update_memories_system::UpdateMemoriesSystem;
 pub use update_particle_effects_system::UpdateParticleEffectsSystem;
 pub use use_item_system::UseItemSystem;
 pub use visibility_system::VisibilitySystem;
-pub use memory_cull_system::MemoryCullSystem;
\ No newline at end of file
+pub use memory_cull_system::MemoryCullSystem;
+pub use stack_spawn_system::StackSpawnSystem;
+pub use ranged_combat_system::RangedCombatSystem;
+pub use throw_system::ThrowSystem;
+pub use encumbrance_system::EncumbranceSystem;
+pub use study_system::StudySystem;
+pub use light_fuel_system::LightFuelSystem;
+pub use refuel_system::RefuelSystem;
+pub use surface_spawn_system::SurfaceSpawnSystem;
+pub use surface_system::SurfaceSystem;
+pub use smoke_system::SmokeSystem;
+pub use smash_system::SmashSystem;
+pub use terrain_damage_system::TerrainDamageSystem;
+pub use cave_in_system::CaveInSystem;
+pub use explosion_system::ExplosionSystem;
+pub use mechanism_system::MechanismSystem;
+pub use pick_lock_system::PickLockSystem;
+pub use fall_system::FallSystem;
\ No newline at end of file
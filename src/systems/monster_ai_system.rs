@@ -1,8 +1,11 @@
 use crate::components::{
-    CombatStats, Confused, Door, Furniture, Hiding, Memory, Monster, Position, Viewshed,
+    monster::MonsterSpecies, Ammunition, CombatStats, Confused, Door, Encumbered, Equipment,
+    Furniture, Hiding, Inventory, Launcher, Memory, Monster, Position, Viewshed, WantsToFire,
     WantsToMelee, WantsToMove, WantsToOpenDoor,
 };
-use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, tile_type::TileType};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, secrets, tile_type::TileType};
+use crate::perception;
+use crate::systems::ranged_combat_system::get_ammunition_for_launcher;
 use crate::{
     ai::{choose_action, reasoner, Action, WeightedAction},
     components::memory::MemoryLocation,
@@ -10,6 +13,16 @@ use crate::{
 use rltk::{a_star_search, RandomNumberGenerator};
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
+// bloody tracks are left in order, so following the newest one in sight leads along the trail.
+fn get_freshest_track(level: &Level, viewshed: &Viewshed, current_idx: usize) -> Option<usize> {
+    viewshed
+        .visible_tiles
+        .iter()
+        .cloned()
+        .filter(|idx| *idx < level.tracks.len() && level.tracks[*idx] > level.tracks[current_idx])
+        .max_by_key(|idx| level.tracks[*idx])
+}
+
 fn get_move_action(
     level: &Level,
     move_idx: usize,
@@ -43,8 +56,37 @@ fn get_move_action(
     )
 }
 
-fn get_next_step(level: &Level, start_idx: usize, end_idx: usize) -> Option<(usize, usize)> {
-    let path = a_star_search(start_idx as i32, end_idx as i32, level);
+// the range of the entity's launcher, if it has one in hand and something to fire from it
+fn get_fire_range(
+    entity: Entity,
+    equipment: &ReadStorage<Equipment>,
+    launchers: &ReadStorage<Launcher>,
+    inventories: &ReadStorage<Inventory>,
+    ammunition: &ReadStorage<Ammunition>,
+) -> Option<u32> {
+    let launcher_ent = equipment.get(entity)?.dominant_hand?;
+    let launcher = launchers.get(launcher_ent)?;
+    let inventory = inventories.get(entity)?;
+    get_ammunition_for_launcher(launcher_ent, launcher, inventory, ammunition)?;
+    Some(launcher.range)
+}
+
+// goblins live down here and know where the secret doors are, anyone else has to find another
+// way round.
+fn get_next_step(
+    level: &Level,
+    start_idx: usize,
+    end_idx: usize,
+    knows_secret_doors: bool,
+) -> Option<(usize, usize)> {
+    let path = match knows_secret_doors {
+        true => a_star_search(start_idx as i32, end_idx as i32, level),
+        false => a_star_search(
+            start_idx as i32,
+            end_idx as i32,
+            &secrets::WithoutSecretDoors { level },
+        ),
+    };
     let step_count = path.steps.len();
     match path.success && step_count > 1 {
         true => Some((path.steps[1], step_count)),
@@ -56,10 +98,11 @@ fn get_move_action_from_path(
     level: &Level,
     start_idx: usize,
     end_idx: usize,
+    knows_secret_doors: bool,
     furniture_storage: &ReadStorage<Furniture>,
     door_storage: &ReadStorage<Door>,
 ) -> Option<WeightedAction> {
-    match get_next_step(level, start_idx, end_idx) {
+    match get_next_step(level, start_idx, end_idx, knows_secret_doors) {
         Some((next_step, step_count)) => Some(get_move_action(
             level,
             next_step,
@@ -91,6 +134,12 @@ impl<'a> System<'a> for MonsterAI {
         ReadStorage<'a, Furniture>,
         ReadStorage<'a, Hiding>,
         ReadStorage<'a, Door>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, Launcher>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Ammunition>,
+        WriteStorage<'a, WantsToFire>,
+        WriteStorage<'a, Encumbered>,
     );
     // This is currently very limited. Monsters will only act if they can see a player, which means that they must
     // also be on the same level to act.
@@ -112,6 +161,12 @@ impl<'a> System<'a> for MonsterAI {
             furniture,
             hiding,
             doors,
+            equipment,
+            launchers,
+            inventories,
+            ammunition,
+            mut wants_to_fire,
+            mut encumbered,
         ) = data;
         let (player_idx, player_level) = {
             let pos = positions.get(*player_entity).unwrap();
@@ -120,7 +175,7 @@ impl<'a> System<'a> for MonsterAI {
         let level = dungeon.get_level_mut(player_level).unwrap();
         let player_hp = combat_stats.get(*player_entity).unwrap().hp;
 
-        for (_monsters, entity, viewshed, position, memory) in
+        for (monster, entity, viewshed, position, memory) in
             (&monsters, &entities, &viewsheds, &positions, &mut memory).join()
         {
             if let Some(is_confused) = confused.get_mut(entity) {
@@ -130,11 +185,21 @@ impl<'a> System<'a> for MonsterAI {
                 }
                 continue;
             }
+            if let Some(encumbered) = encumbered.get_mut(entity) {
+                if encumbered.turns_owed > 0 {
+                    encumbered.turns_owed -= 1;
+                    continue;
+                }
+            }
             if position.level != player_level {
                 continue;
             }
             let mut weighted_actions = vec![];
             let current_idx = position.idx;
+            let knows_secret_doors = match monster.species {
+                MonsterSpecies::Goblin => true,
+                _ => false,
+            };
             let player_is_not_hiding = hiding.get(*player_entity).is_none();
             let distance = level_utils::get_distance_between_idxs(&level, position.idx, player_idx);
             if player_is_not_hiding && distance < 1.5 {
@@ -142,9 +207,21 @@ impl<'a> System<'a> for MonsterAI {
                     Action::Attack(*player_entity),
                     reasoner::attack_weight(player_hp),
                 ));
-            } else if player_is_not_hiding && viewshed.visible_tiles.contains(&player_idx) {
+            } else if player_is_not_hiding
+                && perception::can_spot(&level, current_idx, &viewshed.visible_tiles, player_idx)
+            {
+                if let Some(range) =
+                    get_fire_range(entity, &equipment, &launchers, &inventories, &ammunition)
+                {
+                    if distance <= range as f32 {
+                        weighted_actions.push(WeightedAction::new(
+                            Action::Fire(player_idx),
+                            reasoner::attack_weight(player_hp),
+                        ));
+                    }
+                }
                 if let Some((next_step, step_count)) =
-                    get_next_step(&level, current_idx, player_idx)
+                    get_next_step(&level, current_idx, player_idx, knows_secret_doors)
                 {
                     weighted_actions.push(WeightedAction::new(
                         Action::Chase(next_step),
@@ -164,6 +241,7 @@ impl<'a> System<'a> for MonsterAI {
                         &level,
                         current_idx,
                         *enemy_idx,
+                        knows_secret_doors,
                         &furniture,
                         &doors,
                     ) {
@@ -186,7 +264,7 @@ impl<'a> System<'a> for MonsterAI {
                             ));
                         } else if viewshed.visible_tiles.contains(&hiding_idx) {
                             if let Some((next_step, step_count)) =
-                                get_next_step(&level, current_idx, player_idx)
+                                get_next_step(&level, current_idx, player_idx, knows_secret_doors)
                             {
                                 let hiding_place_hp = combat_stats.get(*hiding_spot).unwrap().hp;
                                 weighted_actions.push(WeightedAction::new(
@@ -198,15 +276,24 @@ impl<'a> System<'a> for MonsterAI {
                     }
                 }
             } else {
-                let destination_idx = match memory.wander_destination {
-                    Some(dest) => Some(dest.1),
-                    None => level_utils::get_random_unblocked_floor_point(&level, &mut rng),
+                let destination_idx = match (
+                    get_freshest_track(&level, viewshed, current_idx),
+                    memory.wander_destination,
+                ) {
+                    (Some(track_idx), _) => Some(track_idx),
+                    (None, Some(dest)) => Some(dest.1),
+                    (None, None) => level_utils::get_random_unblocked_floor_point(&level, &mut rng),
                 };
                 if let Some(idx) = destination_idx {
                     memory.wander_destination = Some(MemoryLocation(position.level as i32, idx));
-                    if let Some(action) =
-                        get_move_action_from_path(&level, current_idx, idx, &furniture, &doors)
-                    {
+                    if let Some(action) = get_move_action_from_path(
+                        &level,
+                        current_idx,
+                        idx,
+                        knows_secret_doors,
+                        &furniture,
+                        &doors,
+                    ) {
                         weighted_actions.push(action);
                     }
                 }
@@ -232,6 +319,11 @@ impl<'a> System<'a> for MonsterAI {
                         .insert(entity, WantsToOpenDoor { door })
                         .expect("couldn't insert open door intent");
                 }
+                Some(Action::Fire(target)) => {
+                    wants_to_fire
+                        .insert(entity, WantsToFire { target })
+                        .expect("couldn't insert fire intent");
+                }
                 _ => {}
             };
         }
@@ -1,8 +1,18 @@
 use crate::components::{
-    BlocksTile, EntityMoved, Grabbing, Hiding, Position, Viewshed, WantsToMove,
+    BlocksTile, Encumbered, EntityMoved, Grabbing, Hiding, Memory, Monster, Position, Viewshed,
+    WantsToMove,
 };
-use crate::dungeon::{dungeon::Dungeon, level_utils};
-use specs::{Entities, Join, ReadStorage, System, WriteExpect, WriteStorage};
+use crate::dungeon::{dungeon::Dungeon, level_utils, surface_type::SurfaceType};
+use crate::noise::make_noise;
+use crate::services::GameLog;
+use rltk::RandomNumberGenerator;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+const ENCUMBERED_NOISE_RADIUS: f32 = 6.0;
+// wading through standing water can't be done quietly either.
+const WADING_NOISE_RADIUS: f32 = 4.0;
+// one in this many steps taken from a patch of ice ends in a slip.
+const ICE_SLIP_CHANCE: i32 = 3;
 
 pub struct MoveSystem {}
 
@@ -17,6 +27,12 @@ impl<'a> System<'a> for MoveSystem {
         WriteStorage<'a, EntityMoved>,
         ReadStorage<'a, BlocksTile>,
         WriteStorage<'a, Hiding>,
+        WriteStorage<'a, Encumbered>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Memory>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -30,7 +46,14 @@ impl<'a> System<'a> for MoveSystem {
             mut moved,
             blocks_tiles,
             mut hidings,
+            mut encumbered,
+            monsters,
+            mut memories,
+            player_entity,
+            mut game_log,
+            mut rng,
         ) = data;
+        let mut noisy_moves: Vec<(Entity, u8, usize, f32)> = vec![];
 
         for (entity, wants_to_move, grabbing, viewshed) in (
             &entities,
@@ -45,6 +68,14 @@ impl<'a> System<'a> for MoveSystem {
                 (pos.idx, pos.level)
             };
             let level = dungeon.get_level_mut(ent_level).unwrap();
+            if level.surfaces[ent_idx] == Some(SurfaceType::Ice)
+                && rng.range(0, ICE_SLIP_CHANCE) == 0
+            {
+                if entity == *player_entity {
+                    game_log.add("You slip on the ice.".to_string());
+                }
+                continue;
+            }
             let delta = wants_to_move.idx - ent_idx;
             let ent_destination_idx = wants_to_move.idx;
             if let Some(grabbing) = grabbing {
@@ -81,8 +112,33 @@ impl<'a> System<'a> for MoveSystem {
                     .insert(entity, EntityMoved {})
                     .expect("unable to insert EntityMoved");
                 hidings.remove(entity);
+                // struggling along under a heavy load takes longer, and is hard to do quietly
+                if let Some(encumbered) = encumbered.get_mut(entity) {
+                    encumbered.turns_owed += 1;
+                    noisy_moves.push((
+                        entity,
+                        ent_level,
+                        ent_destination_idx,
+                        ENCUMBERED_NOISE_RADIUS,
+                    ));
+                }
+                if level.surfaces[ent_destination_idx] == Some(SurfaceType::Water) {
+                    noisy_moves.push((entity, ent_level, ent_destination_idx, WADING_NOISE_RADIUS));
+                }
             }
         }
+        for (noisy_ent, noise_level, noise_idx, noise_radius) in noisy_moves {
+            let level = dungeon.get_level(noise_level).unwrap();
+            make_noise(
+                level,
+                noise_idx,
+                noise_radius,
+                &positions,
+                &monsters,
+                &mut memories,
+                Some(noisy_ent),
+            );
+        }
         wants_to_moves.clear();
     }
 }
@@ -1,9 +1,20 @@
-use crate::components::{door::DoorState, Door, Position, Renderable, Viewshed, WantsToOpenDoor};
-use crate::dungeon::{dungeon::Dungeon, level_utils};
-use rltk::{BLACK, DARK_GRAY, RGB};
-use specs::{Join, ReadStorage, System, WriteExpect, WriteStorage};
+use crate::components::{
+    door::DoorState, viewshed, Door, Hidden, Inventory, Key, Memory, Monster, Name, Position,
+    Renderable, Triggered, Viewshed, WantsToOpenDoor,
+};
+use crate::dungeon::{dungeon::Dungeon, level_utils, locks, secrets};
+use crate::noise::make_noise;
+use crate::services::GameLog;
+use rltk::{RandomNumberGenerator, BLACK, DARK_GRAY, RGB};
+use specs::{
+    Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage,
+};
 use std::collections::HashSet;
 
+// a dropped portcullis is too heavy to lift in one go, but enough heaving gets it back up.
+const PORTCULLIS_LIFT_CHANCE: i32 = 4;
+const PORTCULLIS_NOISE_RADIUS: f32 = 8.0;
+
 pub struct OpenDoorSystem {}
 
 impl<'a> System<'a> for OpenDoorSystem {
@@ -14,6 +25,17 @@ impl<'a> System<'a> for OpenDoorSystem {
         WriteStorage<'a, Viewshed>,
         WriteStorage<'a, Door>,
         WriteStorage<'a, Renderable>,
+        WriteStorage<'a, Triggered>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Key>,
+        WriteStorage<'a, Hidden>,
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Memory>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -24,13 +46,91 @@ impl<'a> System<'a> for OpenDoorSystem {
             mut viewsheds,
             mut doors,
             mut renderables,
+            mut triggered,
+            names,
+            inventories,
+            keys,
+            mut hiddens,
+            entities,
+            player_entity,
+            mut log,
+            mut rng,
+            monsters,
+            mut memories,
         ) = data;
         let mut levels_with_door_open = HashSet::new();
-        for intent in (&wants_to_open_door).join() {
+        for (entity, intent) in (&entities, &wants_to_open_door).join() {
+            // a portcullis that has been dropped has to be heaved back up, which can be heard
+            // some way off.
+            if triggered.get(intent.door).is_some() {
+                let door_position = positions.get(intent.door).unwrap();
+                let level = dungeon.get_level(door_position.level).unwrap();
+                make_noise(
+                    level,
+                    door_position.idx,
+                    PORTCULLIS_NOISE_RADIUS,
+                    &positions,
+                    &monsters,
+                    &mut memories,
+                    Some(entity),
+                );
+                let lifted = rng.range(0, PORTCULLIS_LIFT_CHANCE) == 0;
+                if entity == *player_entity {
+                    if let Some(name) = names.get(intent.door) {
+                        match lifted {
+                            true => log.add(format!("You heave the {} back up.", name.name)),
+                            false => log.add(format!("You strain at the {}.", name.name)),
+                        }
+                    }
+                }
+                if !lifted {
+                    continue;
+                }
+                triggered.remove(intent.door);
+            }
+            let locked_with = match doors.get(intent.door) {
+                Some(door) => match door.state {
+                    DoorState::Locked(key_id) => Some(key_id),
+                    _ => None,
+                },
+                None => None,
+            };
+            if let Some(key_id) = locked_with {
+                // only the key cut for this lock will turn it.
+                let key = inventories.get(entity).and_then(|inventory| {
+                    inventory
+                        .items
+                        .iter()
+                        .find(|item| match keys.get(**item) {
+                            Some(key) => key.key_id == key_id,
+                            None => false,
+                        })
+                        .cloned()
+                });
+                match key {
+                    Some(key) => {
+                        if entity == *player_entity {
+                            if let Some(name) = names.get(key) {
+                                log.add(format!("You unlock the door with the {}.", name.name));
+                            }
+                        }
+                    }
+                    None => {
+                        if entity == *player_entity {
+                            log.add("The door is locked.".to_string());
+                        }
+                        continue;
+                    }
+                }
+            }
             if let Some(door) = doors.get_mut(intent.door) {
                 door.state = DoorState::Opened;
                 let door_position = positions.get(intent.door).unwrap();
                 let mut level = dungeon.get_level_mut(door_position.level as u8).unwrap();
+                locks::unlock_idx(&mut level, door_position.idx);
+                // a secret door standing open is no secret to anyone.
+                secrets::reveal_idx(&mut level, door_position.idx);
+                hiddens.remove(intent.door);
                 level_utils::set_tile_to_floor(&mut level, door_position.idx);
                 level.blocked[door_position.idx] = false;
                 level.opaque[door_position.idx] = false;
@@ -40,12 +140,7 @@ impl<'a> System<'a> for OpenDoorSystem {
                 door_renderable.fg = RGB::named(DARK_GRAY);
             }
         }
-        if levels_with_door_open.len() > 0 {
-            (&positions, &mut viewsheds)
-                .join()
-                .filter(|(p, _)| levels_with_door_open.contains(&p.level))
-                .for_each(|(_, v)| v.dirty = true);
-        }
+        viewshed::mark_dirty_on_levels(&levels_with_door_open, &positions, &mut viewsheds);
         wants_to_open_door.clear();
     }
 }
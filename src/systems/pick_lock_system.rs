@@ -0,0 +1,83 @@
+use crate::components::{door::DoorState, Door, Memory, Monster, Position, WantsToPickLock};
+use crate::dungeon::{dungeon::Dungeon, locks};
+use crate::noise::make_noise;
+use crate::services::GameLog;
+use rltk::RandomNumberGenerator;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+// most attempts fail, so getting through a lock means scraping away at it for a few turns.
+const PICK_LOCK_CHANCE: i32 = 3;
+const PICK_LOCK_NOISE_RADIUS: f32 = 6.0;
+
+pub struct PickLockSystem {}
+
+impl<'a> System<'a> for PickLockSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        WriteExpect<'a, RandomNumberGenerator>,
+        WriteStorage<'a, WantsToPickLock>,
+        WriteStorage<'a, Door>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Memory>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut dungeon,
+            mut rng,
+            mut wants_to_pick_lock,
+            mut doors,
+            positions,
+            monsters,
+            mut memories,
+        ) = data;
+        for (entity, intent) in (&entities, &wants_to_pick_lock).join() {
+            let door = match doors.get_mut(intent.door) {
+                Some(door) => door,
+                None => continue,
+            };
+            match door.state {
+                DoorState::Locked(_) => (),
+                _ => {
+                    if entity == *player_entity {
+                        log.add("That door isn't locked.".to_string());
+                    }
+                    continue;
+                }
+            }
+            let door_position = positions.get(intent.door).unwrap().clone();
+            let level = dungeon.get_level_mut(door_position.level).unwrap();
+
+            // the scraping of picks in the lock carries to anything close enough to hear it
+            make_noise(
+                level,
+                door_position.idx,
+                PICK_LOCK_NOISE_RADIUS,
+                &positions,
+                &monsters,
+                &mut memories,
+                Some(entity),
+            );
+
+            if rng.range(0, PICK_LOCK_CHANCE) != 0 {
+                if entity == *player_entity {
+                    log.add("You fail to pick the lock.".to_string());
+                }
+                continue;
+            }
+            door.state = DoorState::Closed;
+            locks::unlock_idx(level, door_position.idx);
+            if entity == *player_entity {
+                log.add("You pick the lock.".to_string());
+            }
+        }
+        wants_to_pick_lock.clear();
+    }
+}
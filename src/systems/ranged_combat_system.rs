@@ -0,0 +1,294 @@
+use crate::components::{
+    Ammunition, CausesDamage, CombatStats, DamageHistory, Equipment, Furniture, Inventory,
+    Launcher, Name, Position, ProvidesBlock, Stackable, SufferDamage, Viewshed, WantsToFire,
+};
+use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::services::{GameLog, ParticleEffectSpawner, StackSpawner};
+use rltk::{to_cp437, RandomNumberGenerator, BLACK, RGB, WHITE};
+use specs::{
+    storage::{GenericReadStorage, GenericWriteStorage},
+    Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage,
+};
+
+const HIT_CHANCE_LOSS_PER_TILE: i32 = 4;
+
+fn format_hit_text(attacker: &str, target: &str, ammunition: &str, damage: i32) -> String {
+    format!(
+        "{} hits {} with {}, for {} hp",
+        attacker, target, ammunition, damage
+    )
+}
+
+fn format_no_damage_text(attacker: &str, target: &str, ammunition: &str) -> String {
+    format!(
+        "{} is unable to hurt {} with {}",
+        attacker, target, ammunition
+    )
+}
+
+fn format_miss_text(attacker: &str, ammunition: &str) -> String {
+    format!("{}'s {} misses", attacker, ammunition)
+}
+
+fn format_block_text(target: &str, attacker: &str, shield: &str) -> String {
+    format!("{} blocks {}'s shot with {}", target, attacker, shield)
+}
+
+pub fn get_ammunition_for_launcher(
+    launcher_ent: Entity,
+    launcher: &Launcher,
+    inventory: &Inventory,
+    ammunition: &impl GenericReadStorage<Component = Ammunition>,
+) -> Option<Entity> {
+    let matches_launcher = |e: &Entity| match ammunition.get(*e) {
+        Some(a) => a.ammunition_type == launcher.ammunition_type,
+        None => false,
+    };
+    // thrown weapons are their own ammunition
+    if matches_launcher(&launcher_ent) {
+        return Some(launcher_ent);
+    }
+    inventory
+        .items
+        .iter()
+        .find(|e| matches_launcher(*e))
+        .cloned()
+}
+
+pub struct RangedCombatSystem {}
+
+impl<'a> System<'a> for RangedCombatSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToFire>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, Equipment>,
+        WriteStorage<'a, Inventory>,
+        ReadStorage<'a, Launcher>,
+        ReadStorage<'a, Ammunition>,
+        WriteStorage<'a, Stackable>,
+        ReadStorage<'a, CausesDamage>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, DamageHistory>,
+        ReadStorage<'a, ProvidesBlock>,
+        ReadStorage<'a, Furniture>,
+        ReadExpect<'a, Dungeon>,
+        ReadStorage<'a, Viewshed>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteExpect<'a, StackSpawner>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_ent,
+            mut log,
+            mut wants_to_fire,
+            names,
+            combat_stats,
+            mut equipment,
+            mut inventories,
+            launchers,
+            ammunition,
+            mut stackables,
+            causes_damage,
+            mut positions,
+            mut suffer_damage,
+            mut damage_histories,
+            provides_block,
+            furniture,
+            dungeon,
+            viewsheds,
+            mut particle_effect_spawner,
+            mut stack_spawner,
+            mut rng,
+        ) = data;
+        let player_viewshed = viewsheds.get(*player_ent).unwrap();
+        let shots: Vec<(Entity, usize)> = (&entities, &wants_to_fire)
+            .join()
+            .map(|(entity, intent)| (entity, intent.target))
+            .collect();
+        for (entity, target_idx) in shots {
+            let (shooter_idx, shooter_level) = match positions.get(entity) {
+                Some(position) => (position.idx, position.level),
+                None => continue,
+            };
+            let level = dungeon.get_level(shooter_level).unwrap();
+            let launcher_ent = match equipment.get(entity) {
+                Some(e) => e.dominant_hand,
+                None => None,
+            };
+            let (launcher_ent, launcher) = match launcher_ent {
+                Some(launcher_ent) => match launchers.get(launcher_ent) {
+                    Some(launcher) => (launcher_ent, launcher),
+                    None => continue,
+                },
+                None => continue,
+            };
+            let ammunition_ent = match inventories.get(entity) {
+                Some(inventory) => {
+                    get_ammunition_for_launcher(launcher_ent, launcher, inventory, &ammunition)
+                }
+                None => None,
+            };
+            let ammunition_ent = match ammunition_ent {
+                Some(ammunition_ent) => ammunition_ent,
+                None => {
+                    if entity == *player_ent {
+                        log.add("You have nothing to fire".to_string());
+                    }
+                    continue;
+                }
+            };
+            let attacker_name = &names.get(entity).unwrap().name;
+            let ammunition_name = &names.get(ammunition_ent).unwrap().name;
+            let in_player_sight = player_viewshed.visible_tiles.contains(&shooter_idx)
+                || player_viewshed.visible_tiles.contains(&target_idx);
+
+            // the projectile flies along a straight line until it hits something, is stopped by
+            // a wall or piece of furniture, or falls to the ground at the target tile.
+            let mut landing_idx = shooter_idx;
+            let mut hit = None;
+            for (step, idx) in level_utils::get_line_between_idxs(level, shooter_idx, target_idx)
+                .iter()
+                .enumerate()
+            {
+                if level.opaque[*idx] {
+                    break;
+                }
+                let target = level_utils::entities_at_idx(level, *idx)
+                    .into_iter()
+                    .find(|e| *e != entity && combat_stats.get(*e).is_some());
+                if let Some(target) = target {
+                    let chance = launcher.accuracy - HIT_CHANCE_LOSS_PER_TILE * (step as i32 + 1);
+                    if rng.range(0, 100) < chance {
+                        landing_idx = *idx;
+                        hit = Some(target);
+                        break;
+                    }
+                    if furniture.get(target).is_some() {
+                        break;
+                    }
+                } else if level.blocked[*idx] {
+                    break;
+                }
+                landing_idx = *idx;
+                particle_effect_spawner.request(
+                    *idx,
+                    RGB::named(WHITE),
+                    RGB::named(BLACK),
+                    to_cp437('*'),
+                    100.0,
+                    shooter_level,
+                );
+                if *idx == target_idx {
+                    break;
+                }
+            }
+
+            match hit {
+                Some(target) => {
+                    let target_name = &names.get(target).unwrap().name;
+                    particle_effect_spawner.request_attack_particle(landing_idx, shooter_level);
+                    let shield = match equipment.get(target) {
+                        Some(e) => match e.off_hand {
+                            Some(off_hand) => match provides_block.get(off_hand) {
+                                Some(block) => Some((off_hand, block.chance)),
+                                None => None,
+                            },
+                            None => None,
+                        },
+                        None => None,
+                    };
+                    let blocked_by = match shield {
+                        Some((shield, chance)) if rng.range(0, 100) < chance => Some(shield),
+                        _ => None,
+                    };
+                    if let Some(shield) = blocked_by {
+                        if in_player_sight {
+                            log.add(format_block_text(
+                                target_name,
+                                attacker_name,
+                                &names.get(shield).unwrap().name,
+                            ));
+                        }
+                    } else if let Some(damage) = causes_damage.get(ammunition_ent) {
+                        let target_defense = combat_stats.get(target).unwrap().defense;
+                        let damage_dealt = i32::max(
+                            0,
+                            rng.range(damage.min, damage.max + 1) + damage.bonus + launcher.bonus
+                                - target_defense,
+                        );
+                        if damage_dealt == 0 {
+                            if in_player_sight {
+                                log.add(format_no_damage_text(
+                                    attacker_name,
+                                    target_name,
+                                    ammunition_name,
+                                ));
+                            }
+                        } else {
+                            if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(target)
+                            {
+                                damage_to_suffer.amount += damage_dealt;
+                            }
+                            let damage_type = rng.random_slice_entry(&damage.damage_type).unwrap();
+                            if let Some(history) = damage_histories.get_mut(target) {
+                                history.events.insert(*damage_type);
+                            }
+                            if in_player_sight {
+                                log.add(format_hit_text(
+                                    attacker_name,
+                                    target_name,
+                                    ammunition_name,
+                                    damage_dealt,
+                                ));
+                            }
+                        }
+                    }
+                }
+                None => {
+                    if in_player_sight {
+                        log.add(format_miss_text(attacker_name, ammunition_name));
+                    }
+                }
+            }
+
+            // whatever was fired ends up on the floor where it landed, to be picked up again.
+            if let Some(stack) = stackables
+                .get_mut(ammunition_ent)
+                .filter(|s| s.quantity > 1)
+            {
+                stack.quantity -= 1;
+                stack_spawner.request(landing_idx, shooter_level, ammunition_ent);
+            } else {
+                if let Some(inventory) = inventories.get_mut(entity) {
+                    inventory.items.remove(&ammunition_ent);
+                }
+                if let Some(e) = equipment.get_mut(entity) {
+                    if e.dominant_hand == Some(ammunition_ent) {
+                        e.dominant_hand = None;
+                    }
+                    if e.off_hand == Some(ammunition_ent) {
+                        e.off_hand = None;
+                    }
+                }
+                positions
+                    .insert(
+                        ammunition_ent,
+                        Position {
+                            idx: landing_idx,
+                            level: shooter_level,
+                        },
+                    )
+                    .expect("failed inserting position for fired ammunition");
+            }
+        }
+        wants_to_fire.clear();
+    }
+}
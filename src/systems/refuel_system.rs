@@ -0,0 +1,75 @@
+use crate::components::{
+    CausesLight, Equipment, Fuel, Inventory, Name, Refillable, Stackable, WantsToRefuel,
+};
+use crate::inventory::consume_one;
+use crate::services::GameLog;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+pub struct RefuelSystem {}
+
+impl<'a> System<'a> for RefuelSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToRefuel>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, Fuel>,
+        ReadStorage<'a, Refillable>,
+        WriteStorage<'a, CausesLight>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut game_log,
+            mut wants_to_refuel,
+            names,
+            equipment,
+            fuels,
+            refillables,
+            mut causes_light,
+            mut inventories,
+            mut stackables,
+        ) = data;
+        for (intent, entity, equipment, inventory) in
+            (&wants_to_refuel, &entities, &equipment, &mut inventories).join()
+        {
+            let fuel = match fuels.get(intent.fuel) {
+                Some(fuel) => fuel,
+                None => continue,
+            };
+            let light_ent = [equipment.dominant_hand, equipment.off_hand]
+                .iter()
+                .filter_map(|e| *e)
+                .find(|e| refillables.get(*e).is_some());
+            let light_ent = match light_ent {
+                Some(light_ent) => light_ent,
+                None => {
+                    if entity == *player_entity {
+                        game_log.add("You aren't holding anything to refill.".to_string());
+                    }
+                    continue;
+                }
+            };
+            let capacity = refillables.get(light_ent).unwrap().capacity;
+            if let Some(light) = causes_light.get_mut(light_ent) {
+                let turns = light.turns_remaining.unwrap_or(0);
+                light.turns_remaining = Some(u32::min(capacity, turns + fuel.turns));
+            }
+            if entity == *player_entity {
+                let light_name = match names.get(light_ent) {
+                    Some(name) => name.name.clone(),
+                    _ => "light".to_string(),
+                };
+                game_log.add(format!("You refill the {}.", light_name));
+            }
+            consume_one(intent.fuel, inventory, &mut stackables, &entities);
+        }
+        wants_to_refuel.clear();
+    }
+}
@@ -1,6 +1,6 @@
 use crate::components::{Grabbable, Grabbing, Name, Position, WantsToReleaseGrabbed};
 use crate::services::GameLog;
-use crate::dungeon::dungeon::Dungeon;
+use crate::dungeon::{dungeon::Dungeon, level_utils};
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
 
 pub struct ReleaseSystem {}
@@ -39,11 +39,10 @@ impl<'a> System<'a> for ReleaseSystem {
                         let level = dungeon.get_level(ent_position.level).unwrap();
                         match positions.get(grabbing.thing) {
                             Some(pos) => {
-                                let neighbor_x =
-                                    pos.idx == ent_position.idx + 1 || pos.idx == ent_position.idx - 1;
-                                let neighbor_y =
-                                    pos.idx == ent_position.idx + level.width as usize || pos.idx == ent_position.idx - level.width as usize;
-                                !(neighbor_y || neighbor_x)
+                                let width = level.width as u32;
+                                let (x, y) = level_utils::idx_xy(width, ent_position.idx);
+                                let (thing_x, thing_y) = level_utils::idx_xy(width, pos.idx);
+                                (x - thing_x).abs() + (y - thing_y).abs() != 1
                             }
                             None => false,
                         }
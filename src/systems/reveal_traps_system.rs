@@ -1,4 +1,4 @@
-use crate::{components::{Position, Hidden, Name, Viewshed}, player::InteractionType};
+use crate::{components::{Door, Position, Hidden, Name, Viewshed}, player::InteractionType};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
 use crate::services::GameLog;
 use rltk::RandomNumberGenerator;
@@ -18,6 +18,7 @@ impl<'a> System<'a> for RevealTrapsSystem<'a> {
         WriteExpect<'a, RandomNumberGenerator>,
         ReadStorage<'a, Name>,
         WriteExpect<'a, GameLog>,
+        ReadStorage<'a, Door>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -30,6 +31,7 @@ impl<'a> System<'a> for RevealTrapsSystem<'a> {
             mut rng,
             names,
             mut log,
+            doors,
         ) = data;
         let dungeon_level = positions.get(*player_ent).unwrap();
         let level = dungeon.get_level(dungeon_level.level).unwrap();
@@ -39,6 +41,8 @@ impl<'a> System<'a> for RevealTrapsSystem<'a> {
             .iter()
             .map(|idx| level_utils::entities_at_idx(&level, *idx as usize))
             .flatten()
+            // secret doors only turn up when they're searched for.
+            .filter(|e| doors.get(*e).is_none())
             .for_each(|e| {
                 if let Some(this_hidden) = hidden.get_mut(e) {
                     if rng.roll_dice(1, 24) == 1 {
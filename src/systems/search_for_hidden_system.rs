@@ -1,5 +1,5 @@
-use crate::components::{Hidden, Name, Position, Viewshed, WantsToSearchHidden};
-use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::components::{Door, Hidden, Name, Position, Viewshed, WantsToSearchHidden};
+use crate::dungeon::{dungeon::Dungeon, level_utils, secrets};
 use crate::services::{GameLog, ParticleEffectSpawner};
 use rltk::RandomNumberGenerator;
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
@@ -12,13 +12,14 @@ impl<'a> System<'a> for SearchForHiddenSystem {
         WriteStorage<'a, Hidden>,
         WriteStorage<'a, WantsToSearchHidden>,
         WriteExpect<'a, RandomNumberGenerator>,
-        ReadExpect<'a, Dungeon>,
+        WriteExpect<'a, Dungeon>,
         ReadExpect<'a, Entity>,
         WriteExpect<'a, GameLog>,
         ReadStorage<'a, Name>,
         ReadStorage<'a, Position>,
         ReadStorage<'a, Viewshed>,
         WriteExpect<'a, ParticleEffectSpawner>,
+        ReadStorage<'a, Door>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -27,15 +28,17 @@ impl<'a> System<'a> for SearchForHiddenSystem {
             mut hiddens,
             mut wants_to_search_hiddens,
             mut rng,
-            dungeon,
+            mut dungeon,
             player_entity,
             mut log,
             names,
             positions,
             viewsheds,
             mut particle_effect_spawner,
+            doors,
         ) = data;
         let player_level = positions.get(*player_entity).unwrap().level;
+        let mut found_doors: Vec<(Entity, u8, usize)> = vec![];
 
         for (entity, position, viewshed, _wants_to_search) in (
             &entities,
@@ -65,6 +68,13 @@ impl<'a> System<'a> for SearchForHiddenSystem {
                                         let hidden_name = names.get(*hidden_entity).unwrap();
                                         log.add(format!("You spotted a {}", hidden_name.name));
                                     }
+                                    if is_player && doors.get(*hidden_entity).is_some() {
+                                        found_doors.push((
+                                            *hidden_entity,
+                                            position.level,
+                                            tile_idx as usize,
+                                        ));
+                                    }
                                 }
                                 _ => {
                                     if is_player {
@@ -82,6 +92,13 @@ impl<'a> System<'a> for SearchForHiddenSystem {
                     }
                 });
         }
+        // once the player knows where a secret door is it's just another door.
+        for (door, level, idx) in found_doors.iter() {
+            hiddens.remove(*door);
+            if let Some(level) = dungeon.get_level_mut(*level) {
+                secrets::reveal_idx(level, *idx);
+            }
+        }
         wants_to_search_hiddens.clear();
     }
 }
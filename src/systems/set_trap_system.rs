@@ -1,4 +1,5 @@
-use crate::components::{Consumable, Inventory, Position, Trap, WantsToTrap};
+use crate::components::{Consumable, Inventory, Position, Stackable, Trap, WantsToTrap};
+use crate::inventory::consume_one;
 use crate::services::{GameLog, TrapSpawner};
 use crate::types::trap_type;
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
@@ -16,6 +17,7 @@ impl<'a> System<'a> for SetTrapSystem {
         ReadStorage<'a, Position>,
         ReadStorage<'a, Consumable>,
         WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
     );
     fn run(&mut self, data: Self::SystemData) {
         let (
@@ -28,6 +30,7 @@ impl<'a> System<'a> for SetTrapSystem {
             positions,
             consumables,
             mut inventories,
+            mut stackables,
         ) = data;
         for (trapping_entity, trap_intent, position, inventory) in
             (&entities, &mut wants_to_traps, &positions, &mut inventories).join()
@@ -44,8 +47,7 @@ impl<'a> System<'a> for SetTrapSystem {
             }
 
             if let Some(_) = consumables.get(trap_intent.item) {
-                inventory.items.remove(&trap_intent.item);
-                entities.delete(trap_intent.item).expect("Delete Failed");
+                consume_one(trap_intent.item, inventory, &mut stackables, &entities);
             };
         }
         wants_to_traps.clear();
@@ -0,0 +1,97 @@
+use crate::components::{
+    causes_damage::DamageType, CausesDamage, CombatStats, Equipment, Name, Position, WantsToSmash,
+};
+use crate::services::{GameLog, ParticleEffectSpawner, TerrainDamage};
+use rltk::RandomNumberGenerator;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+// only something heavy swung with enough force can knock chunks out of stone.
+pub fn get_smashing_damage(
+    held: Option<Entity>,
+    causes_damage: &ReadStorage<CausesDamage>,
+) -> Option<CausesDamage> {
+    match held.and_then(|item| causes_damage.get(item)) {
+        Some(damage)
+            if damage
+                .damage_type
+                .iter()
+                .any(|t| *t == DamageType::Blunt || *t == DamageType::Crush) =>
+        {
+            Some(damage.clone())
+        }
+        _ => None,
+    }
+}
+
+pub struct SmashSystem {}
+
+impl<'a> System<'a> for SmashSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToSmash>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Equipment>,
+        ReadStorage<'a, CausesDamage>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Name>,
+        WriteExpect<'a, TerrainDamage>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteExpect<'a, RandomNumberGenerator>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut wants_to_smash,
+            positions,
+            equipment,
+            causes_damage,
+            combat_stats,
+            names,
+            mut terrain_damage,
+            mut particle_spawner,
+            mut rng,
+        ) = data;
+        for (entity, to_smash, position, equipment, stats) in (
+            &entities,
+            &wants_to_smash,
+            &positions,
+            &equipment,
+            &combat_stats,
+        )
+            .join()
+        {
+            let weapon = equipment.dominant_hand;
+            let weapon_name = match weapon.and_then(|w| names.get(w)) {
+                Some(name) => name.name.clone(),
+                None => String::from("fist"),
+            };
+            particle_spawner.request_attack_particle(to_smash.idx, position.level);
+            match get_smashing_damage(weapon, &causes_damage) {
+                Some(damage) => {
+                    let damage_dealt = i32::max(
+                        1,
+                        rng.range(damage.min, damage.max + 1) + damage.bonus + stats.power,
+                    );
+                    terrain_damage.request(to_smash.idx, position.level, damage_dealt);
+                    if entity == *player_entity {
+                        log.add(format!(
+                            "You strike the stone with your {}, for {} damage",
+                            weapon_name, damage_dealt
+                        ));
+                    }
+                }
+                None => {
+                    if entity == *player_entity {
+                        log.add(format!("You can't break stone with your {}", weapon_name));
+                    }
+                }
+            }
+        }
+        wants_to_smash.clear();
+    }
+}
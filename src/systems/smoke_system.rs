@@ -0,0 +1,116 @@
+use crate::components::{
+    causes_damage::DamageType, viewshed, CombatStats, DamageHistory, Position, SufferDamage,
+    Viewshed,
+};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, tile_type::TileType};
+use crate::services::GameLog;
+use specs::{
+    storage::GenericWriteStorage, Entities, Entity, Join, ReadExpect, ReadStorage, System,
+    WriteExpect, WriteStorage,
+};
+use std::collections::HashSet;
+
+const SMOKE_FROM_FIRE: u8 = 6;
+const MAX_SMOKE: u8 = 9;
+// smoke thins as it drifts, so it only spreads a few tiles from the fire feeding it.
+const SMOKE_SPREAD_LOSS: u8 = 2;
+const SMOKE_OPAQUE_DENSITY: u8 = 4;
+const SMOKE_CHOKE_DENSITY: u8 = 3;
+const SMOKE_DAMAGE: i32 = 1;
+
+fn blocks_smoke(level: &Level, idx: usize) -> bool {
+    match level.tiles[idx] {
+        TileType::Wall | TileType::Column | TileType::Door => true,
+        _ => false,
+    }
+}
+
+fn get_next_smoke(level: &Level) -> Box<[u8]> {
+    let mut next_smoke: Box<[u8]> = level
+        .smoke
+        .iter()
+        .map(|density| density.saturating_sub(1))
+        .collect();
+    for (idx, density) in level.smoke.iter().enumerate() {
+        if *density <= SMOKE_SPREAD_LOSS {
+            continue;
+        }
+        for neighbor in level_utils::get_neighbor_idxs(level, idx) {
+            if !blocks_smoke(level, neighbor) {
+                next_smoke[neighbor] = u8::max(next_smoke[neighbor], density - SMOKE_SPREAD_LOSS);
+            }
+        }
+    }
+    for (idx, fire) in level.fire.iter().enumerate() {
+        if *fire > 0 {
+            next_smoke[idx] = u8::min(next_smoke[idx] + SMOKE_FROM_FIRE, MAX_SMOKE);
+        }
+    }
+    next_smoke
+}
+
+pub struct SmokeSystem {}
+
+impl<'a> System<'a> for SmokeSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, SufferDamage>,
+        WriteStorage<'a, DamageHistory>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut game_log,
+            mut dungeon,
+            positions,
+            combat_stats,
+            mut viewsheds,
+            mut suffer_damage,
+            mut damage_histories,
+        ) = data;
+
+        let mut levels_with_changed_sight = HashSet::new();
+        for (level_number, level) in dungeon.levels.iter_mut() {
+            let next_smoke = get_next_smoke(level);
+            for (idx, density) in next_smoke.iter().enumerate() {
+                let was_thick = level.smoke[idx] >= SMOKE_OPAQUE_DENSITY;
+                let is_thick = *density >= SMOKE_OPAQUE_DENSITY;
+                if was_thick != is_thick {
+                    // thick smoke hides whatever is behind it, once it thins the tile is only as
+                    // opaque as whatever is built on it.
+                    level.opaque[idx] = is_thick || blocks_smoke(level, idx);
+                    levels_with_changed_sight.insert(*level_number);
+                }
+            }
+            level.smoke = next_smoke;
+        }
+        viewshed::mark_dirty_on_levels(&levels_with_changed_sight, &positions, &mut viewsheds);
+
+        // anything with eyes to sting and lungs to fill chokes on the thicker smoke.
+        for (entity, _combat_stats, _viewshed, position) in
+            (&entities, &combat_stats, &viewsheds, &positions).join()
+        {
+            let level = dungeon.get_level(position.level).unwrap();
+            if level.smoke[position.idx] < SMOKE_CHOKE_DENSITY {
+                continue;
+            }
+            if let Some(damage_to_suffer) = suffer_damage.get_mut_or_default(entity) {
+                damage_to_suffer.amount += SMOKE_DAMAGE;
+            }
+            if let Some(damage_history) = damage_histories.get_mut(entity) {
+                damage_history.events.insert(DamageType::Choke);
+            }
+            if entity == *player_entity {
+                game_log.add("You choke on the smoke.".to_string());
+            }
+        }
+    }
+}
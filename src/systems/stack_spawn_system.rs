@@ -0,0 +1,150 @@
+use crate::components::{
+    Ammunition, AreaOfEffect, Armable, CausesDamage, CausesFire, CausesLight, Confusion,
+    Consumable, Equipable, Explosive, Flammable, Fuel, Identifies, Info, Inventory, Item, Launcher,
+    Name, Position, Potion, ProvidesHealing, Ranged, Renderable, Saveable, Stackable, Trap, Weight,
+};
+use crate::services::{stack_spawner::StackDestination, StackSpawner};
+use specs::{
+    saveload::{MarkerAllocator, SimpleMarker, SimpleMarkerAllocator},
+    Component, Entities, Entity, System, WriteExpect, WriteStorage,
+};
+
+// everything an item can be made of, a split off item gets a copy of each one its stack has.
+type ItemStorages<'a> = (
+    WriteStorage<'a, Renderable>,
+    WriteStorage<'a, Name>,
+    WriteStorage<'a, Info>,
+    WriteStorage<'a, Item>,
+    WriteStorage<'a, Equipable>,
+    WriteStorage<'a, CausesDamage>,
+    WriteStorage<'a, Launcher>,
+    WriteStorage<'a, Ammunition>,
+    WriteStorage<'a, Consumable>,
+    WriteStorage<'a, ProvidesHealing>,
+    WriteStorage<'a, Potion>,
+    WriteStorage<'a, Ranged>,
+    WriteStorage<'a, AreaOfEffect>,
+    WriteStorage<'a, Confusion>,
+    WriteStorage<'a, CausesFire>,
+    WriteStorage<'a, Trap>,
+    WriteStorage<'a, Armable>,
+    WriteStorage<'a, Weight>,
+    WriteStorage<'a, Identifies>,
+    WriteStorage<'a, Fuel>,
+    WriteStorage<'a, Explosive>,
+    WriteStorage<'a, Flammable>,
+    WriteStorage<'a, CausesLight>,
+);
+
+fn copy_component<T: Component + Clone>(
+    storage: &mut WriteStorage<T>,
+    source: Entity,
+    target: Entity,
+) {
+    if let Some(component) = storage.get(source).cloned() {
+        storage
+            .insert(target, component)
+            .expect("failed copying component for split stack");
+    }
+}
+
+fn copy_item_components(storages: &mut ItemStorages, source: Entity, target: Entity) {
+    let (
+        renderables,
+        names,
+        infos,
+        items,
+        equipables,
+        causes_damage,
+        launchers,
+        ammunition,
+        consumables,
+        provides_healing,
+        potions,
+        ranged,
+        areas_of_effect,
+        confusion,
+        causes_fire,
+        traps,
+        armables,
+        weights,
+        identifies,
+        fuel,
+        explosives,
+        flammables,
+        causes_light,
+    ) = storages;
+    copy_component(renderables, source, target);
+    copy_component(names, source, target);
+    copy_component(infos, source, target);
+    copy_component(items, source, target);
+    copy_component(equipables, source, target);
+    copy_component(causes_damage, source, target);
+    copy_component(launchers, source, target);
+    copy_component(ammunition, source, target);
+    copy_component(consumables, source, target);
+    copy_component(provides_healing, source, target);
+    copy_component(potions, source, target);
+    copy_component(ranged, source, target);
+    copy_component(areas_of_effect, source, target);
+    copy_component(confusion, source, target);
+    copy_component(causes_fire, source, target);
+    copy_component(traps, source, target);
+    copy_component(armables, source, target);
+    copy_component(weights, source, target);
+    copy_component(identifies, source, target);
+    copy_component(fuel, source, target);
+    copy_component(explosives, source, target);
+    copy_component(flammables, source, target);
+    copy_component(causes_light, source, target);
+}
+
+pub struct StackSpawnSystem {}
+impl<'a> System<'a> for StackSpawnSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, Position>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
+        ItemStorages<'a>,
+        WriteExpect<'a, StackSpawner>,
+        WriteExpect<'a, SimpleMarkerAllocator<Saveable>>,
+        WriteStorage<'a, SimpleMarker<Saveable>>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            mut positions,
+            mut inventories,
+            mut stackables,
+            mut item_storages,
+            mut spawner,
+            mut marker_allocator,
+            mut markers,
+        ) = data;
+        for request in spawner.requests.drain(..) {
+            if !entities.is_alive(request.source) {
+                continue;
+            }
+            let new_item = entities.create();
+            match request.destination {
+                StackDestination::Floor { idx, level } => {
+                    positions
+                        .insert(new_item, Position { idx, level })
+                        .expect("failed inserting position for split stack");
+                }
+                StackDestination::Inventory(owner) => {
+                    if let Some(inventory) = inventories.get_mut(owner) {
+                        inventory.items.insert(new_item);
+                    }
+                }
+            }
+            stackables
+                .insert(new_item, Stackable { quantity: 1 })
+                .expect("failed inserting stackable for split stack");
+            copy_item_components(&mut item_storages, request.source, new_item);
+            marker_allocator.mark(new_item, &mut markers);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::components::{Inventory, Name, Position, WantsToStudy};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, room_decorators::RoomType};
+use crate::identification::Identification;
+use crate::services::GameLog;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+// only rooms stocked with books and desks have what's needed to look things up.
+fn is_library(room_type: &Option<RoomType>) -> bool {
+    matches!(room_type, Some(RoomType::ClassRoom))
+}
+
+fn is_in_library(level: &Level, idx: usize) -> bool {
+    let (x, y) = level_utils::idx_xy(level.width as u32, idx);
+    level
+        .rooms
+        .iter()
+        .any(|room| is_library(&room.room_type) && room.rect.contains(x, y))
+}
+
+pub struct StudySystem {}
+
+impl<'a> System<'a> for StudySystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToStudy>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Inventory>,
+        ReadStorage<'a, Name>,
+        ReadExpect<'a, Dungeon>,
+        WriteExpect<'a, Identification>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut wants_to_study,
+            positions,
+            inventories,
+            names,
+            dungeon,
+            mut identification,
+        ) = data;
+        for (entity, _study, position, inventory) in
+            (&entities, &wants_to_study, &positions, &inventories).join()
+        {
+            if entity != *player_entity {
+                continue;
+            }
+            let level = dungeon.get_level(position.level).unwrap();
+            if !is_in_library(level, position.idx) {
+                log.add("There's nothing here to study with, find a library".to_string());
+                continue;
+            }
+            let unidentified = inventory
+                .items
+                .iter()
+                .filter_map(|item| names.get(*item))
+                .find(|name| !identification.is_identified(&name.name));
+            match unidentified {
+                Some(name) => {
+                    let appearance = identification.get_display_name(&name.name);
+                    identification.identify(&name.name);
+                    log.add(format!(
+                        "After some reading you learn the {} is a {}",
+                        appearance, name.name
+                    ));
+                }
+                None => log.add("You have nothing left to study".to_string()),
+            }
+        }
+        wants_to_study.clear();
+    }
+}
@@ -0,0 +1,40 @@
+use crate::dungeon::{dungeon::Dungeon, surface_type::SurfaceType, tile_type::TileType};
+use crate::services::SurfaceSpawner;
+use specs::{System, WriteExpect};
+
+// liquids only settle on open ground, and can't wash away water that's already there.
+fn can_cover(tile: &TileType, existing: &Option<SurfaceType>, new_surface: &SurfaceType) -> bool {
+    match tile {
+        TileType::Wall | TileType::Column | TileType::WaterDeep | TileType::Ledge => false,
+        _ => match existing {
+            Some(SurfaceType::Water) | Some(SurfaceType::Ice) => {
+                *new_surface == SurfaceType::Water || *new_surface == SurfaceType::Ice
+            }
+            _ => true,
+        },
+    }
+}
+
+pub struct SurfaceSpawnSystem {}
+
+impl<'a> System<'a> for SurfaceSpawnSystem {
+    type SystemData = (WriteExpect<'a, Dungeon>, WriteExpect<'a, SurfaceSpawner>);
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (mut dungeon, mut spawner) = data;
+        for request in spawner.requests.iter() {
+            if let Some(level) = dungeon.get_level_mut(request.level) {
+                let covers = match level.tiles.get(request.idx) {
+                    Some(tile) => {
+                        can_cover(tile, &level.surfaces[request.idx], &request.surface_type)
+                    }
+                    None => false,
+                };
+                if covers {
+                    level.surfaces[request.idx] = Some(request.surface_type);
+                }
+            }
+        }
+        spawner.requests.clear();
+    }
+}
@@ -0,0 +1,143 @@
+use crate::components::{
+    CausesLight, Dousable, Dripping, EntityMoved, Equipment, Lightable, Monster, Name, OnFire,
+    Position,
+};
+use crate::dungeon::{
+    dungeon::Dungeon,
+    surface_type::{self, SurfaceType},
+    tile_type::TileType,
+};
+use crate::services::GameLog;
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+const BLOODY_STEPS: u32 = 5;
+const DRIPPING_WATER_STEPS: u32 = 3;
+
+pub struct SurfaceSystem {}
+
+impl<'a> System<'a> for SurfaceSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, EntityMoved>,
+        ReadStorage<'a, Monster>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Equipment>,
+        WriteStorage<'a, CausesLight>,
+        WriteStorage<'a, Dousable>,
+        WriteStorage<'a, Lightable>,
+        WriteStorage<'a, OnFire>,
+        WriteStorage<'a, Dripping>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut game_log,
+            mut dungeon,
+            positions,
+            moved,
+            monsters,
+            names,
+            equipment,
+            mut causes_light,
+            mut dousables,
+            mut lightables,
+            mut on_fires,
+            mut drippings,
+        ) = data;
+        for (entity, position, _moved) in (&entities, &positions, &moved).join() {
+            let level = dungeon.get_level_mut(position.level).unwrap();
+            let idx = position.idx;
+            if level.tiles[idx] == TileType::WaterDeep {
+                // there's no keeping a flame dry while swimming.
+                if let Some(equipment) = equipment.get(entity) {
+                    for item in [equipment.dominant_hand, equipment.off_hand]
+                        .iter()
+                        .filter_map(|item| *item)
+                    {
+                        if let Some(light) = causes_light.get_mut(item) {
+                            if light.lit {
+                                light.lit = false;
+                                dousables.remove(item);
+                                lightables
+                                    .insert(item, Lightable {})
+                                    .expect("could not insert lightable");
+                                if entity == *player_entity {
+                                    let item_name = match names.get(item) {
+                                        Some(name) => name.name.clone(),
+                                        _ => "light".to_string(),
+                                    };
+                                    game_log
+                                        .add(format!("Your {} goes out in the water.", item_name));
+                                }
+                            }
+                        }
+                    }
+                }
+                drippings
+                    .insert(
+                        entity,
+                        Dripping {
+                            surface_type: SurfaceType::Water,
+                            steps: DRIPPING_WATER_STEPS,
+                        },
+                    )
+                    .expect("could not insert dripping");
+                continue;
+            }
+            match level.surfaces[idx] {
+                Some(SurfaceType::Blood) => {
+                    drippings
+                        .insert(
+                            entity,
+                            Dripping {
+                                surface_type: SurfaceType::Blood,
+                                steps: BLOODY_STEPS,
+                            },
+                        )
+                        .expect("could not insert dripping");
+                }
+                Some(_) => {}
+                None => {
+                    if let Some(dripping) = drippings.get_mut(entity) {
+                        match dripping.surface_type {
+                            SurfaceType::Water => level.surfaces[idx] = Some(SurfaceType::Water),
+                            // goblins only bother following the tracks of their prey.
+                            SurfaceType::Blood if monsters.get(entity).is_none() => {
+                                level.track_count += 1;
+                                level.tracks[idx] = level.track_count;
+                            }
+                            _ => {}
+                        }
+                        dripping.steps = dripping.steps.saturating_sub(1);
+                        if dripping.steps == 0 {
+                            drippings.remove(entity);
+                        }
+                    }
+                }
+            }
+        }
+
+        let extinguished: Vec<Entity> = (&entities, &on_fires, &positions)
+            .join()
+            .filter(|(_entity, _on_fire, position)| {
+                let level = dungeon.get_level(position.level).unwrap();
+                match (level.tiles[position.idx], level.surfaces[position.idx]) {
+                    (TileType::WaterDeep, _) => true,
+                    (_, Some(surface)) => surface_type::surface_extinguishes_fire(&surface),
+                    _ => false,
+                }
+            })
+            .map(|(entity, _on_fire, _position)| entity)
+            .collect();
+        for entity in extinguished {
+            on_fires.remove(entity);
+            causes_light.remove(entity);
+        }
+    }
+}
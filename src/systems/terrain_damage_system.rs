@@ -0,0 +1,73 @@
+use crate::components::{viewshed, Position, Viewshed};
+use crate::dungeon::{dungeon::Dungeon, level_utils, tile_type::TileType};
+use crate::services::{DebrisSpawner, GameLog, TerrainDamage};
+use specs::{Entity, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+use std::collections::HashSet;
+
+fn get_name_for_tile(tile_type: &TileType) -> &str {
+    match tile_type {
+        TileType::Wall => "wall",
+        TileType::Column => "column",
+        _ => "stone",
+    }
+}
+
+pub struct TerrainDamageSystem {}
+
+impl<'a> System<'a> for TerrainDamageSystem {
+    type SystemData = (
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteExpect<'a, Dungeon>,
+        WriteExpect<'a, TerrainDamage>,
+        WriteExpect<'a, DebrisSpawner>,
+        ReadStorage<'a, Position>,
+        WriteStorage<'a, Viewshed>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            player_entity,
+            mut log,
+            mut dungeon,
+            mut terrain_damage,
+            mut debris_spawner,
+            positions,
+            mut viewsheds,
+        ) = data;
+        let player_level = positions.get(*player_entity).unwrap().level;
+        let mut levels_with_broken_tiles = HashSet::new();
+        for request in terrain_damage.requests.iter() {
+            let level = match dungeon.get_level_mut(request.level) {
+                Some(level) => level,
+                None => continue,
+            };
+            if !level_utils::tile_is_breakable(level, request.idx) {
+                continue;
+            }
+            level.tile_hp[request.idx] -= request.amount;
+            if level.tile_hp[request.idx] > 0 {
+                continue;
+            }
+            // broken stone falls as rubble, opening the way through.
+            let tile_name = get_name_for_tile(&level.tiles[request.idx]).to_string();
+            level.tile_hp[request.idx] = 0;
+            level_utils::set_tile_to_floor(level, request.idx);
+            level.blocked[request.idx] = false;
+            level.opaque[request.idx] = false;
+            debris_spawner.request_rubble(request.idx, request.level);
+            levels_with_broken_tiles.insert(request.level);
+            let seen_by_player = match viewsheds.get(*player_entity) {
+                Some(viewshed) => {
+                    request.level == player_level && viewshed.visible_tiles.contains(&request.idx)
+                }
+                None => false,
+            };
+            if seen_by_player {
+                log.add(format!("The {} crumbles into rubble", tile_name));
+            }
+        }
+        terrain_damage.requests.clear();
+        viewshed::mark_dirty_on_levels(&levels_with_broken_tiles, &positions, &mut viewsheds);
+    }
+}
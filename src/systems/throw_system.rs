@@ -0,0 +1,306 @@
+use crate::components::{
+    CausesLight, CombatStats, Confused, Confusion, Explosive, Flammable, Fuel, Inventory, Memory,
+    Monster, Name, OnFire, Position, Potion, ProvidesHealing, Stackable, WantsToThrow,
+};
+use crate::dungeon::{dungeon::Dungeon, level::Level, level_utils, surface_type::SurfaceType};
+use crate::identification::Identification;
+use crate::noise::make_noise;
+use crate::services::{
+    ExplosionSpawner, GameLog, ParticleEffectSpawner, StackSpawner, SurfaceSpawner,
+};
+use rltk::{to_cp437, BLACK, GOLD, LIGHT_BLUE, MAGENTA, ORANGE, RED, RGB, WHITE};
+use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
+
+const POTION_SPLASH_RADIUS: u32 = 1;
+const OIL_SPLASH_RADIUS: u32 = 1;
+const NOISE_RADIUS: f32 = 10.0;
+
+// thrown items travel in a straight line and drop to the floor in front of anything that blocks
+// them. Something standing on the target tile itself is hit, rather than thrown short of.
+fn get_landing_idx(level: &Level, start_idx: usize, target_idx: usize) -> usize {
+    let mut landing_idx = start_idx;
+    for idx in level_utils::get_line_between_idxs(level, start_idx, target_idx) {
+        if level.opaque[idx] {
+            break;
+        }
+        if level.blocked[idx] {
+            if idx == target_idx && !level.tile_content[idx].is_empty() {
+                landing_idx = idx;
+            }
+            break;
+        }
+        landing_idx = idx;
+        if idx == target_idx {
+            break;
+        }
+    }
+    landing_idx
+}
+
+pub struct ThrowSystem {}
+
+impl<'a> System<'a> for ThrowSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, Entity>,
+        WriteExpect<'a, GameLog>,
+        WriteStorage<'a, WantsToThrow>,
+        ReadStorage<'a, Name>,
+        WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Position>,
+        ReadExpect<'a, Dungeon>,
+        WriteStorage<'a, CombatStats>,
+        ReadStorage<'a, Potion>,
+        ReadStorage<'a, ProvidesHealing>,
+        ReadStorage<'a, Confusion>,
+        WriteStorage<'a, Confused>,
+        WriteStorage<'a, CausesLight>,
+        ReadStorage<'a, Flammable>,
+        WriteStorage<'a, OnFire>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Memory>,
+        WriteExpect<'a, ParticleEffectSpawner>,
+        WriteStorage<'a, Stackable>,
+        WriteExpect<'a, StackSpawner>,
+        ReadExpect<'a, Identification>,
+        ReadStorage<'a, Fuel>,
+        WriteExpect<'a, SurfaceSpawner>,
+        ReadStorage<'a, Explosive>,
+        WriteExpect<'a, ExplosionSpawner>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            entities,
+            player_entity,
+            mut log,
+            mut wants_to_throw,
+            names,
+            mut inventories,
+            mut positions,
+            dungeon,
+            mut combat_stats,
+            potions,
+            provides_healing,
+            causes_confusion,
+            mut confused,
+            mut causes_light,
+            flammables,
+            mut on_fire,
+            monsters,
+            mut memories,
+            mut particle_spawner,
+            mut stackables,
+            mut stack_spawner,
+            identification,
+            fuels,
+            mut surface_spawner,
+            explosives,
+            mut explosion_spawner,
+        ) = data;
+        for (entity, to_throw, inventory) in (&entities, &wants_to_throw, &mut inventories).join() {
+            let (thrower_idx, level_number) = {
+                let position = positions.get(entity).unwrap();
+                (position.idx, position.level)
+            };
+            let level = dungeon.get_level(level_number).unwrap();
+            let item = to_throw.item;
+            let item_name = identification.get_display_name(&names.get(item).unwrap().name);
+            let landing_idx = get_landing_idx(level, thrower_idx, to_throw.target);
+            // only a single item is thrown from a stack, the rest stays in the inventory
+            let from_stack = match stackables.get_mut(item) {
+                Some(stack) if stack.quantity > 1 => {
+                    stack.quantity -= 1;
+                    true
+                }
+                _ => false,
+            };
+            if !from_stack {
+                inventory.items.remove(&item);
+            }
+            for idx in level_utils::get_line_between_idxs(level, thrower_idx, landing_idx) {
+                particle_spawner.request(
+                    idx,
+                    RGB::named(WHITE),
+                    RGB::named(BLACK),
+                    to_cp437('*'),
+                    100.0,
+                    level_number,
+                );
+            }
+
+            if potions.get(item).is_some() {
+                // potions shatter, splashing their contents over everything close by
+                if entity == *player_entity {
+                    log.add(format!("The {} shatters", item_name));
+                }
+                let splashed = level_utils::get_field_of_view_from_idx(
+                    level,
+                    landing_idx,
+                    POTION_SPLASH_RADIUS,
+                );
+                for idx in splashed.iter() {
+                    if level_utils::idx_not_in_map(level, *idx) {
+                        continue;
+                    }
+                    particle_spawner.request(
+                        *idx,
+                        RGB::named(LIGHT_BLUE),
+                        RGB::named(BLACK),
+                        to_cp437('░'),
+                        200.0,
+                        level_number,
+                    );
+                    for target in level_utils::entities_at_idx(level, *idx) {
+                        if let Some(heals) = provides_healing.get(item) {
+                            if let Some(stats) = combat_stats.get_mut(target) {
+                                stats.hp = i32::min(stats.max_hp, stats.hp + heals.amount);
+                                particle_spawner.request(
+                                    *idx,
+                                    RGB::named(RED),
+                                    RGB::named(BLACK),
+                                    to_cp437('♥'),
+                                    200.0,
+                                    level_number,
+                                );
+                            }
+                        }
+                        if let Some(confuses) = causes_confusion.get(item) {
+                            if combat_stats.get(target).is_some() {
+                                confused
+                                    .insert(
+                                        target,
+                                        Confused {
+                                            turns: confuses.turns,
+                                        },
+                                    )
+                                    .expect("Failed to confuse target");
+                                particle_spawner.request(
+                                    *idx,
+                                    RGB::named(MAGENTA),
+                                    RGB::named(BLACK),
+                                    to_cp437('?'),
+                                    200.0,
+                                    level_number,
+                                );
+                            }
+                        }
+                    }
+                }
+                if !from_stack {
+                    entities
+                        .delete(item)
+                        .expect("failed deleting shattered potion");
+                }
+            } else if let Some(explosive) = explosives.get(item) {
+                // bombs go off the moment they hit the ground
+                explosion_spawner.request(
+                    landing_idx,
+                    level_number,
+                    explosive.radius,
+                    explosive.damage,
+                );
+                if !from_stack {
+                    entities
+                        .delete(item)
+                        .expect("failed deleting exploded bomb");
+                }
+            } else if fuels.get(item).is_some() {
+                // flasks of oil break open and leave a slick waiting for a spark
+                if entity == *player_entity {
+                    log.add(format!("The {} shatters, spilling oil", item_name));
+                }
+                let splashed =
+                    level_utils::get_field_of_view_from_idx(level, landing_idx, OIL_SPLASH_RADIUS);
+                for idx in splashed.iter() {
+                    if level_utils::idx_not_in_map(level, *idx) {
+                        continue;
+                    }
+                    particle_spawner.request(
+                        *idx,
+                        RGB::named(GOLD),
+                        RGB::named(BLACK),
+                        to_cp437('░'),
+                        200.0,
+                        level_number,
+                    );
+                    surface_spawner.request(*idx, level_number, SurfaceType::Oil);
+                }
+                if !from_stack {
+                    entities
+                        .delete(item)
+                        .expect("failed deleting shattered flask");
+                }
+            } else {
+                let is_lit = match causes_light.get(item) {
+                    Some(light) => light.lit,
+                    None => false,
+                };
+                if is_lit {
+                    for target in level_utils::entities_at_idx(level, landing_idx) {
+                        if let Some(f) = flammables.get(target) {
+                            on_fire
+                                .insert(target, OnFire {})
+                                .expect("couldn't light target on fire");
+                            causes_light
+                                .insert(
+                                    target,
+                                    CausesLight {
+                                        radius: 3,
+                                        color: RGB::named(RED),
+                                        lit: true,
+                                        turns_remaining: Some(f.turns_remaining as u32),
+                                    },
+                                )
+                                .expect("couldn't insert cause light for target");
+                            particle_spawner.request(
+                                landing_idx,
+                                RGB::named(ORANGE),
+                                RGB::named(RED),
+                                to_cp437('░'),
+                                200.0,
+                                level_number,
+                            );
+                            if entity == *player_entity {
+                                if let Some(target_name) = names.get(target) {
+                                    log.add(format!(
+                                        "The {} sets the {} alight",
+                                        item_name, target_name.name
+                                    ));
+                                }
+                            }
+                        }
+                    }
+                }
+                if from_stack {
+                    stack_spawner.request(landing_idx, level_number, item);
+                } else {
+                    positions
+                        .insert(
+                            item,
+                            Position {
+                                idx: landing_idx,
+                                level: level_number,
+                            },
+                        )
+                        .expect("failed inserting position for thrown item");
+                }
+                if entity == *player_entity {
+                    log.add(format!("The {} clatters to the ground", item_name));
+                }
+            }
+
+            // the noise of the landing draws the attention of anything close enough to hear it
+            make_noise(
+                level,
+                landing_idx,
+                NOISE_RADIUS,
+                &positions,
+                &monsters,
+                &mut memories,
+                Some(entity),
+            );
+        }
+        wants_to_throw.clear();
+    }
+}
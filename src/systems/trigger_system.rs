@@ -1,4 +1,4 @@
-use crate::components::{CausesDamage, DamageHistory, EntityMoved, EntryTrigger, Hidden, Name, Position, SufferDamage, Triggered, Viewshed};
+use crate::components::{Activated, CausesDamage, DamageHistory, EntityMoved, EntryTrigger, Hidden, Name, Position, SufferDamage, Triggered, Viewshed, WiredTo};
 use crate::dungeon::{dungeon::Dungeon, level_utils};
 use crate::services::{GameLog, ParticleEffectSpawner};
 use rltk::RandomNumberGenerator;
@@ -27,6 +27,8 @@ impl<'a> System<'a> for TriggerSystem {
         WriteExpect<'a, GameLog>,
         Entities<'a>,
         WriteExpect<'a, RandomNumberGenerator>,
+        ReadStorage<'a, WiredTo>,
+        WriteStorage<'a, Activated>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
@@ -47,6 +49,8 @@ impl<'a> System<'a> for TriggerSystem {
             mut log,
             ents,
             mut rng,
+            wired_to,
+            mut activated,
         ) = data;
         let player_level = positions.get(*player_ent).unwrap().level;
         let player_viewshed = viewsheds.get(*player_ent).unwrap();
@@ -80,6 +84,14 @@ impl<'a> System<'a> for TriggerSystem {
                             damage_history.events.insert(*damage_type);
                         }
                     }
+                    // whatever the trigger is wired to goes off wherever it is on the level.
+                    if let Some(wiring) = wired_to.get(*maybe_triggered) {
+                        for target in wiring.targets.iter() {
+                            activated
+                                .insert(*target, Activated { by: entity })
+                                .expect("could not insert activated for mechanism");
+                        }
+                    }
                     hidden.remove(*maybe_triggered);
                     triggered
                         .insert(*maybe_triggered, Triggered {})
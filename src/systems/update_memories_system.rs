@@ -1,4 +1,6 @@
 use crate::components::{memory::MemoryLocation, Hiding, Memory, Position, Viewshed, WantsToHide};
+use crate::dungeon::dungeon::Dungeon;
+use crate::perception;
 use specs::{Entity, Join, ReadExpect, ReadStorage, System, WriteStorage};
 pub struct UpdateMemoriesSystem {}
 
@@ -10,15 +12,23 @@ impl<'a> System<'a> for UpdateMemoriesSystem {
         ReadStorage<'a, Position>,
         ReadStorage<'a, WantsToHide>,
         ReadStorage<'a, Hiding>,
+        ReadExpect<'a, Dungeon>,
     );
 
     fn run(&mut self, data: Self::SystemData) {
-        let (mut memories, viewsheds, player_entity, positions, hide_intents, hiding) = data;
+        let (mut memories, viewsheds, player_entity, positions, hide_intents, hiding, dungeon) =
+            data;
         let player_position = positions.get(*player_entity).unwrap();
+        let player_level = dungeon.get_level(player_position.level).unwrap();
         for (memory, viewshed, position) in (&mut memories, &viewsheds, &positions).join() {
             if hiding.get(*player_entity).is_none()
                 && position.level == player_position.level
-                && viewshed.visible_tiles.contains(&player_position.idx)
+                && perception::can_spot(
+                    player_level,
+                    position.idx,
+                    &viewshed.visible_tiles,
+                    player_position.idx,
+                )
             {
                 memory.last_known_enemy_positions.insert(
                     *player_entity,
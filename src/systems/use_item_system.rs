@@ -1,9 +1,11 @@
 use crate::components::{
     AreaOfEffect, CausesDamage, CausesFire, CausesLight, CombatStats, Confused, Confusion,
     Consumable, DamageHistory, Flammable, Name, OnFire, Position, ProvidesHealing, SufferDamage,
-    WantsToUse, Inventory
+    WantsToUse, Inventory, Stackable, Identifies
 };
 use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::identification::Identification;
+use crate::inventory::consume_one;
 use crate::services::{GameLog, ParticleEffectSpawner};
 use rltk::{RandomNumberGenerator, BLACK, MAGENTA, ORANGE, RED, RGB};
 use specs::{
@@ -37,6 +39,9 @@ impl<'a> System<'a> for UseItemSystem {
         WriteStorage<'a, CausesLight>,
         WriteStorage<'a, DamageHistory>,
         WriteStorage<'a, Inventory>,
+        WriteStorage<'a, Stackable>,
+        ReadStorage<'a, Identifies>,
+        WriteExpect<'a, Identification>,
         WriteExpect<'a, RandomNumberGenerator>,
     );
     fn run(&mut self, data: Self::SystemData) {
@@ -63,6 +68,9 @@ impl<'a> System<'a> for UseItemSystem {
             mut causes_light,
             mut damage_histories,
             mut inventories,
+            mut stackables,
+            identifies,
+            mut identification,
             mut rng,
         ) = data;
         let player_position = positions.get(*player_entity).unwrap();
@@ -106,6 +114,29 @@ impl<'a> System<'a> for UseItemSystem {
                 },
             };
 
+            if entity == *player_entity {
+                // using an item is the surest way to learn what it is
+                let item_name = &names.get(to_use.item).unwrap().name;
+                let appearance = identification.get_display_name(item_name);
+                if identification.identify(item_name) {
+                    game_log.add(format!("The {} was a {}.", appearance, item_name));
+                }
+                if identifies.get(to_use.item).is_some() {
+                    let mut learned_something = false;
+                    for item in inventory.items.iter() {
+                        let name = &names.get(*item).unwrap().name;
+                        let appearance = identification.get_display_name(name);
+                        if identification.identify(name) {
+                            game_log.add(format!("You learn the {} is a {}.", appearance, name));
+                            learned_something = true;
+                        }
+                    }
+                    if !learned_something {
+                        game_log.add("You learn nothing new.".to_string());
+                    }
+                }
+            }
+
             let heals = provides_healing.get(to_use.item);
             let damages = causes_damage.get(to_use.item);
             let confuses = causes_confusion.get(to_use.item);
@@ -122,6 +153,7 @@ impl<'a> System<'a> for UseItemSystem {
                                 target,
                                 CausesLight {
                                     radius: 3,
+                                    color: RGB::named(RED),
                                     lit: true,
                                     turns_remaining: Some(f.turns_remaining as u32),
                                 },
@@ -217,8 +249,7 @@ impl<'a> System<'a> for UseItemSystem {
             }
 
             if let Some(_) = consumables.get(to_use.item) {
-                inventory.items.remove(&to_use.item);
-                entities.delete(to_use.item).expect("Delete Failed");
+                consume_one(to_use.item, inventory, &mut stackables, &entities);
             };
         }
         wants_to_use.clear();
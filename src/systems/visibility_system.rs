@@ -1,11 +1,10 @@
 use crate::dungeon::{dungeon::Dungeon, level_utils};
+use crate::perception;
 use crate::{
     components::{Monster, Position, Viewshed},
     player::InteractionType,
 };
 use specs::{Entities, Entity, Join, ReadExpect, ReadStorage, System, WriteExpect, WriteStorage};
-use std::collections::HashSet;
-use std::iter::FromIterator;
 
 pub struct VisibilitySystem<'a> {
     pub queued_action: &'a mut Option<(Entity, InteractionType)>,
@@ -46,28 +45,8 @@ impl<'a> System<'a> for VisibilitySystem<'a> {
                         viewshed.range,
                     );
                 }
-                let lit_indexes =
-                    level
-                        .lit_tiles
-                        .iter()
-                        .enumerate()
-                        .filter_map(|(idx, lit)| match lit {
-                            true => Some(idx),
-                            false => None,
-                        });
-                viewshed.visible_tiles = viewshed
-                    .los_tiles
-                    .clone()
-                    .intersection(&HashSet::from_iter(lit_indexes))
-                    .copied()
-                    .collect::<HashSet<usize>>()
-                    .union(&level_utils::get_field_of_view_from_idx(
-                        &*level,
-                        position.idx,
-                        2,
-                    ))
-                    .copied()
-                    .collect();
+                viewshed.visible_tiles =
+                    perception::get_visible_tiles(&*level, position.idx, &viewshed.los_tiles);
 
                 if ent == *player_ent {
                     for idx in viewshed.visible_tiles.iter() {
@@ -41,6 +41,13 @@ pub fn get_range_for_item(item_type: &ItemType) -> Option<u32> {
     }
 }
 
+pub fn get_weight_for_item(item_type: &ItemType) -> f32 {
+    match item_type {
+        ItemType::Caltrops => 1.0,
+        ItemType::BearTrap => 4.0,
+    }
+}
+
 pub fn get_trap_type_for_item(item_type: &ItemType) -> Option<TrapType> {
     match item_type {
         ItemType::Caltrops => Some(TrapType::Caltrops),
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Material {
+    Wood,
+    Cloth,
+}
+
+// the percentage chance that something will catch from each burning tile next to it in a turn.
+pub fn get_ignition_chance(material: &Material) -> i32 {
+    match material {
+        Material::Wood => 25,
+        Material::Cloth => 60,
+    }
+}
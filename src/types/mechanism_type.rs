@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum MechanismType {
+    DartLauncher,
+    Portcullis,
+    AlarmBell,
+    CollapsingFloor,
+}
+
+pub fn get_name_for_mechanism(mechanism_type: &MechanismType) -> String {
+    match mechanism_type {
+        MechanismType::DartLauncher => "Dart Launcher".to_string(),
+        MechanismType::Portcullis => "Portcullis".to_string(),
+        MechanismType::AlarmBell => "Alarm Bell".to_string(),
+        MechanismType::CollapsingFloor => "Loose Flagstones".to_string(),
+    }
+}
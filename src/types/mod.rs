@@ -1,7 +1,13 @@
 pub mod equip_menu_type;
 pub mod item_type;
+pub mod material;
+pub mod mechanism_type;
 pub mod trap_type;
+pub mod workstation_type;
 
 pub use equip_menu_type::EquipMenuType;
 pub use item_type::ItemType;
+pub use material::Material;
+pub use mechanism_type::MechanismType;
 pub use trap_type::TrapType;
+pub use workstation_type::WorkstationType;
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WorkstationType {
+    Counter,
+    Desk,
+    Stove,
+    WeaponRack,
+}
+
+pub fn get_verb_for_workstation(workstation_type: &WorkstationType) -> String {
+    match workstation_type {
+        WorkstationType::Counter => "Assemble".to_string(),
+        WorkstationType::Desk => "Write".to_string(),
+        WorkstationType::Stove => "Brew".to_string(),
+        WorkstationType::WeaponRack => "Forge".to_string(),
+    }
+}
@@ -28,6 +28,8 @@ pub enum MapAction {
     GrabFurniture,
     ReleaseFurniture,
     Attack,
+    Fire,
+    Throw,
     Hide,
     GoDownStairs,
     GoUpStairs,
@@ -65,6 +67,8 @@ impl MapAction {
             Self::GrabFurniture,
             Self::ReleaseFurniture,
             Self::Attack,
+            Self::Fire,
+            Self::Throw,
             Self::Hide,
             Self::GoDownStairs,
             Self::GoUpStairs,
@@ -109,6 +113,8 @@ impl Display for MapAction {
                 MapAction::GrabFurniture => "Grab Furniture",
                 MapAction::ReleaseFurniture => "Release Furniture",
                 MapAction::Attack => "Attack",
+                MapAction::Fire => "Fire",
+                MapAction::Throw => "Throw",
                 MapAction::Hide => "Hide",
                 MapAction::GoDownStairs => "Go Downstairs",
                 MapAction::GoUpStairs => "Go Upstairs",